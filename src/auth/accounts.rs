@@ -0,0 +1,456 @@
+// This is where accounts are managed.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// Account list is stored in .copper-launcher/accounts.json, it has no tokens in it
+// Tokens are kept in secret store, see store.rs
+// Microsoft logins are refreshed in background before they expire
+// authlib-injector sessions are checked and refreshed when game is launched
+
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::auth::authlib_injector::{self, YggdrasilSession};
+use crate::auth::microsoft::{self, MicrosoftAccount, MicrosoftAuth};
+use crate::auth::offline;
+use crate::auth::store;
+use crate::launcher::config;
+use crate::minecraft::game_launch::LaunchAccount;
+
+
+// Account list - .copper-launcher/accounts.json
+const ACCOUNTS_FILE: &str = "accounts.json";
+
+// Logins expiring sooner than this are refreshed
+const REFRESH_MARGIN: u64 = 60 * 60;
+
+// How often background refresh checks accounts
+const REFRESH_CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+// Accounts are loaded, changed and saved while holding this lock
+// Background refresh runs on its own thread
+static ACCOUNTS_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AccountKind {
+    Microsoft,
+    Offline,
+    AuthlibInjector,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Account {
+    pub id: String,
+    pub kind: AccountKind,
+    pub username: String,
+    pub uuid: String,
+    // Yggdrasil API root of authlib-injector accounts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_root: Option<String>,
+}
+
+impl Account {
+    pub fn label(&self) -> String {
+        match self.kind {
+            AccountKind::Microsoft => format!("{} (Microsoft)", self.username),
+            AccountKind::Offline => format!("{} (Offline)", self.username),
+            AccountKind::AuthlibInjector => {
+                let server = self.api_root
+                    .as_deref()
+                    .and_then(|a| reqwest::Url::parse(a).ok())
+                    .and_then(|u| u.host_str().map(|h| h.to_string()))
+                    .unwrap_or_default();
+                format!("{} ({})", self.username, server)
+            }
+        }
+    }
+}
+
+// Parts of account that never leave secret store
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct AccountSecrets {
+    pub access_token: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    // Yggdrasil sessions are tied to client token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_token: Option<String>,
+    pub xuid: String,
+    // Unix time when access token stops working
+    pub expires_at: u64,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct AccountsFile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    active: Option<String>,
+    #[serde(default)]
+    accounts: Vec<Account>,
+}
+
+#[derive(Debug, Default)]
+pub struct Accounts {
+    pub active: Option<String>,
+    pub accounts: Vec<Account>,
+    secrets: HashMap<String, AccountSecrets>,
+    // Secret store could not be read, it is not written until it can be read again
+    secrets_lost: bool,
+}
+
+
+fn unix_time() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
+impl Accounts {
+    fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let accounts_path = config::launcher_path().join(ACCOUNTS_FILE);
+        let accounts_file: AccountsFile = match fs::read_to_string(&accounts_path) {
+            Ok(content) => serde_json::from_str(&content)?,
+            Err(_) => AccountsFile::default(),
+        };
+
+        // Account list stays usable without secrets, accounts just have to log in again
+        let secrets = store::secret_store(config::load_config().secret_service)
+            .load()
+            .and_then(|secrets| Ok(match secrets {
+                Some(secrets) => serde_json::from_slice(&secrets)?,
+                None => HashMap::new(),
+            }));
+        let (secrets, secrets_lost) = match secrets {
+            Ok(secrets) => (secrets, false),
+            Err(e) => {
+                eprintln!("Failed to load account secrets: {}", e);
+                (HashMap::new(), true)
+            }
+        };
+
+        Ok(Accounts {
+            active: accounts_file.active,
+            accounts: accounts_file.accounts,
+            secrets,
+            secrets_lost,
+        })
+    }
+
+    fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        // Saving would replace secrets of all other accounts with just the new ones
+        // Offline accounts have no secrets, so they can still be added
+        if self.secrets_lost && self.secrets.values().any(|s| !s.access_token.is_empty()) {
+            return Err("Account secrets could not be read, unlock the secret store and try again".into());
+        }
+
+        let accounts_file = AccountsFile {
+            active: self.active.clone(),
+            accounts: self.accounts.clone(),
+        };
+        fs::write(config::launcher_path().join(ACCOUNTS_FILE), serde_json::to_string_pretty(&accounts_file)?)?;
+
+        if !self.secrets_lost {
+            store::secret_store(config::load_config().secret_service).save(&serde_json::to_vec(&self.secrets)?)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Account> {
+        self.accounts.iter().find(|a| a.id == id)
+    }
+
+    // Active account, first account when none was chosen
+    pub fn active_account(&self) -> Option<&Account> {
+        self.active.as_deref().and_then(|id| self.get(id)).or_else(|| self.accounts.first())
+    }
+
+    // Adds account or replaces account with same id
+    fn insert(&mut self, account: Account, secrets: AccountSecrets) {
+        self.secrets.insert(account.id.clone(), secrets);
+        self.active = Some(account.id.clone());
+
+        match self.accounts.iter_mut().find(|a| a.id == account.id) {
+            Some(existing) => *existing = account,
+            None => self.accounts.push(account),
+        }
+    }
+
+    pub fn add_microsoft(&mut self, microsoft_account: MicrosoftAccount) -> String {
+        let account = Account {
+            id: microsoft_account.profile.id.clone(),
+            kind: AccountKind::Microsoft,
+            username: microsoft_account.profile.name.clone(),
+            uuid: microsoft::hyphenated_uuid(&microsoft_account.profile.id),
+            api_root: None,
+        };
+        let id = account.id.clone();
+
+        self.insert(account, AccountSecrets {
+            access_token: microsoft_account.access_token,
+            refresh_token: microsoft_account.refresh_token,
+            client_token: None,
+            xuid: microsoft_account.xuid,
+            expires_at: microsoft_account.expires_at,
+        });
+
+        id
+    }
+
+    // Name has to follow Minecraft rules, see offline.rs
+    pub fn add_offline(&mut self, username: &str) -> Result<String, String> {
+        offline::validate_name(username)?;
+
+        let account = Account {
+            id: format!("offline:{}", username),
+            kind: AccountKind::Offline,
+            username: username.to_string(),
+            uuid: offline::offline_uuid(username),
+            api_root: None,
+        };
+        let id = account.id.clone();
+
+        self.insert(account, AccountSecrets::default());
+
+        Ok(id)
+    }
+
+    pub fn add_authlib_injector(&mut self, session: YggdrasilSession) -> String {
+        let account = Account {
+            id: format!("{}:{}", session.api_root, session.profile.id),
+            kind: AccountKind::AuthlibInjector,
+            username: session.profile.name.clone(),
+            uuid: microsoft::hyphenated_uuid(&session.profile.id),
+            api_root: Some(session.api_root),
+        };
+        let id = account.id.clone();
+
+        self.insert(account, AccountSecrets {
+            access_token: session.access_token,
+            refresh_token: None,
+            client_token: Some(session.client_token),
+            xuid: "0".to_string(),
+            expires_at: 0,
+        });
+
+        id
+    }
+
+    pub fn remove(&mut self, id: &str) {
+        self.accounts.retain(|a| a.id != id);
+        self.secrets.remove(id);
+
+        if self.active.as_deref() == Some(id) {
+            self.active = self.accounts.first().map(|a| a.id.clone());
+        }
+    }
+
+    pub fn set_active(&mut self, id: &str) {
+        if self.get(id).is_some() {
+            self.active = Some(id.to_string());
+        }
+    }
+
+    // Replaces tokens of Microsoft account with new login
+    // Profile id stays the same, name may have changed
+    fn replace_microsoft(&mut self, microsoft_account: MicrosoftAccount) {
+        let active = self.active.clone();
+        self.add_microsoft(microsoft_account);
+        self.active = active;
+    }
+
+    fn needs_refresh(&self, account: &Account) -> bool {
+        account.kind == AccountKind::Microsoft
+            && self.secrets.get(&account.id).is_none_or(|s| s.expires_at <= unix_time() + REFRESH_MARGIN)
+    }
+}
+
+
+// New Microsoft login from refresh token, takes several requests
+fn refresh_microsoft(auth: &MicrosoftAuth, refresh_token: &str) -> Result<MicrosoftAccount, Box<dyn std::error::Error>> {
+    let microsoft_token = auth.refresh(refresh_token)?;
+    auth.login(&microsoft_token)
+}
+
+// Loads accounts, runs change and saves them while holding lock
+pub fn update<T>(change: impl FnOnce(&mut Accounts) -> T) -> Result<T, Box<dyn std::error::Error>> {
+    let _lock = ACCOUNTS_LOCK.lock().map_err(|_| "Accounts lock is poisoned")?;
+
+    let mut accounts = Accounts::load()?;
+    let result = change(&mut accounts);
+    accounts.save()?;
+
+    Ok(result)
+}
+
+// Accounts without secrets, for showing them
+pub fn list() -> Result<Accounts, Box<dyn std::error::Error>> {
+    let _lock = ACCOUNTS_LOCK.lock().map_err(|_| "Accounts lock is poisoned")?;
+
+    let accounts = Accounts::load()?;
+    Ok(Accounts {
+        active: accounts.active,
+        accounts: accounts.accounts,
+        secrets: HashMap::new(),
+        secrets_lost: accounts.secrets_lost,
+    })
+}
+
+// Account and its secrets, None when account was removed
+fn account_with_secrets(accounts: &Accounts, id: &str) -> Option<(Account, AccountSecrets)> {
+    let account = accounts.get(id)?.clone();
+    let secrets = accounts.secrets.get(id).cloned().unwrap_or_default();
+    Some((account, secrets))
+}
+
+// Account the game is launched with, expired Microsoft login is refreshed first
+// Lock is not held during login requests, same as in refresh_expiring
+pub fn launch_account(id: &str) -> Result<LaunchAccount, Box<dyn std::error::Error>> {
+    let (mut account, mut secrets) = {
+        let _lock = ACCOUNTS_LOCK.lock().map_err(|_| "Accounts lock is poisoned")?;
+        account_with_secrets(&Accounts::load()?, id).ok_or("Account does not exist")?
+    };
+
+    if account.kind == AccountKind::Microsoft && secrets.expires_at <= unix_time() {
+        let refresh_token = secrets.refresh_token.clone().ok_or("Account has no refresh token, log in again")?;
+        let auth = MicrosoftAuth::new(&config::load_config().microsoft)?;
+        let microsoft_account = refresh_microsoft(&auth, &refresh_token)?;
+
+        // Account removed during refresh stays removed
+        (account, secrets) = update(|accounts| {
+            if accounts.get(id).is_some() {
+                accounts.replace_microsoft(microsoft_account);
+            }
+            account_with_secrets(accounts, id)
+        })?
+        .ok_or("Account does not exist")?;
+    }
+
+    // Yggdrasil tokens have no known lifetime, server is asked
+    if account.kind == AccountKind::AuthlibInjector {
+        let api_root = account.api_root.clone().ok_or("Account has no auth server")?;
+        let client_token = secrets.client_token.clone().unwrap_or_default();
+
+        if !authlib_injector::validate(&api_root, &secrets.access_token, &client_token) {
+            let session = authlib_injector::refresh(&api_root, &secrets.access_token, &client_token, None)
+                .map_err(|e| format!("Session expired, log in again: {}", e))?;
+
+            (account, secrets) = update(|accounts| {
+                if accounts.get(id).is_some() {
+                    let active = accounts.active.clone();
+                    accounts.add_authlib_injector(session);
+                    accounts.active = active;
+                }
+                account_with_secrets(accounts, id)
+            })?
+            .ok_or("Account does not exist")?;
+        }
+    }
+
+    Ok(match account.kind {
+        AccountKind::Microsoft => LaunchAccount {
+            username: account.username.clone(),
+            uuid: account.uuid.clone(),
+            access_token: secrets.access_token,
+            xuid: secrets.xuid,
+            user_type: "msa".to_string(),
+            jvm_arguments: Vec::new(),
+        },
+        AccountKind::Offline => LaunchAccount::offline(&account.username),
+        AccountKind::AuthlibInjector => LaunchAccount {
+            username: account.username.clone(),
+            uuid: account.uuid.clone(),
+            access_token: secrets.access_token,
+            xuid: "0".to_string(),
+            user_type: "mojang".to_string(),
+            jvm_arguments: authlib_injector::jvm_arguments(account.api_root.as_deref().unwrap_or_default())?,
+        },
+    })
+}
+
+// Refreshes Microsoft logins that expire soon, returns number of refreshed accounts
+// Lock is not held during login requests, UI reads accounts in the meantime
+pub fn refresh_expiring() -> Result<usize, Box<dyn std::error::Error>> {
+    let expiring: Vec<(String, Option<String>)> = {
+        let _lock = ACCOUNTS_LOCK.lock().map_err(|_| "Accounts lock is poisoned")?;
+
+        let accounts = Accounts::load()?;
+        accounts.accounts
+            .iter()
+            .filter(|a| accounts.needs_refresh(a))
+            .map(|a| (a.id.clone(), accounts.secrets.get(&a.id).and_then(|s| s.refresh_token.clone())))
+            .collect()
+    };
+
+    if expiring.is_empty() {
+        return Ok(0);
+    }
+
+    let auth = MicrosoftAuth::new(&config::load_config().microsoft)?;
+    let mut logins = Vec::new();
+    for (id, refresh_token) in expiring {
+        let Some(refresh_token) = refresh_token else {
+            eprintln!("Account {} has no refresh token, log in again", id);
+            continue;
+        };
+
+        match refresh_microsoft(&auth, &refresh_token) {
+            Ok(microsoft_account) => logins.push(microsoft_account),
+            Err(e) => eprintln!("Failed to refresh account {}: {}", id, e),
+        }
+    }
+
+    if logins.is_empty() {
+        return Ok(0);
+    }
+
+    // Accounts removed during refresh stay removed
+    update(|accounts| {
+        let mut refreshed = 0;
+        for microsoft_account in logins {
+            if accounts.get(&microsoft_account.profile.id).is_some() {
+                accounts.replace_microsoft(microsoft_account);
+                refreshed += 1;
+            }
+        }
+        refreshed
+    })
+}
+
+// Keeps Microsoft logins fresh while launcher is running
+pub fn spawn_refresh_thread() {
+    thread::spawn(|| loop {
+        match refresh_expiring() {
+            Ok(0) => {}
+            Ok(refreshed) => println!("Refreshed {} account(s)", refreshed),
+            Err(e) => eprintln!("Failed to refresh accounts: {}", e),
+        }
+
+        thread::sleep(REFRESH_CHECK_INTERVAL);
+    });
+}
+
+// Moves secrets into other store when it is changed in preferences
+pub fn move_secrets(to_secret_service: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if !cfg!(feature = "secret-service") {
+        return Ok(());
+    }
+
+    let _lock = ACCOUNTS_LOCK.lock().map_err(|_| "Accounts lock is poisoned")?;
+
+    let from = store::secret_store(!to_secret_service);
+    let to = store::secret_store(to_secret_service);
+
+    if let Some(secrets) = from.load()? {
+        to.save(&secrets)?;
+        from.clear()?;
+    }
+
+    Ok(())
+}
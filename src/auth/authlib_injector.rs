@@ -0,0 +1,213 @@
+// This is where authlib-injector accounts are handled.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// authlib-injector lets the game use Yggdrasil compatible auth server instead of Mojang's
+// Specification: https://github.com/yushijinhun/authlib-injector/wiki
+// Agent jar is kept in .copper-launcher/authlib-injector
+
+
+use base64::Engine;
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::launcher::config;
+use crate::minecraft::api;
+
+
+// Latest agent release
+const ARTIFACT_URL: &str = "https://authlib-injector.yushi.moe/artifact/latest.json";
+
+// Agent directory inside launcher home
+const AGENT_DIR: &str = "authlib-injector";
+
+#[derive(Debug, Deserialize)]
+struct Artifact {
+    version: String,
+    download_url: String,
+    checksums: ArtifactChecksums,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArtifactChecksums {
+    sha256: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct YggdrasilProfile {
+    // UUID without dashes
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthResponse {
+    access_token: String,
+    client_token: String,
+    selected_profile: Option<YggdrasilProfile>,
+    #[serde(default)]
+    available_profiles: Vec<YggdrasilProfile>,
+}
+
+// Yggdrasil errors have message for user
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct YggdrasilError {
+    error_message: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct YggdrasilSession {
+    pub api_root: String,
+    pub access_token: String,
+    pub client_token: String,
+    pub profile: YggdrasilProfile,
+}
+
+
+// Server can point to its real API root with X-Authlib-Injector-API-Location header
+pub fn resolve_api_root(url: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let url = if url.starts_with("http://") || url.starts_with("https://") { url.to_string() } else { format!("https://{}", url) };
+    let response = reqwest::blocking::get(&url)?;
+
+    let api_root = match response.headers().get("X-Authlib-Injector-API-Location").and_then(|h| h.to_str().ok()) {
+        Some(location) => reqwest::Url::parse(&url)?.join(location)?.to_string(),
+        None => url,
+    };
+
+    Ok(api_root.trim_end_matches('/').to_string())
+}
+
+// Server metadata, passed to agent so it doesn't have to fetch it when game starts
+pub fn prefetched_metadata(api_root: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let metadata = reqwest::blocking::get(api_root)?.error_for_status()?.text()?;
+
+    // Has to be JSON, otherwise server is not Yggdrasil API
+    serde_json::from_str::<serde_json::Value>(&metadata).map_err(|_| format!("{} is not Yggdrasil API", api_root))?;
+
+    Ok(base64::engine::general_purpose::STANDARD.encode(metadata))
+}
+
+fn post_json(url: &str, body: serde_json::Value) -> Result<reqwest::blocking::Response, Box<dyn std::error::Error>> {
+    let response = reqwest::blocking::Client::new().post(url).json(&body).send()?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let message = response
+            .json::<YggdrasilError>()
+            .ok()
+            .and_then(|e| e.error_message.or(e.error))
+            .unwrap_or_else(|| status.to_string());
+        return Err(message.into());
+    }
+
+    Ok(response)
+}
+
+// Logs in with username (usually email) and password
+pub fn authenticate(api_root: &str, username: &str, password: &str) -> Result<YggdrasilSession, Box<dyn std::error::Error>> {
+    let response: AuthResponse = post_json(&format!("{}/authserver/authenticate", api_root), serde_json::json!({
+        "agent": { "name": "Minecraft", "version": 1 },
+        "username": username,
+        "password": password,
+        "requestUser": false,
+    }))?.json()?;
+
+    if let Some(profile) = response.selected_profile {
+        return Ok(YggdrasilSession {
+            api_root: api_root.to_string(),
+            access_token: response.access_token,
+            client_token: response.client_token,
+            profile,
+        });
+    }
+
+    // Accounts with more profiles have to pick one, first one is used
+    let profile = response.available_profiles.into_iter().next().ok_or("This account has no game profiles")?;
+    refresh(api_root, &response.access_token, &response.client_token, Some(&profile))
+}
+
+// Access token still works
+pub fn validate(api_root: &str, access_token: &str, client_token: &str) -> bool {
+    post_json(&format!("{}/authserver/validate", api_root), serde_json::json!({
+        "accessToken": access_token,
+        "clientToken": client_token,
+    })).is_ok()
+}
+
+// New access token, old one stops working
+pub fn refresh(api_root: &str, access_token: &str, client_token: &str, profile: Option<&YggdrasilProfile>) -> Result<YggdrasilSession, Box<dyn std::error::Error>> {
+    let mut body = serde_json::json!({
+        "accessToken": access_token,
+        "clientToken": client_token,
+        "requestUser": false,
+    });
+    if let Some(profile) = profile {
+        body["selectedProfile"] = serde_json::json!({ "id": profile.id, "name": profile.name });
+    }
+
+    let response: AuthResponse = post_json(&format!("{}/authserver/refresh", api_root), body)?.json()?;
+    let profile = response.selected_profile.or_else(|| profile.cloned()).ok_or("Server did not return game profile")?;
+
+    Ok(YggdrasilSession {
+        api_root: api_root.to_string(),
+        access_token: response.access_token,
+        client_token: response.client_token,
+        profile,
+    })
+}
+
+// Downloads latest agent when it is not there yet, jar is checked against SHA-256
+pub fn agent_jar() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let agent_dir = config::launcher_path().join(AGENT_DIR);
+    fs::create_dir_all(&agent_dir)?;
+
+    let artifact: Artifact = match reqwest::blocking::get(ARTIFACT_URL).and_then(|r| r.error_for_status()).and_then(|r| r.json()) {
+        Ok(artifact) => artifact,
+        Err(e) => {
+            // Offline, last downloaded agent is used
+            // Names can't be compared as text, "1.2.5" would come after "1.10.0"
+            eprintln!("Failed to check authlib-injector version: {}", e);
+            return fs::read_dir(&agent_dir)?
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().extension().is_some_and(|ext| ext == "jar"))
+                .filter_map(|e| Some((e.metadata().ok()?.modified().ok()?, e.path())))
+                .max_by_key(|(modified, _)| *modified)
+                .map(|(_, path)| path)
+                .ok_or_else(|| "authlib-injector is not downloaded yet".into());
+        }
+    };
+
+    let jar_path = agent_dir.join(format!("authlib-injector-{}.jar", artifact.version));
+    if jar_path.exists() && api::sha256_hex(&fs::read(&jar_path)?).eq_ignore_ascii_case(&artifact.checksums.sha256) {
+        return Ok(jar_path);
+    }
+
+    println!("Downloading authlib-injector {}...", artifact.version);
+    let content = reqwest::blocking::get(&artifact.download_url)?.error_for_status()?.bytes()?;
+
+    let actual = api::sha256_hex(&content);
+    if !actual.eq_ignore_ascii_case(&artifact.checksums.sha256) {
+        return Err(format!("SHA-256 mismatch for authlib-injector: expected {}, got {}", artifact.checksums.sha256, actual).into());
+    }
+
+    fs::write(&jar_path, &content)?;
+    Ok(jar_path)
+}
+
+// JVM arguments that load agent for auth server
+pub fn jvm_arguments(api_root: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let jar_path = agent_jar()?;
+    let metadata = prefetched_metadata(api_root)?;
+
+    Ok(vec![
+        format!("-javaagent:{}={}", jar_path.display(), api_root),
+        format!("-Dauthlibinjector.yggdrasil.prefetched={}", metadata),
+    ])
+}
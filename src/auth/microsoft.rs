@@ -0,0 +1,348 @@
+// This is where Microsoft accounts are logged in.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// Login chain: Microsoft OAuth device code -> Xbox Live token -> XSTS token -> Minecraft token
+// Minecraft token is then used to check game ownership and fetch player profile
+// Documentation: https://minecraft.wiki/w/Microsoft_authentication
+
+
+use base64::Engine;
+use serde::Deserialize;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::launcher::config::MicrosoftConfig;
+
+
+pub const DEVICE_CODE_URL: &str = "https://login.microsoftonline.com/consumers/oauth2/v2.0/devicecode";
+pub const TOKEN_URL: &str = "https://login.microsoftonline.com/consumers/oauth2/v2.0/token";
+pub const XBOX_LIVE_URL: &str = "https://user.auth.xboxlive.com/user/authenticate";
+pub const XSTS_URL: &str = "https://xsts.auth.xboxlive.com/xsts/authorize";
+pub const MINECRAFT_LOGIN_URL: &str = "https://api.minecraftservices.com/authentication/login_with_xbox";
+pub const ENTITLEMENTS_URL: &str = "https://api.minecraftservices.com/entitlements/mcstore";
+pub const PROFILE_URL: &str = "https://api.minecraftservices.com/minecraft/profile";
+
+// Refresh token is needed to log in again without user
+const SCOPE: &str = "XboxLive.signin offline_access";
+
+// Code shown to user, they enter it at verification_uri
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeviceCode {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub expires_in: u64,
+    pub interval: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MicrosoftToken {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+}
+
+// Error response while user has not finished login yet
+#[derive(Debug, Deserialize)]
+struct TokenError {
+    error: String,
+    #[serde(default)]
+    error_description: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct XboxResponse {
+    token: String,
+    display_claims: XboxDisplayClaims,
+}
+
+#[derive(Debug, Deserialize)]
+struct XboxDisplayClaims {
+    xui: Vec<XboxUserInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct XboxUserInfo {
+    uhs: String,
+}
+
+// XSTS errors come with code explaining why account can't be used
+#[derive(Debug, Deserialize)]
+struct XstsError {
+    #[serde(rename = "XErr")]
+    xerr: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct XboxToken {
+    pub token: String,
+    pub user_hash: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MinecraftLoginResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct Entitlements {
+    #[serde(default)]
+    items: Vec<EntitlementItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EntitlementItem {
+    name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MinecraftProfile {
+    // UUID without dashes
+    pub id: String,
+    pub name: String,
+}
+
+// Logged in account, everything launch needs
+#[derive(Debug, Clone)]
+pub struct MicrosoftAccount {
+    pub profile: MinecraftProfile,
+    pub xuid: String,
+    pub access_token: String,
+    // Unix time when Minecraft token stops working
+    pub expires_at: u64,
+    pub refresh_token: Option<String>,
+}
+
+
+// "069a79f444e94726a5befca90e38aaf5" -> "069a79f4-44e9-4726-a5be-fca90e38aaf5"
+pub fn hyphenated_uuid(uuid: &str) -> String {
+    if uuid.len() != 32 {
+        return uuid.to_string();
+    }
+
+    format!("{}-{}-{}-{}-{}", &uuid[0..8], &uuid[8..12], &uuid[12..16], &uuid[16..20], &uuid[20..32])
+}
+
+fn unix_time() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
+// XUID is only in claims of Minecraft token, which is JWT
+fn token_xuid(access_token: &str) -> Option<String> {
+    let payload = access_token.split('.').nth(1)?;
+    let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(payload.trim_end_matches('=')).ok()?;
+    let claims: serde_json::Value = serde_json::from_slice(&payload).ok()?;
+
+    claims.get("xuid").and_then(|x| x.as_str()).map(|x| x.to_string())
+}
+
+fn xsts_error_message(xerr: u64) -> String {
+    match xerr {
+        2148916227 => "This account is banned from Xbox".to_string(),
+        2148916233 => "This Microsoft account has no Xbox account, create one at xbox.com first".to_string(),
+        2148916235 => "Xbox Live is not available in your country".to_string(),
+        2148916236 | 2148916237 => "This account needs adult verification on xbox.com".to_string(),
+        2148916238 => "This is a child account, it has to be added to a family by an adult".to_string(),
+        _ => format!("Xbox Live authorization failed with code {}", xerr),
+    }
+}
+
+
+pub struct MicrosoftAuth {
+    config: MicrosoftConfig,
+    client: reqwest::blocking::Client,
+}
+
+impl MicrosoftAuth {
+    pub fn new(config: &MicrosoftConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        if config.client_id.is_empty() {
+            return Err("Microsoft client ID is not set, add it in Preferences".into());
+        }
+
+        Ok(MicrosoftAuth {
+            config: config.clone(),
+            client: reqwest::blocking::Client::new(),
+        })
+    }
+
+    // Starts login, user has to open verification_uri and enter user_code
+    pub fn request_device_code(&self) -> Result<DeviceCode, Box<dyn std::error::Error>> {
+        let response = self.client
+            .post(&self.config.device_code_url)
+            .form(&[("client_id", self.config.client_id.as_str()), ("scope", SCOPE)])
+            .send()?
+            .error_for_status()?;
+
+        Ok(response.json()?)
+    }
+
+    // Waits until user finishes login in browser
+    pub fn wait_for_token(&self, device_code: &DeviceCode) -> Result<MicrosoftToken, Box<dyn std::error::Error>> {
+        let deadline = unix_time() + device_code.expires_in;
+        let mut interval = device_code.interval.max(1);
+
+        while unix_time() < deadline {
+            thread::sleep(Duration::from_secs(interval));
+
+            let response = self.client
+                .post(&self.config.token_url)
+                .form(&[
+                    ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+                    ("client_id", self.config.client_id.as_str()),
+                    ("device_code", device_code.device_code.as_str()),
+                ])
+                .send()?;
+
+            if response.status().is_success() {
+                return Ok(response.json()?);
+            }
+
+            let error: TokenError = response.json()?;
+            match error.error.as_str() {
+                "authorization_pending" => continue,
+                "slow_down" => interval += 5,
+                "authorization_declined" => return Err("Login was declined".into()),
+                "expired_token" => break,
+                _ => return Err(format!("Microsoft login failed: {} {}", error.error, error.error_description).into()),
+            }
+        }
+
+        Err("Login code expired, try again".into())
+    }
+
+    // New Microsoft token without user, refresh token is replaced by new one
+    pub fn refresh(&self, refresh_token: &str) -> Result<MicrosoftToken, Box<dyn std::error::Error>> {
+        let response = self.client
+            .post(&self.config.token_url)
+            .form(&[
+                ("grant_type", "refresh_token"),
+                ("client_id", self.config.client_id.as_str()),
+                ("refresh_token", refresh_token),
+                ("scope", SCOPE),
+            ])
+            .send()?;
+
+        if !response.status().is_success() {
+            let error: TokenError = response.json()?;
+            return Err(format!("Failed to refresh Microsoft login: {} {}", error.error, error.error_description).into());
+        }
+
+        Ok(response.json()?)
+    }
+
+    pub fn xbox_live_token(&self, microsoft_token: &str) -> Result<XboxToken, Box<dyn std::error::Error>> {
+        let body = serde_json::json!({
+            "Properties": {
+                "AuthMethod": "RPS",
+                "SiteName": "user.auth.xboxlive.com",
+                "RpsTicket": format!("d={}", microsoft_token),
+            },
+            "RelyingParty": "http://auth.xboxlive.com",
+            "TokenType": "JWT",
+        });
+
+        let response: XboxResponse = self.client
+            .post(&self.config.xbox_live_url)
+            .json(&body)
+            .send()?
+            .error_for_status()?
+            .json()?;
+
+        let user_hash = response.display_claims.xui.first().ok_or("Xbox Live response has no user hash")?.uhs.clone();
+        Ok(XboxToken { token: response.token, user_hash })
+    }
+
+    pub fn xsts_token(&self, xbox_live_token: &str) -> Result<XboxToken, Box<dyn std::error::Error>> {
+        let body = serde_json::json!({
+            "Properties": {
+                "SandboxId": "RETAIL",
+                "UserTokens": [xbox_live_token],
+            },
+            "RelyingParty": "rp://api.minecraftservices.com/",
+            "TokenType": "JWT",
+        });
+
+        let response = self.client.post(&self.config.xsts_url).json(&body).send()?;
+
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+            let error: XstsError = response.json()?;
+            return Err(xsts_error_message(error.xerr).into());
+        }
+
+        let response: XboxResponse = response.error_for_status()?.json()?;
+        let user_hash = response.display_claims.xui.first().ok_or("XSTS response has no user hash")?.uhs.clone();
+        Ok(XboxToken { token: response.token, user_hash })
+    }
+
+    // Returns Minecraft access token and seconds until it expires
+    pub fn minecraft_login(&self, xsts: &XboxToken) -> Result<(String, u64), Box<dyn std::error::Error>> {
+        let body = serde_json::json!({
+            "identityToken": format!("XBL3.0 x={};{}", xsts.user_hash, xsts.token),
+        });
+
+        let response: MinecraftLoginResponse = self.client
+            .post(&self.config.minecraft_login_url)
+            .json(&body)
+            .send()?
+            .error_for_status()?
+            .json()?;
+
+        Ok((response.access_token, response.expires_in))
+    }
+
+    pub fn owns_minecraft(&self, access_token: &str) -> Result<bool, Box<dyn std::error::Error>> {
+        let entitlements: Entitlements = self.client
+            .get(&self.config.entitlements_url)
+            .bearer_auth(access_token)
+            .send()?
+            .error_for_status()?
+            .json()?;
+
+        Ok(entitlements.items.iter().any(|i| i.name == "game_minecraft" || i.name == "product_minecraft"))
+    }
+
+    pub fn profile(&self, access_token: &str) -> Result<MinecraftProfile, Box<dyn std::error::Error>> {
+        let response = self.client
+            .get(&self.config.profile_url)
+            .bearer_auth(access_token)
+            .send()?;
+
+        // Game is owned, but player name was never chosen
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err("This account has no Minecraft profile, set up your player name on minecraft.net first".into());
+        }
+
+        Ok(response.error_for_status()?.json()?)
+    }
+
+    // Rest of login chain once Microsoft token is known
+    pub fn login(&self, microsoft_token: &MicrosoftToken) -> Result<MicrosoftAccount, Box<dyn std::error::Error>> {
+        println!("Logging in to Xbox Live...");
+        let xbox_live = self.xbox_live_token(&microsoft_token.access_token)?;
+        let xsts = self.xsts_token(&xbox_live.token)?;
+
+        println!("Logging in to Minecraft...");
+        let (access_token, expires_in) = self.minecraft_login(&xsts)?;
+
+        if !self.owns_minecraft(&access_token)? {
+            return Err("This account does not own Minecraft".into());
+        }
+
+        let profile = self.profile(&access_token)?;
+        let xuid = token_xuid(&access_token).unwrap_or_else(|| "0".to_string());
+
+        println!("Logged in as {}", profile.name);
+        Ok(MicrosoftAccount {
+            profile,
+            xuid,
+            access_token,
+            expires_at: unix_time() + expires_in,
+            refresh_token: microsoft_token.refresh_token.clone(),
+        })
+    }
+}
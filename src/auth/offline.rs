@@ -0,0 +1,63 @@
+// This is where offline accounts are handled.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// Offline players get same UUID vanilla server gives them in offline mode
+// UUID is name based (version 3) from "OfflinePlayer:<name>", so player data in LAN worlds stays with the name
+
+
+use md5::{Digest, Md5};
+
+
+// Minecraft player name rules
+const MIN_NAME_LENGTH: usize = 3;
+const MAX_NAME_LENGTH: usize = 16;
+
+pub fn validate_name(name: &str) -> Result<(), String> {
+    if name.len() < MIN_NAME_LENGTH || name.len() > MAX_NAME_LENGTH {
+        return Err(format!("Name must be {} to {} characters long", MIN_NAME_LENGTH, MAX_NAME_LENGTH));
+    }
+
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err("Name can only contain letters, numbers and underscores".to_string());
+    }
+
+    Ok(())
+}
+
+// Same as Java's UUID.nameUUIDFromBytes("OfflinePlayer:<name>")
+pub fn offline_uuid(name: &str) -> String {
+    let mut hash: [u8; 16] = Md5::digest(format!("OfflinePlayer:{}", name).as_bytes()).into();
+
+    // Version 3, IETF variant
+    hash[6] = (hash[6] & 0x0f) | 0x30;
+    hash[8] = (hash[8] & 0x3f) | 0x80;
+
+    let hex: String = hash.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}-{}-{}-{}-{}", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..32])
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uuid_matches_vanilla() {
+        // UUID.nameUUIDFromBytes("OfflinePlayer:Notch".getBytes(StandardCharsets.UTF_8))
+        assert_eq!(offline_uuid("Notch"), "b50ad385-829d-3141-a216-7e7d7539ba7f");
+    }
+
+    #[test]
+    fn name_rules() {
+        assert!(validate_name("ab").is_err());
+        assert!(validate_name("abc").is_ok());
+        assert!(validate_name("Player_123456789").is_ok());
+        assert!(validate_name("Player_1234567890").is_err());
+        assert!(validate_name("some-name").is_err());
+        assert!(validate_name("Jöhn").is_err());
+    }
+}
@@ -0,0 +1,282 @@
+// This is where skins and capes of Microsoft accounts are handled.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// Uses Minecraft services profile endpoints, same access token as the game
+// Skin texture is 64x64 (or legacy 64x32) PNG, front of player is rendered from it for preview
+
+
+use serde::Deserialize;
+
+use crate::launcher::config::MicrosoftConfig;
+
+
+// PNG files start with this
+const PNG_SIGNATURE: &[u8] = &[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+// Boundary of multipart skin upload
+const UPLOAD_BOUNDARY: &str = "copper-launcher-skin-upload";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SkinModel {
+    // Steve, 4 pixel wide arms
+    Classic,
+    // Alex, 3 pixel wide arms
+    Slim,
+}
+
+impl SkinModel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SkinModel::Classic => "classic",
+            SkinModel::Slim => "slim",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Skin {
+    pub state: String,
+    pub url: String,
+    // "CLASSIC" or "SLIM"
+    #[serde(default)]
+    pub variant: String,
+}
+
+impl Skin {
+    pub fn model(&self) -> SkinModel {
+        if self.variant.eq_ignore_ascii_case("slim") { SkinModel::Slim } else { SkinModel::Classic }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Cape {
+    pub id: String,
+    pub state: String,
+    pub url: String,
+    #[serde(default)]
+    pub alias: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PlayerProfile {
+    pub name: String,
+    #[serde(default)]
+    pub skins: Vec<Skin>,
+    // Only capes account owns
+    #[serde(default)]
+    pub capes: Vec<Cape>,
+}
+
+impl PlayerProfile {
+    pub fn active_skin(&self) -> Option<&Skin> {
+        self.skins.iter().find(|s| s.state == "ACTIVE")
+    }
+
+    pub fn active_cape(&self) -> Option<&Cape> {
+        self.capes.iter().find(|c| c.state == "ACTIVE")
+    }
+}
+
+// Services errors have message for user
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ServicesError {
+    error_message: Option<String>,
+}
+
+
+// Everything profile page needs from Minecraft services
+// Implemented by ProfileClient, tests can provide their own offline implementation
+pub trait ProfileApi {
+    fn profile(&self) -> Result<PlayerProfile, Box<dyn std::error::Error>>;
+
+    fn upload_skin(&self, png: &[u8], model: SkinModel) -> Result<PlayerProfile, Box<dyn std::error::Error>>;
+
+    fn show_cape(&self, cape_id: &str) -> Result<PlayerProfile, Box<dyn std::error::Error>>;
+
+    fn hide_cape(&self) -> Result<PlayerProfile, Box<dyn std::error::Error>>;
+
+    // Skin and cape textures
+    fn texture(&self, url: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>>;
+}
+
+pub struct ProfileClient {
+    profile_url: String,
+    access_token: String,
+    client: reqwest::blocking::Client,
+}
+
+impl ProfileClient {
+    pub fn new(config: &MicrosoftConfig, access_token: &str) -> Self {
+        ProfileClient {
+            profile_url: config.profile_url.trim_end_matches('/').to_string(),
+            access_token: access_token.to_string(),
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+
+    fn send(&self, request: reqwest::blocking::RequestBuilder) -> Result<PlayerProfile, Box<dyn std::error::Error>> {
+        let response = request.bearer_auth(&self.access_token).send()?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let message = response
+                .json::<ServicesError>()
+                .ok()
+                .and_then(|e| e.error_message)
+                .unwrap_or_else(|| status.to_string());
+            return Err(message.into());
+        }
+
+        Ok(response.json()?)
+    }
+}
+
+impl ProfileApi for ProfileClient {
+    fn profile(&self) -> Result<PlayerProfile, Box<dyn std::error::Error>> {
+        self.send(self.client.get(&self.profile_url))
+    }
+
+    fn upload_skin(&self, png: &[u8], model: SkinModel) -> Result<PlayerProfile, Box<dyn std::error::Error>> {
+        validate_skin(png)?;
+
+        // Small enough to be built by hand
+        let mut body = format!(
+            "--{0}\r\nContent-Disposition: form-data; name=\"variant\"\r\n\r\n{1}\r\n--{0}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"skin.png\"\r\nContent-Type: image/png\r\n\r\n",
+            UPLOAD_BOUNDARY, model.as_str()
+        ).into_bytes();
+        body.extend_from_slice(png);
+        body.extend_from_slice(format!("\r\n--{}--\r\n", UPLOAD_BOUNDARY).as_bytes());
+
+        self.send(self.client
+            .post(format!("{}/skins", self.profile_url))
+            .header(reqwest::header::CONTENT_TYPE, format!("multipart/form-data; boundary={}", UPLOAD_BOUNDARY))
+            .body(body))
+    }
+
+    fn show_cape(&self, cape_id: &str) -> Result<PlayerProfile, Box<dyn std::error::Error>> {
+        self.send(self.client
+            .put(format!("{}/capes/active", self.profile_url))
+            .json(&serde_json::json!({ "capeId": cape_id })))
+    }
+
+    fn hide_cape(&self) -> Result<PlayerProfile, Box<dyn std::error::Error>> {
+        self.send(self.client.delete(format!("{}/capes/active", self.profile_url)))
+    }
+
+    fn texture(&self, url: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        Ok(self.client.get(url).send()?.error_for_status()?.bytes()?.to_vec())
+    }
+}
+
+
+// Width and height from PNG header
+fn png_size(png: &[u8]) -> Option<(u32, u32)> {
+    if png.len() < 24 || !png.starts_with(PNG_SIGNATURE) || &png[12..16] != b"IHDR" {
+        return None;
+    }
+
+    let width = u32::from_be_bytes(png[16..20].try_into().ok()?);
+    let height = u32::from_be_bytes(png[20..24].try_into().ok()?);
+    Some((width, height))
+}
+
+// Checked before upload, so user gets clear message instead of server error
+pub fn validate_skin(png: &[u8]) -> Result<(), String> {
+    match png_size(png) {
+        Some((64, 64)) | Some((64, 32)) => Ok(()),
+        Some((width, height)) => Err(format!("Skin has to be 64x64 or 64x32 pixels, this one is {}x{}", width, height)),
+        None => Err("Skin has to be PNG image".to_string()),
+    }
+}
+
+
+// Front of player, in skin pixels
+pub const FRONT_WIDTH: usize = 16;
+pub const FRONT_HEIGHT: usize = 32;
+
+// Copies rectangle of texture into image, with alpha blending
+// Pixels are 4 bytes with premultiplied alpha last, so channel order doesn't matter
+#[allow(clippy::too_many_arguments)]
+fn blit(texture: &[u8], texture_width: usize, x: usize, y: usize, width: usize, height: usize, image: &mut [u8], image_x: usize, image_y: usize, mirror: bool) {
+    for row in 0..height {
+        for column in 0..width {
+            let source_column = if mirror { width - 1 - column } else { column };
+            let source = ((y + row) * texture_width + x + source_column) * 4;
+            let target = ((image_y + row) * FRONT_WIDTH + image_x + column) * 4;
+            let Some(pixel) = texture.get(source..source + 4) else { continue };
+
+            let alpha = pixel[3] as u32;
+            for channel in 0..4 {
+                let under = image[target + channel] as u32;
+                image[target + channel] = (pixel[channel] as u32 + under * (255 - alpha) / 255).min(255) as u8;
+            }
+        }
+    }
+}
+
+// Renders front of player from skin texture, base layer first and then overlay layer
+// Returns FRONT_WIDTH x FRONT_HEIGHT image scaled by scale, pixels stay sharp
+pub fn render_front(texture: &[u8], texture_width: usize, texture_height: usize, model: SkinModel, scale: usize) -> Vec<u8> {
+    let mut image = vec![0; FRONT_WIDTH * FRONT_HEIGHT * 4];
+    // Legacy skins have no left limbs and no overlays except hat
+    let legacy = texture_height < 64;
+    let arm_width = if model == SkinModel::Slim { 3 } else { 4 };
+
+    // Head and hat
+    blit(texture, texture_width, 8, 8, 8, 8, &mut image, 4, 0, false);
+    blit(texture, texture_width, 40, 8, 8, 8, &mut image, 4, 0, false);
+
+    // Body
+    blit(texture, texture_width, 20, 20, 8, 12, &mut image, 4, 8, false);
+
+    // Right arm and right leg, on left side of image
+    blit(texture, texture_width, 44, 20, arm_width, 12, &mut image, 4 - arm_width, 8, false);
+    blit(texture, texture_width, 4, 20, 4, 12, &mut image, 4, 20, false);
+
+    if legacy {
+        // Left limbs are mirrored right ones
+        blit(texture, texture_width, 44, 20, arm_width, 12, &mut image, 12, 8, true);
+        blit(texture, texture_width, 4, 20, 4, 12, &mut image, 8, 20, true);
+    } else {
+        blit(texture, texture_width, 36, 52, arm_width, 12, &mut image, 12, 8, false);
+        blit(texture, texture_width, 20, 52, 4, 12, &mut image, 8, 20, false);
+
+        // Jacket, sleeves and pants
+        blit(texture, texture_width, 20, 36, 8, 12, &mut image, 4, 8, false);
+        blit(texture, texture_width, 44, 36, arm_width, 12, &mut image, 4 - arm_width, 8, false);
+        blit(texture, texture_width, 52, 52, arm_width, 12, &mut image, 12, 8, false);
+        blit(texture, texture_width, 4, 36, 4, 12, &mut image, 4, 20, false);
+        blit(texture, texture_width, 4, 52, 4, 12, &mut image, 8, 20, false);
+    }
+
+    scale_image(&image, FRONT_WIDTH, FRONT_HEIGHT, scale)
+}
+
+// Cape as seen from behind player, 10x16 pixels
+pub fn render_cape(texture: &[u8], texture_width: usize, scale: usize) -> Vec<u8> {
+    let mut cape = Vec::with_capacity(10 * 16 * 4);
+    for row in 1..17 {
+        let start = (row * texture_width + 1) * 4;
+        cape.extend_from_slice(texture.get(start..start + 10 * 4).unwrap_or(&[0; 40]));
+    }
+
+    scale_image(&cape, 10, 16, scale)
+}
+
+// Nearest neighbour scaling, textures would be blurry otherwise
+fn scale_image(image: &[u8], width: usize, height: usize, scale: usize) -> Vec<u8> {
+    let mut scaled = Vec::with_capacity(width * height * scale * scale * 4);
+    for y in 0..height * scale {
+        for x in 0..width * scale {
+            let source = ((y / scale) * width + x / scale) * 4;
+            scaled.extend_from_slice(&image[source..source + 4]);
+        }
+    }
+
+    scaled
+}
@@ -0,0 +1,216 @@
+// This is where account tokens are stored.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// Tokens are never written in plaintext
+// Default store encrypts them with ChaCha20-Poly1305, key is kept in separate file readable only by user
+// With "secret-service" feature they can be kept in desktop keyring instead (GNOME Keyring, KWallet)
+
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::PathBuf;
+
+use crate::launcher::config;
+
+
+// Encrypted tokens - .copper-launcher/accounts.secrets
+const SECRETS_FILE: &str = "accounts.secrets";
+
+// Encryption key - .copper-launcher/accounts.key
+const KEY_FILE: &str = "accounts.key";
+
+// ChaCha20-Poly1305 nonce is 12 bytes, stored in front of encrypted data
+const NONCE_LENGTH: usize = 12;
+
+// Where secrets of all accounts are kept, secrets are single serialized blob
+pub trait SecretStore {
+    fn load(&self) -> Result<Option<Vec<u8>>, Box<dyn std::error::Error>>;
+
+    fn save(&self, secrets: &[u8]) -> Result<(), Box<dyn std::error::Error>>;
+
+    fn clear(&self) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+
+// Writes file only user can read
+// Content goes to temporary file first, so interrupted write never leaves half of the file
+fn write_private(path: &PathBuf, content: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut temp_name = path.as_os_str().to_owned();
+        temp_name.push(".tmp");
+    let temp_path = PathBuf::from(temp_name);
+
+    // Leftover from interrupted write could have other permissions
+    if temp_path.exists() {
+        fs::remove_file(&temp_path)?;
+    }
+
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(&temp_path)?;
+
+    file.write_all(content)?;
+    file.sync_all()?;
+    fs::rename(&temp_path, path)?;
+
+    Ok(())
+}
+
+pub struct EncryptedFileStore {
+    secrets_path: PathBuf,
+    key_path: PathBuf,
+}
+
+impl EncryptedFileStore {
+    pub fn new() -> Self {
+        let launcher_path = config::launcher_path();
+
+        EncryptedFileStore {
+            secrets_path: launcher_path.join(SECRETS_FILE),
+            key_path: launcher_path.join(KEY_FILE),
+        }
+    }
+
+    // Key is created with first save
+    fn key(&self, create: bool) -> Result<Option<Key>, Box<dyn std::error::Error>> {
+        if self.key_path.exists() {
+            let key_bytes = fs::read(&self.key_path)?;
+            if key_bytes.len() != 32 {
+                return Err(format!("Invalid account key in {}", self.key_path.display()).into());
+            }
+            return Ok(Some(*Key::from_slice(&key_bytes)));
+        }
+
+        if !create {
+            return Ok(None);
+        }
+
+        let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+        write_private(&self.key_path, key.as_slice())?;
+
+        Ok(Some(key))
+    }
+}
+
+impl SecretStore for EncryptedFileStore {
+    fn load(&self) -> Result<Option<Vec<u8>>, Box<dyn std::error::Error>> {
+        if !self.secrets_path.exists() {
+            return Ok(None);
+        }
+
+        let key = self.key(false)?.ok_or("Account key is missing, accounts have to be added again")?;
+        let content = fs::read(&self.secrets_path)?;
+        if content.len() < NONCE_LENGTH {
+            return Err("Account secrets file is corrupted".into());
+        }
+
+        let (nonce, ciphertext) = content.split_at(NONCE_LENGTH);
+        let secrets = ChaCha20Poly1305::new(&key)
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| "Failed to decrypt account secrets")?;
+
+        Ok(Some(secrets))
+    }
+
+    fn save(&self, secrets: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+        let key = self.key(true)?.ok_or("Failed to create account key")?;
+
+        // New nonce for every write
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = ChaCha20Poly1305::new(&key)
+            .encrypt(&nonce, secrets)
+            .map_err(|_| "Failed to encrypt account secrets")?;
+
+        let mut content = nonce.to_vec();
+        content.extend(ciphertext);
+        write_private(&self.secrets_path, &content)?;
+
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.secrets_path.exists() {
+            fs::remove_file(&self.secrets_path)?;
+        }
+
+        Ok(())
+    }
+}
+
+
+#[cfg(feature = "secret-service")]
+pub struct SecretServiceStore;
+
+#[cfg(feature = "secret-service")]
+impl SecretServiceStore {
+    fn attributes() -> std::collections::HashMap<&'static str, &'static str> {
+        std::collections::HashMap::from([("application", "copper-launcher"), ("type", "accounts")])
+    }
+}
+
+#[cfg(feature = "secret-service")]
+impl SecretStore for SecretServiceStore {
+    fn load(&self) -> Result<Option<Vec<u8>>, Box<dyn std::error::Error>> {
+        use secret_service::EncryptionType;
+        use secret_service::blocking::SecretService;
+
+        let service = SecretService::connect(EncryptionType::Dh)?;
+        let items = service.search_items(Self::attributes())?;
+
+        let Some(item) = items.unlocked.first().or_else(|| items.locked.first()) else {
+            return Ok(None);
+        };
+        item.ensure_unlocked()?;
+
+        Ok(Some(item.get_secret()?))
+    }
+
+    fn save(&self, secrets: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+        use secret_service::EncryptionType;
+        use secret_service::blocking::SecretService;
+
+        let service = SecretService::connect(EncryptionType::Dh)?;
+        let collection = service.get_default_collection()?;
+        collection.ensure_unlocked()?;
+        collection.create_item("Copper Launcher accounts", Self::attributes(), secrets, true, "application/json")?;
+
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<(), Box<dyn std::error::Error>> {
+        use secret_service::EncryptionType;
+        use secret_service::blocking::SecretService;
+
+        let service = SecretService::connect(EncryptionType::Dh)?;
+        let items = service.search_items(Self::attributes())?;
+        for item in items.unlocked.iter().chain(items.locked.iter()) {
+            item.delete()?;
+        }
+
+        Ok(())
+    }
+}
+
+
+// Store selected in preferences, Secret Service is only available when built with it
+pub fn secret_store(use_secret_service: bool) -> Box<dyn SecretStore> {
+    #[cfg(feature = "secret-service")]
+    if use_secret_service {
+        return Box::new(SecretServiceStore);
+    }
+
+    #[cfg(not(feature = "secret-service"))]
+    if use_secret_service {
+        eprintln!("Launcher was built without Secret Service support, using encrypted file");
+    }
+
+    Box::new(EncryptedFileStore::new())
+}
@@ -0,0 +1,218 @@
+// This is where CurseForge API is handled.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// API documentation: https://docs.curseforge.com/rest-api/
+// Every request needs API key, it is set in launcher preferences
+
+
+use serde::Deserialize;
+
+use crate::instances::instance::LoaderKind;
+use crate::launcher::config::CurseForgeConfig;
+
+
+pub const CURSEFORGE_API_URL: &str = "https://api.curseforge.com";
+
+// Minecraft game id on CurseForge
+const MINECRAFT_GAME_ID: u32 = 432;
+
+// Results per search page
+const SEARCH_PAGE_SIZE: u32 = 20;
+
+// Hash algorithm ids used in file hashes
+const HASH_ALGO_SHA1: u32 = 1;
+
+// Dependency relation type of required dependencies
+const RELATION_REQUIRED: u32 = 3;
+
+// Every response wraps its content in data
+#[derive(Debug, Deserialize)]
+struct Response<T> {
+    data: T,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeMod {
+    pub id: u32,
+    pub name: String,
+    #[serde(default)]
+    pub summary: String,
+    #[serde(default)]
+    pub authors: Vec<CurseForgeAuthor>,
+    #[serde(default)]
+    pub download_count: u64,
+    pub class_id: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CurseForgeAuthor {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeFile {
+    pub mod_id: u32,
+    pub display_name: String,
+    pub file_name: String,
+    // Missing when author does not allow downloads outside of CurseForge
+    pub download_url: Option<String>,
+    #[serde(default)]
+    pub hashes: Vec<CurseForgeHash>,
+    #[serde(default)]
+    pub dependencies: Vec<CurseForgeDependency>,
+}
+
+impl CurseForgeFile {
+    pub fn sha1(&self) -> Option<&str> {
+        self.hashes.iter().find(|h| h.algo == HASH_ALGO_SHA1).map(|h| h.value.as_str())
+    }
+
+    pub fn required_dependencies(&self) -> impl Iterator<Item = u32> + '_ {
+        self.dependencies.iter().filter(|d| d.relation_type == RELATION_REQUIRED).map(|d| d.mod_id)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CurseForgeHash {
+    pub value: String,
+    pub algo: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeDependency {
+    pub mod_id: u32,
+    pub relation_type: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResults {
+    pub data: Vec<CurseForgeMod>,
+    pub pagination: Pagination,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Pagination {
+    pub index: u32,
+    pub result_count: u32,
+    pub total_count: u32,
+}
+
+
+// Mod loader type ids used by CurseForge
+pub fn mod_loader_type(kind: LoaderKind) -> u32 {
+    match kind {
+        LoaderKind::Forge => 1,
+        LoaderKind::Fabric => 4,
+        LoaderKind::Quilt => 5,
+        LoaderKind::NeoForge => 6,
+    }
+}
+
+
+// Everything launcher needs from CurseForge
+// Implemented by CurseForgeClient, tests can provide their own offline implementation
+pub trait CurseForgeApi {
+    fn mods(&self, mod_ids: &[u32]) -> Result<Vec<CurseForgeMod>, Box<dyn std::error::Error>>;
+
+    fn files(&self, file_ids: &[u32]) -> Result<Vec<CurseForgeFile>, Box<dyn std::error::Error>>;
+
+    // Files of mod for minecraft version and loader, newest first
+    fn mod_files(&self, mod_id: u32, game_version: &str, loader: Option<LoaderKind>) -> Result<Vec<CurseForgeFile>, Box<dyn std::error::Error>>;
+
+    fn search(&self, query: &str, class_id: u32, game_version: &str, loader: Option<LoaderKind>, index: u32) -> Result<SearchResults, Box<dyn std::error::Error>>;
+}
+
+pub struct CurseForgeClient {
+    base_url: String,
+    api_key: String,
+    client: reqwest::blocking::Client,
+}
+
+impl CurseForgeClient {
+    pub fn new(base_url: &str, api_key: &str) -> Self {
+        CurseForgeClient {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.to_string(),
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+
+    // Client from launcher preferences, fails when API key is not set
+    pub fn from_config(config: &CurseForgeConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        match config.api_key.as_deref() {
+            Some(api_key) if !api_key.is_empty() => Ok(Self::new(&config.api_url, api_key)),
+            _ => Err("CurseForge API key is not set, add it in Preferences".into()),
+        }
+    }
+
+    fn get<T: serde::de::DeserializeOwned>(&self, url: reqwest::Url) -> Result<T, Box<dyn std::error::Error>> {
+        let response = self.client.get(url).header("x-api-key", &self.api_key).send()?.error_for_status()?;
+        Ok(response.json()?)
+    }
+
+    fn post<T: serde::de::DeserializeOwned>(&self, path: &str, body: serde_json::Value) -> Result<T, Box<dyn std::error::Error>> {
+        let url = format!("{}{}", self.base_url, path);
+        let response = self.client.post(&url).header("x-api-key", &self.api_key).json(&body).send()?.error_for_status()?;
+        Ok(response.json()?)
+    }
+}
+
+impl CurseForgeApi for CurseForgeClient {
+    fn mods(&self, mod_ids: &[u32]) -> Result<Vec<CurseForgeMod>, Box<dyn std::error::Error>> {
+        if mod_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let response: Response<Vec<CurseForgeMod>> = self.post("/v1/mods", serde_json::json!({ "modIds": mod_ids }))?;
+        Ok(response.data)
+    }
+
+    fn files(&self, file_ids: &[u32]) -> Result<Vec<CurseForgeFile>, Box<dyn std::error::Error>> {
+        if file_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let response: Response<Vec<CurseForgeFile>> = self.post("/v1/mods/files", serde_json::json!({ "fileIds": file_ids }))?;
+        Ok(response.data)
+    }
+
+    fn mod_files(&self, mod_id: u32, game_version: &str, loader: Option<LoaderKind>) -> Result<Vec<CurseForgeFile>, Box<dyn std::error::Error>> {
+        let mut params = vec![("gameVersion", game_version.to_string())];
+        if let Some(loader) = loader {
+            params.push(("modLoaderType", mod_loader_type(loader).to_string()));
+        }
+
+        let url = reqwest::Url::parse_with_params(&format!("{}/v1/mods/{}/files", self.base_url, mod_id), &params)?;
+        let response: Response<Vec<CurseForgeFile>> = self.get(url)?;
+        Ok(response.data)
+    }
+
+    fn search(&self, query: &str, class_id: u32, game_version: &str, loader: Option<LoaderKind>, index: u32) -> Result<SearchResults, Box<dyn std::error::Error>> {
+        let mut params = vec![
+            ("gameId", MINECRAFT_GAME_ID.to_string()),
+            ("classId", class_id.to_string()),
+            ("searchFilter", query.to_string()),
+            ("gameVersion", game_version.to_string()),
+            // Sorted by popularity
+            ("sortField", "2".to_string()),
+            ("sortOrder", "desc".to_string()),
+            ("index", index.to_string()),
+            ("pageSize", SEARCH_PAGE_SIZE.to_string()),
+        ];
+        if let Some(loader) = loader {
+            params.push(("modLoaderType", mod_loader_type(loader).to_string()));
+        }
+
+        let url = reqwest::Url::parse_with_params(&format!("{}/v1/mods/search", self.base_url), &params)?;
+        self.get(url)
+    }
+}
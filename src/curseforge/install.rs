@@ -0,0 +1,122 @@
+// This is where CurseForge projects are installed into instances.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// Newest file matching instance is installed together with required dependencies
+// Some authors don't allow downloads outside of CurseForge, those files have to be downloaded manually
+
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::curseforge::api::{CurseForgeApi, CurseForgeFile};
+use crate::instances::instance::InstanceMetadata;
+use crate::minecraft::api;
+use crate::modrinth::install::{self, ProjectType};
+
+
+// Class ids of project types on CurseForge
+pub fn class_id(project_type: ProjectType) -> u32 {
+    match project_type {
+        ProjectType::Mod => 6,
+        ProjectType::ResourcePack => 12,
+        ProjectType::Shader => 6552,
+        ProjectType::Datapack => 6945,
+    }
+}
+
+pub fn project_type_from_class(class_id: u32) -> Option<ProjectType> {
+    ProjectType::ALL.into_iter().find(|t| self::class_id(*t) == class_id)
+}
+
+// Downloads file into directory, file is checked against CurseForge SHA-1
+pub fn download_file(file: &CurseForgeFile, destination: &Path) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let Some(url) = &file.download_url else {
+        return Err(format!(
+            "{} can't be downloaded by launcher, download it manually from CurseForge into {}",
+            file.display_name,
+            destination.display()
+        ).into());
+    };
+
+    // File name comes from server, it must not point outside destination
+    if file.file_name.contains(['/', '\\']) || file.file_name.starts_with('.') {
+        return Err(format!("Invalid file name: {}", file.file_name).into());
+    }
+
+    let path = destination.join(&file.file_name);
+    api::download_file_verified(url, &path, file.sha1(), None)?;
+
+    Ok(path)
+}
+
+// Installs project into instance, mods also get their required dependencies
+// Returns names of installed files
+pub fn install_project(
+    metadata: &InstanceMetadata,
+    mod_id: u32,
+    project_type: ProjectType,
+    world: Option<&str>,
+    curseforge: &dyn CurseForgeApi,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let destination = install::destination_dir(&metadata.name, project_type, world)?;
+    fs::create_dir_all(&destination)?;
+
+    // Only mods are filtered by loader
+    let loader = match project_type {
+        ProjectType::Mod => metadata.loader.as_ref().map(|l| l.kind),
+        _ => None,
+    };
+
+    let newest_file = |mod_id: u32| -> Result<Option<CurseForgeFile>, Box<dyn std::error::Error>> {
+        Ok(curseforge.mod_files(mod_id, &metadata.minecraft_version, loader)?.into_iter().next())
+    };
+
+    let file = newest_file(mod_id)?
+        .ok_or_else(|| format!("No file for Minecraft {} found", metadata.minecraft_version))?;
+
+    if destination.join(&file.file_name).exists() {
+        return Err(format!("{} is already installed", file.display_name).into());
+    }
+
+    let mut installed_mods = HashSet::new();
+    let mut installed_files = Vec::new();
+    let mut pending = vec![file];
+
+    while let Some(file) = pending.pop() {
+        // Same dependency can be required by several mods
+        if !installed_mods.insert(file.mod_id) {
+            continue;
+        }
+
+        // Dependency already in instance
+        if destination.join(&file.file_name).exists() {
+            continue;
+        }
+
+        println!("Installing {}...", file.display_name);
+        download_file(&file, &destination)?;
+        installed_files.push(file.file_name.clone());
+
+        if project_type != ProjectType::Mod {
+            continue;
+        }
+
+        for dependency_id in file.required_dependencies() {
+            if installed_mods.contains(&dependency_id) {
+                continue;
+            }
+
+            match newest_file(dependency_id)? {
+                Some(dependency_file) => pending.push(dependency_file),
+                None => eprintln!("No compatible file of dependency {} found", dependency_id),
+            }
+        }
+    }
+
+    Ok(installed_files)
+}
@@ -0,0 +1,186 @@
+// This is where CurseForge modpacks are handled.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// Modpack is zip with manifest.json, mods are referenced by project and file ids
+// Files are resolved through CurseForge API, everything else comes from overrides directory
+
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::Path;
+use zip::ZipArchive;
+
+use crate::curseforge::api::CurseForgeApi;
+use crate::curseforge::install;
+use crate::instances::instance::{self, InstanceMetadata, InstanceSettings, LoaderKind, ModLoader};
+use crate::modrinth::install::{self as modrinth_install, ProjectType};
+
+
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Manifest {
+    pub minecraft: ManifestMinecraft,
+    pub manifest_type: String,
+    pub name: String,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub files: Vec<ManifestFile>,
+    #[serde(default = "default_overrides")]
+    pub overrides: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestMinecraft {
+    pub version: String,
+    #[serde(default)]
+    pub mod_loaders: Vec<ManifestLoader>,
+}
+
+// Loader id looks like "forge-47.2.0"
+#[derive(Debug, Deserialize)]
+pub struct ManifestLoader {
+    pub id: String,
+    #[serde(default)]
+    pub primary: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ManifestFile {
+    #[serde(rename = "projectID")]
+    pub project_id: u32,
+    #[serde(rename = "fileID")]
+    pub file_id: u32,
+    #[serde(default = "default_required")]
+    pub required: bool,
+}
+
+fn default_overrides() -> String {
+    "overrides".to_string()
+}
+
+fn default_required() -> bool {
+    true
+}
+
+
+// Maps loader id from manifest to mod loader
+fn parse_loader(loaders: &[ManifestLoader]) -> Result<Option<ModLoader>, Box<dyn std::error::Error>> {
+    let Some(loader) = loaders.iter().find(|l| l.primary).or_else(|| loaders.first()) else {
+        return Ok(None);
+    };
+
+    let (name, version) = loader.id.split_once('-').ok_or_else(|| format!("Invalid mod loader: {}", loader.id))?;
+    let kind = match name {
+        "forge" => LoaderKind::Forge,
+        "neoforge" => LoaderKind::NeoForge,
+        "fabric" => LoaderKind::Fabric,
+        "quilt" => LoaderKind::Quilt,
+        _ => return Err(format!("Unsupported mod loader: {}", name).into()),
+    };
+
+    Ok(Some(ModLoader { kind, version: version.to_string() }))
+}
+
+pub fn read_manifest(zip: &mut ZipArchive<File>) -> Result<Manifest, Box<dyn std::error::Error>> {
+    let mut manifest_file = zip.by_name(MANIFEST_FILE)?;
+    let mut manifest_content = String::new();
+    manifest_file.read_to_string(&mut manifest_content)?;
+
+    let manifest: Manifest = serde_json::from_str(&manifest_content)?;
+    if manifest.manifest_type != "minecraftModpack" {
+        return Err(format!("Unsupported manifest type: {}", manifest.manifest_type).into());
+    }
+
+    Ok(manifest)
+}
+
+// Checks if zip file is CurseForge modpack
+pub fn is_curseforge_modpack(path: &Path) -> bool {
+    let Ok(file) = File::open(path) else { return false };
+    let Ok(mut zip) = ZipArchive::new(file) else { return false };
+
+    read_manifest(&mut zip).is_ok()
+}
+
+// Imports CurseForge modpack zip as new instance
+// When instance_name is None, modpack name is used
+pub fn curseforge_import(modpack_path: &Path, instance_name: Option<&str>, curseforge: &dyn CurseForgeApi) -> Result<String, Box<dyn std::error::Error>> {
+    let mut zip = ZipArchive::new(File::open(modpack_path)?)?;
+    let manifest = read_manifest(&mut zip)?;
+
+    println!("Importing modpack {} {}...", manifest.name, manifest.version);
+
+    let loader = parse_loader(&manifest.minecraft.mod_loaders)?;
+
+    // Files and their projects, project class decides where file goes
+    let file_ids: Vec<u32> = manifest.files.iter().filter(|f| f.required).map(|f| f.file_id).collect();
+    let project_ids: Vec<u32> = manifest.files.iter().filter(|f| f.required).map(|f| f.project_id).collect();
+
+    println!("Looking up {} modpack files on CurseForge...", file_ids.len());
+    let files = curseforge.files(&file_ids)?;
+    let class_ids: HashMap<u32, Option<u32>> = curseforge.mods(&project_ids)?.into_iter().map(|m| (m.id, m.class_id)).collect();
+
+    if files.len() != file_ids.len() {
+        return Err(format!("CurseForge returned {} of {} modpack files", files.len(), file_ids.len()).into());
+    }
+
+    // Everything has to be downloadable before instance is created
+    let blocked: Vec<&str> = files.iter().filter(|f| f.download_url.is_none()).map(|f| f.display_name.as_str()).collect();
+    if !blocked.is_empty() {
+        return Err(format!(
+            "These files can't be downloaded by launcher, their authors only allow downloads on CurseForge: {}",
+            blocked.join(", ")
+        ).into());
+    }
+
+    let mut metadata = InstanceMetadata {
+        name: instance_name.map(|n| n.to_string()).unwrap_or_else(|| instance::sanitize_instance_name(&manifest.name)),
+        minecraft_version: manifest.minecraft.version.clone(),
+        loader,
+        settings: InstanceSettings::default(),
+        version_id: None,
+    };
+
+    let instance_dir_path = instance::instance_new(&metadata)?;
+    let mc_instance_dir_path = instance_dir_path.join("minecraft");
+
+    for (i, file) in files.iter().enumerate() {
+        // Unknown classes are mostly mods
+        let project_type = class_ids
+            .get(&file.mod_id)
+            .copied()
+            .flatten()
+            .and_then(install::project_type_from_class)
+            .unwrap_or(ProjectType::Mod);
+
+        // Data packs belong to worlds, modpack worlds come with their own
+        if project_type == ProjectType::Datapack {
+            eprintln!("Skipping data pack {}, it is not part of any world", file.display_name);
+            continue;
+        }
+
+        let destination = modrinth_install::destination_dir(&metadata.name, project_type, None)?;
+        fs::create_dir_all(&destination)?;
+
+        println!("Downloading file {}/{}: {}", i + 1, files.len(), file.file_name);
+        install::download_file(file, &destination)
+            .map_err(|e| format!("Failed to download {}: {}", file.file_name, e))?;
+    }
+
+    instance::zip_extract_dir(&mut zip, &manifest.overrides, &mc_instance_dir_path, instance::GAME_FILE_ENTRIES)?;
+
+    instance::instance_install_game(&mut metadata)?;
+
+    println!("Modpack {} imported succesfully!", manifest.name);
+    Ok(metadata.name)
+}
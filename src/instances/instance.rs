@@ -7,87 +7,595 @@
 
 
 use gtk::{glib};
-use std::{fs, path::PathBuf};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
 
-use crate::minecraft::api;
+use crate::instances::upgrade;
+use crate::minecraft::{api, fabric, forge, neoforge, quilt};
 
-pub fn instance_create(instance_name: &str, minecraft_version: &str) {
+
+// Instance metadata file - .copper-launcher/instances/{instance}/instance.json
+const METADATA_FILE: &str = "instance.json";
+
+// Entries from instance's minecraft directory that are exported by default
+const EXPORT_DEFAULT_ENTRIES: &[&str] = &["config", "mods", "resourcepacks", "shaderpacks", "options.txt"];
+
+// Downloaded game files, they are never exported or taken from archives
+// Loader libraries without hash are used as they are, so archive could put code there
+pub const GAME_FILE_ENTRIES: &[&str] = &["versions", "libraries", "assets", "natives"];
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InstanceMetadata {
+    pub name: String,
+    pub minecraft_version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loader: Option<ModLoader>,
+    #[serde(default)]
+    pub settings: InstanceSettings,
+    // Version that gets launched, differs from minecraft_version when mod loader is installed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_id: Option<String>,
+}
+
+impl InstanceMetadata {
+    pub fn launch_version_id(&self) -> &str {
+        self.version_id.as_deref().unwrap_or(&self.minecraft_version)
+    }
+}
+
+// Per-instance JVM and game settings
+// Unset values come from launcher-wide defaults in launcher config
+// Memory is in megabytes
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct InstanceSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_memory: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_memory: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub jvm_args: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gc_preset: Option<GcPreset>,
+    // Used instead of Java found on system
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub java_path: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_width: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_height: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fullscreen: Option<bool>,
+    // Replace values of game arguments with same name, others are added
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub game_args: Vec<String>,
+    // Shell commands, see minecraft/hooks.rs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_launch_command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wrapper_command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_exit_command: Option<String>,
+    // Extra environment variables for game and hooks
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+}
+
+impl InstanceSettings {
+    // Settings used for launch, extra arguments of defaults go before instance's own
+    pub fn with_defaults(&self, defaults: &InstanceSettings) -> InstanceSettings {
+        InstanceSettings {
+            min_memory: self.min_memory.or(defaults.min_memory),
+            max_memory: self.max_memory.or(defaults.max_memory),
+            jvm_args: defaults.jvm_args.iter().chain(&self.jvm_args).cloned().collect(),
+            gc_preset: self.gc_preset.or(defaults.gc_preset),
+            java_path: self.java_path.clone().or_else(|| defaults.java_path.clone()),
+            window_width: self.window_width.or(defaults.window_width),
+            window_height: self.window_height.or(defaults.window_height),
+            fullscreen: self.fullscreen.or(defaults.fullscreen),
+            game_args: defaults.game_args.iter().chain(&self.game_args).cloned().collect(),
+            pre_launch_command: self.pre_launch_command.clone().or_else(|| defaults.pre_launch_command.clone()),
+            wrapper_command: self.wrapper_command.clone().or_else(|| defaults.wrapper_command.clone()),
+            post_exit_command: self.post_exit_command.clone().or_else(|| defaults.post_exit_command.clone()),
+            env: defaults.env.iter().chain(&self.env).map(|(k, v)| (k.clone(), v.clone())).collect(),
+        }
+    }
+
+    // Settings without hooks, environment, JVM arguments (e.g. -javaagent) and Java path
+    // Shared archives never carry them, mods in imported instance still run on Play like in any modpack
+    pub fn without_commands(&self) -> InstanceSettings {
+        InstanceSettings {
+            jvm_args: Vec::new(),
+            java_path: None,
+            pre_launch_command: None,
+            wrapper_command: None,
+            post_exit_command: None,
+            env: BTreeMap::new(),
+            ..self.clone()
+        }
+    }
+}
+
+// Garbage collector flags
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GcPreset {
+    // JVM's own choice
+    Default,
+    G1,
+    Parallel,
+    Shenandoah,
+    Zgc,
+    // G1 tuned for Minecraft servers and big modpacks, https://docs.papermc.io/paper/aikars-flags
+    Aikar,
+}
+
+impl GcPreset {
+    pub const ALL: [GcPreset; 6] = [GcPreset::Default, GcPreset::G1, GcPreset::Parallel, GcPreset::Shenandoah, GcPreset::Zgc, GcPreset::Aikar];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            GcPreset::Default => "JVM default",
+            GcPreset::G1 => "G1",
+            GcPreset::Parallel => "Parallel",
+            GcPreset::Shenandoah => "Shenandoah",
+            GcPreset::Zgc => "ZGC",
+            GcPreset::Aikar => "Aikar's flags",
+        }
+    }
+
+    pub fn jvm_args(&self) -> &'static [&'static str] {
+        match self {
+            GcPreset::Default => &[],
+            GcPreset::G1 => &["-XX:+UseG1GC"],
+            GcPreset::Parallel => &["-XX:+UseParallelGC"],
+            GcPreset::Shenandoah => &["-XX:+UseShenandoahGC"],
+            GcPreset::Zgc => &["-XX:+UseZGC"],
+            GcPreset::Aikar => &[
+                "-XX:+UseG1GC",
+                "-XX:+ParallelRefProcEnabled",
+                "-XX:MaxGCPauseMillis=200",
+                "-XX:+UnlockExperimentalVMOptions",
+                "-XX:+DisableExplicitGC",
+                "-XX:+AlwaysPreTouch",
+                "-XX:G1NewSizePercent=30",
+                "-XX:G1MaxNewSizePercent=40",
+                "-XX:G1HeapRegionSize=8M",
+                "-XX:G1ReservePercent=20",
+                "-XX:G1HeapWastePercent=5",
+                "-XX:G1MixedGCCountTarget=4",
+                "-XX:InitiatingHeapOccupancyPercent=15",
+                "-XX:G1MixedGCLiveThresholdPercent=90",
+                "-XX:G1RSetUpdatingPauseTimePercent=5",
+                "-XX:SurvivorRatio=32",
+                "-XX:+PerfDisableSharedMem",
+                "-XX:MaxTenuringThreshold=1",
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ModLoader {
+    pub kind: LoaderKind,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LoaderKind {
+    Fabric,
+    Quilt,
+    Forge,
+    NeoForge,
+}
+
+// What ends up inside exported archive
+// Paths are relative to instance's minecraft directory
+#[derive(Debug)]
+pub struct ExportOptions {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    pub include_saves: bool,
+    // Keeps hooks, environment, JVM arguments and Java path, only for local backups
+    pub keep_settings: bool,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        ExportOptions {
+            include: EXPORT_DEFAULT_ENTRIES.iter().map(|e| e.to_string()).collect(),
+            exclude: Vec::new(),
+            include_saves: false,
+            keep_settings: false,
+        }
+    }
+}
+
+
+// Instances directory - .copper-launcher/instances
+pub fn instances_path() -> PathBuf {
     let home_dir = glib::home_dir();
-    
-    // Build the instance path
-    let mut instance_dir_path = PathBuf::from(home_dir);
-            instance_dir_path.push(".copper-launcher");
-            instance_dir_path.push("instances");
+
+    let mut instances_path = home_dir;
+            instances_path.push(".copper-launcher");
+            instances_path.push("instances");
+
+    instances_path
+}
+
+// Instance directory - .copper-launcher/instances/{instance}
+pub fn instance_path(instance_name: &str) -> PathBuf {
+    let mut instance_dir_path = instances_path();
             instance_dir_path.push(instance_name);
-    
+
+    instance_dir_path
+}
+
+// Names of all instances, sorted alphabetically
+pub fn instance_list() -> Vec<String> {
+    let mut instances: Vec<String> = match fs::read_dir(instances_path()) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_dir())
+            .filter_map(|e| e.file_name().into_string().ok())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+
+    instances.sort();
+    instances
+}
+
+pub fn instance_metadata(instance_name: &str) -> Result<InstanceMetadata, Box<dyn std::error::Error>> {
+    let metadata_path = instance_path(instance_name).join(METADATA_FILE);
+    let metadata_content = fs::read_to_string(&metadata_path)?;
+    let metadata: InstanceMetadata = serde_json::from_str(&metadata_content)?;
+
+    Ok(metadata)
+}
+
+pub fn write_instance_metadata(instance_dir_path: &Path, metadata: &InstanceMetadata) -> Result<(), Box<dyn std::error::Error>> {
+    let metadata_json = serde_json::to_string_pretty(metadata)?;
+    fs::write(instance_dir_path.join(METADATA_FILE), metadata_json)?;
+
+    Ok(())
+}
+
+// Creates instance directory layout
+fn instance_create_dirs(instance_dir_path: &Path) -> io::Result<()> {
     // Create instance directory
     fs::DirBuilder::new()
         .recursive(true)
-        .create(&instance_dir_path)
-        .expect("Failed to create instance");
-    
+        .create(instance_dir_path)?;
+
     // Instance directory - Minecraft game directory
-    let mut mc_instance_dir_path = instance_dir_path.clone();
+    let mut mc_instance_dir_path = instance_dir_path.to_path_buf();
             mc_instance_dir_path.push("minecraft");
-    
+
     fs::DirBuilder::new()
         .recursive(true)
-        .create(&mc_instance_dir_path)
-        .expect("Failed to create minecraft directory");
-    
+        .create(&mc_instance_dir_path)?;
+
     // Instance directory - Minecraft - saves
     let mut mc_save_instance_dir_path = mc_instance_dir_path.clone();
             mc_save_instance_dir_path.push("saves");
-    
+
     fs::DirBuilder::new()
         .recursive(true)
-        .create(&mc_save_instance_dir_path)
-        .expect("Failed to create saves directory");
-    
+        .create(&mc_save_instance_dir_path)?;
+
     // Instance directory - Minecraft - resourcepacks
     let mut mc_txt_instance_dir_path = mc_instance_dir_path.clone();
             mc_txt_instance_dir_path.push("resourcepacks");
-    
+
     fs::DirBuilder::new()
         .recursive(true)
-        .create(&mc_txt_instance_dir_path)
-        .expect("Failed to create resourcepacks directory");
+        .create(&mc_txt_instance_dir_path)?;
 
     // Instance directory - Minecraft - mods
     let mut mc_mod_instance_dir_path = mc_instance_dir_path.clone();
             mc_mod_instance_dir_path.push("mods");
-    
+
     fs::DirBuilder::new()
         .recursive(true)
-        .create(&mc_mod_instance_dir_path)
-        .expect("Failed to create mods directory");
+        .create(&mc_mod_instance_dir_path)?;
 
     // Instance directory - Minecraft - shaderpacks
     let mut mc_shader_instance_dir_path = mc_instance_dir_path.clone();
             mc_shader_instance_dir_path.push("shaderpacks");
-    
+
     fs::DirBuilder::new()
         .recursive(true)
-        .create(&mc_shader_instance_dir_path)
-        .expect("Failed to create shaderpacks directory");
+        .create(&mc_shader_instance_dir_path)?;
+
+    Ok(())
+}
+
+// Instance name is used as directory name, so it must stay inside instances directory
+// Names from imported archives and modpacks are untrusted
+pub fn validate_instance_name(instance_name: &str) -> Result<(), String> {
+    if instance_name.trim().is_empty() {
+        return Err("Instance name is empty".to_string());
+    }
+    if instance_name.contains(['/', '\\', '\0']) {
+        return Err(format!("Instance name {} can't contain / or \\", instance_name));
+    }
+    if instance_name.starts_with('.') || instance_name.contains("..") {
+        return Err(format!("Instance name {} can't start with . or contain ..", instance_name));
+    }
+    if Path::new(instance_name).is_absolute() {
+        return Err(format!("Instance name {} can't be a path", instance_name));
+    }
 
+    Ok(())
+}
+
+// Turns name from modpack or other launcher into valid instance name
+// Characters that can't be in directory names become "_", e.g. "Pack: 1.20/Fabric" -> "Pack_ 1.20_Fabric"
+pub fn sanitize_instance_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_control() || matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') { '_' } else { c })
+        .collect();
+    let name = name.replace("..", "_");
+    let name = name.trim().trim_start_matches('.').trim();
+
+    if name.is_empty() { "Instance".to_string() } else { name.to_string() }
+}
+
+// Creates empty instance with metadata, game files are not downloaded yet
+pub fn instance_new(metadata: &InstanceMetadata) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    validate_instance_name(&metadata.name)?;
+
+    let instance_dir_path = instance_path(&metadata.name);
+    if instance_dir_path.exists() {
+        return Err(format!("Instance {} already exists", metadata.name).into());
+    }
+
+    instance_create_dirs(&instance_dir_path)?;
+    write_instance_metadata(&instance_dir_path, metadata)?;
+
+    Ok(instance_dir_path)
+}
+
+// Downloads game files and mod loader for instance based on its metadata
+// Metadata is updated with version id of installed mod loader
+pub fn instance_install_game(metadata: &mut InstanceMetadata) -> Result<(), Box<dyn std::error::Error>> {
+    let instance_dir_path = instance_path(&metadata.name);
 
     // Minecraft downloader
-    println!("Downloading Minecraft {}...", minecraft_version);  
-    match api::setup_minecraft_version(minecraft_version, &instance_dir_path, true) {
+    println!("Downloading Minecraft {}...", metadata.minecraft_version);
+    api::setup_minecraft_version(&metadata.minecraft_version, &instance_dir_path, true)?;
+
+    // Mod loader
+    metadata.version_id = match &metadata.loader {
+        Some(loader) => match loader.kind {
+            LoaderKind::Fabric => Some(fabric::install_fabric(&metadata.minecraft_version, &loader.version, &instance_dir_path)?),
+            LoaderKind::Quilt => Some(quilt::install_quilt(&metadata.minecraft_version, &loader.version, &instance_dir_path)?),
+            LoaderKind::Forge => Some(forge::install_forge(&metadata.minecraft_version, &loader.version, &instance_dir_path)?),
+            LoaderKind::NeoForge => Some(neoforge::install_neoforge(&metadata.minecraft_version, &loader.version, &instance_dir_path)?),
+        },
+        None => None,
+    };
+
+    write_instance_metadata(&instance_dir_path, metadata)?;
+
+    Ok(())
+}
+
+pub fn instance_create(instance_name: &str, minecraft_version: &str, loader: Option<ModLoader>) {
+    let mut metadata = InstanceMetadata {
+        name: instance_name.to_string(),
+        minecraft_version: minecraft_version.to_string(),
+        loader,
+        settings: InstanceSettings::default(),
+        version_id: None,
+    };
+
+    if let Err(e) = instance_new(&metadata) {
+        println!("Failed to create instance: {}", e);
+        return;
+    }
+
+    match instance_install_game(&mut metadata) {
         Ok(_) => println!("Instance created succesfully!"),
         Err(e) => println!("Failed to download minecraft: {}", e)
-    }   
+    }
 
 }
 
 // Work in progress
 pub fn instance_remove(instance_name: &str) {
      let home_dir = glib::home_dir();
-    
+
     // Build the instance path
     let mut instance_dir_path = PathBuf::from(home_dir);
             instance_dir_path.push(".copper-launcher");
             instance_dir_path.push("instances");
-            instance_dir_path.push(instance_name);   
+            instance_dir_path.push(instance_name);
+}
+
+
+// Copies file or whole directory, existing files are overwritten
+pub fn copy_path(source: &Path, destination: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    if source.is_dir() {
+        fs::create_dir_all(destination)?;
+
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            copy_path(&entry.path(), &destination.join(entry.file_name()))?;
+        }
+    } else if source.is_file() {
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(source, destination)?;
+    }
+
+    Ok(())
+}
+
+
+// Adds file or whole directory to archive, skipping excluded paths
+// archive_path is path inside archive, relative_path is path relative to minecraft directory
+fn zip_add_path(
+    zip: &mut ZipWriter<File>,
+    path: &Path,
+    archive_path: &str,
+    relative_path: &str,
+    exclude: &[String],
+) -> Result<(), Box<dyn std::error::Error>> {
+    if exclude.iter().any(|e| relative_path == e || relative_path.starts_with(&format!("{}/", e))) {
+        return Ok(());
+    }
+
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    if path.is_dir() {
+        zip.add_directory(archive_path, options)?;
+
+        for entry in fs::read_dir(path)? {
+            let entry = entry?;
+            let file_name = entry.file_name().to_string_lossy().to_string();
+
+            zip_add_path(
+                zip,
+                &entry.path(),
+                &format!("{}/{}", archive_path, file_name),
+                &format!("{}/{}", relative_path, file_name),
+                exclude,
+            )?;
+        }
+    } else if path.is_file() {
+        zip.start_file(archive_path, options)?;
+        let mut file = File::open(path)?;
+        io::copy(&mut file, zip)?;
+    }
+
+    Ok(())
+}
+
+// Exports instance into single zip archive
+// Game files (client, libraries, assets) are not bundled, they are downloaded again on import
+pub fn instance_export(instance_name: &str, archive_path: &Path, options: &ExportOptions) -> Result<(), Box<dyn std::error::Error>> {
+    let instance_dir_path = instance_path(instance_name);
+    let mut metadata = instance_metadata(instance_name)?;
+    if !options.keep_settings {
+        metadata.settings = metadata.settings.without_commands();
+    }
+
+    let mc_instance_dir_path = instance_dir_path.join("minecraft");
+
+    let mut zip = ZipWriter::new(File::create(archive_path)?);
+    let options_deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    // Metadata
+    zip.start_file(METADATA_FILE, options_deflated)?;
+    zip.write_all(serde_json::to_string_pretty(&metadata)?.as_bytes())?;
+
+    // Minecraft directory content
+    let mut entries = options.include.clone();
+    if options.include_saves && !entries.iter().any(|e| e == "saves") {
+        entries.push("saves".to_string());
+    }
+
+    for entry in &entries {
+        let entry_path = mc_instance_dir_path.join(entry);
+        if !entry_path.exists() {
+            continue;
+        }
+
+        println!("Exporting {}...", entry);
+        zip_add_path(&mut zip, &entry_path, &format!("minecraft/{}", entry), entry, &options.exclude)?;
+    }
+
+    zip.finish()?;
+
+    println!("Instance {} exported to {}", instance_name, archive_path.display());
+    Ok(())
+}
+
+// Half imported instance is removed, so import can be tried again with same name
+pub fn remove_failed_instance(instance_dir_path: &Path) {
+    if let Err(e) = fs::remove_dir_all(instance_dir_path) {
+        eprintln!("Failed to remove {}: {}", instance_dir_path.display(), e);
+    }
+}
+
+// Imports instance from archive created by instance_export
+// When instance_name is None, name stored in archive metadata is used
+pub fn instance_import(archive_path: &Path, instance_name: Option<&str>) -> Result<String, Box<dyn std::error::Error>> {
+    let mut zip = ZipArchive::new(File::open(archive_path)?)?;
+
+    let mut metadata: InstanceMetadata = {
+        let mut metadata_file = zip.by_name(METADATA_FILE)?;
+        let mut metadata_content = String::new();
+        metadata_file.read_to_string(&mut metadata_content)?;
+        serde_json::from_str(&metadata_content)?
+    };
+
+    if let Some(name) = instance_name {
+        metadata.name = name.to_string();
+    }
+
+    // Archive may come from anyone, commands in it are not trusted
+    // Backups made by launcher itself are restored with their settings
+    let settings = metadata.settings.without_commands();
+    if settings != metadata.settings && !upgrade::is_instance_backup(archive_path) {
+        println!("Hooks, environment, JVM arguments and Java path of imported instance were removed");
+        metadata.settings = settings;
+    }
+
+    let instance_dir_path = instance_new(&metadata)?;
+
+    // Extract minecraft directory content
+    // Game files are always downloaded again from version metadata, never taken from archive
+    let result = zip_extract_dir(&mut zip, "minecraft", &instance_dir_path.join("minecraft"), GAME_FILE_ENTRIES)
+        .and_then(|_| instance_install_game(&mut metadata));
+
+    if let Err(e) = result {
+        remove_failed_instance(&instance_dir_path);
+        return Err(e);
+    }
+
+    println!("Instance {} imported succesfully!", metadata.name);
+    Ok(metadata.name)
+}
+
+// Extracts everything under directory prefix inside archive into destination directory
+// Files already in destination are replaced, they may be hard links to official launcher's files
+// Top level entries listed in skip are not extracted
+pub fn zip_extract_dir(zip: &mut ZipArchive<File>, prefix: &str, destination: &Path, skip: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    for i in 0..zip.len() {
+        let mut file = zip.by_index(i)?;
+
+        // enclosed_name rejects paths that would escape destination directory
+        let relative_path = match file.enclosed_name() {
+            Some(path) => match path.strip_prefix(prefix) {
+                Ok(relative_path) if !relative_path.as_os_str().is_empty() => relative_path.to_path_buf(),
+                _ => continue,
+            },
+            None => continue,
+        };
+
+        if relative_path.components().next().is_some_and(|c| skip.iter().any(|s| c.as_os_str() == *s)) {
+            continue;
+        }
+
+        let out_path = destination.join(relative_path);
+        if file.is_dir() {
+            fs::create_dir_all(&out_path)?;
+        } else {
+            let mut content = Vec::new();
+            file.read_to_end(&mut content)?;
+            api::replace_file(&out_path, &content)?;
+        }
+    }
+
+    Ok(())
 }
@@ -0,0 +1,306 @@
+// This is where profiles from the official Minecraft Launcher are imported.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+
+use gtk::{glib};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::instances::instance::{self, InstanceMetadata, InstanceSettings, LoaderKind, ModLoader};
+use crate::minecraft::api;
+
+
+// Entries copied from profile's game directory
+const IMPORT_ENTRIES: &[&str] = &["mods", "saves", "options.txt", "config", "resourcepacks", "shaderpacks", "servers.dat"];
+
+#[derive(Debug, Deserialize)]
+pub struct LauncherProfiles {
+    pub profiles: HashMap<String, LauncherProfile>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LauncherProfile {
+    #[serde(default)]
+    pub name: String,
+    // "latest-release", "latest-snapshot" or "custom"
+    #[serde(rename = "type", default)]
+    pub profile_type: String,
+    #[serde(rename = "lastVersionId")]
+    pub last_version_id: String,
+    #[serde(rename = "gameDir")]
+    pub game_dir: Option<String>,
+    #[serde(rename = "javaArgs")]
+    pub java_args: Option<String>,
+}
+
+impl LauncherProfile {
+    // Built-in profiles have no name in launcher_profiles.json
+    pub fn display_name(&self) -> String {
+        match (self.name.as_str(), self.profile_type.as_str()) {
+            ("", "latest-release") => "Latest release".to_string(),
+            ("", "latest-snapshot") => "Latest snapshot".to_string(),
+            ("", _) => self.last_version_id.clone(),
+            (name, _) => name.to_string(),
+        }
+    }
+}
+
+
+// Official launcher directory - ~/.minecraft
+pub fn official_minecraft_path() -> PathBuf {
+    let home_dir = glib::home_dir();
+
+    let mut minecraft_path = home_dir;
+            minecraft_path.push(".minecraft");
+
+    minecraft_path
+}
+
+// Lists profiles from launcher_profiles.json, sorted by name
+pub fn list_profiles() -> Result<Vec<LauncherProfile>, Box<dyn std::error::Error>> {
+    let profiles_path = official_minecraft_path().join("launcher_profiles.json");
+    let profiles_content = fs::read_to_string(&profiles_path)?;
+    let launcher_profiles: LauncherProfiles = serde_json::from_str(&profiles_content)?;
+
+    let mut profiles: Vec<LauncherProfile> = launcher_profiles.profiles.into_values().collect();
+    profiles.sort_by_key(|p| p.display_name().to_lowercase());
+
+    Ok(profiles)
+}
+
+// Resolves "latest-release" and "latest-snapshot" to actual version ids
+fn resolve_version_id(version_id: &str) -> Result<String, Box<dyn std::error::Error>> {
+    match version_id {
+        "latest-release" => Ok(api::fetch_minecraft_versions()?.latest.release),
+        "latest-snapshot" => Ok(api::fetch_minecraft_versions()?.latest.snapshot),
+        _ => Ok(version_id.to_string()),
+    }
+}
+
+// Reads locally installed version JSON
+fn read_local_version(version_id: &str) -> Option<serde_json::Value> {
+    let mut version_json_path = official_minecraft_path();
+            version_json_path.push("versions");
+            version_json_path.push(version_id);
+            version_json_path.push(format!("{}.json", version_id));
+
+    let version_content = fs::read_to_string(&version_json_path).ok()?;
+    serde_json::from_str(&version_content).ok()
+}
+
+// Detects mod loader from version id created by loader installers
+// Examples: "fabric-loader-0.16.9-1.21.1", "quilt-loader-0.27.1-1.21.1", "1.20.1-forge-47.3.0", "neoforge-21.1.77"
+fn parse_loader(version_id: &str, minecraft_version: &str) -> Option<ModLoader> {
+    if let Some(rest) = version_id.strip_prefix("fabric-loader-") {
+        let version = rest.strip_suffix(&format!("-{}", minecraft_version))?;
+        return Some(ModLoader { kind: LoaderKind::Fabric, version: version.to_string() });
+    }
+
+    if let Some(rest) = version_id.strip_prefix("quilt-loader-") {
+        let version = rest.strip_suffix(&format!("-{}", minecraft_version))?;
+        return Some(ModLoader { kind: LoaderKind::Quilt, version: version.to_string() });
+    }
+
+    if let Some(version) = version_id.strip_prefix("neoforge-") {
+        return Some(ModLoader { kind: LoaderKind::NeoForge, version: version.to_string() });
+    }
+
+    // Older Forge installers also repeat minecraft version: "1.12.2-forge1.12.2-14.23.5.2847"
+    // and the oldest ones capitalize it: "1.7.10-Forge10.13.4.1614-1.7.10"
+    if let Some(index) = version_id.to_ascii_lowercase().find("-forge") {
+        let version = version_id[index + "-forge".len()..].trim_start_matches('-');
+        let version = version.strip_prefix(&format!("{}-", minecraft_version)).unwrap_or(version);
+        return Some(ModLoader { kind: LoaderKind::Forge, version: version.to_string() });
+    }
+
+    None
+}
+
+// Maps javaArgs to instance settings, heap size flags become memory settings
+fn parse_java_args(java_args: &str) -> InstanceSettings {
+    let parse_memory = |value: &str| -> Option<u32> {
+        let (number, unit) = value.split_at(value.find(|c: char| !c.is_ascii_digit())?);
+        let number: u32 = number.parse().ok()?;
+        match unit {
+            "G" | "g" => Some(number * 1024),
+            "M" | "m" => Some(number),
+            _ => None,
+        }
+    };
+
+    let mut settings = InstanceSettings::default();
+
+    for arg in java_args.split_whitespace() {
+        if let Some(value) = arg.strip_prefix("-Xmx")
+            && let Some(memory) = parse_memory(value)
+        {
+            settings.max_memory = Some(memory);
+        } else if let Some(value) = arg.strip_prefix("-Xms")
+            && let Some(memory) = parse_memory(value)
+        {
+            settings.min_memory = Some(memory);
+        } else {
+            settings.jvm_args.push(arg.to_string());
+        }
+    }
+
+    settings
+}
+
+// Hard links file, copying it when linking is not possible (e.g. different filesystem)
+// Launcher never writes into existing game files, api::replace_file renames new file over the link
+fn link_or_copy(source: &Path, destination: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    if !source.is_file() || destination.exists() {
+        return Ok(());
+    }
+
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    if fs::hard_link(source, destination).is_err() {
+        fs::copy(source, destination)?;
+    }
+
+    Ok(())
+}
+
+// Links already downloaded client, libraries and assets into instance
+// Only files of the given versions are reused, anything missing gets downloaded later
+fn reuse_game_files(version_ids: &[&str], mc_instance_dir_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let minecraft_path = official_minecraft_path();
+
+    for version_id in version_ids {
+        let Some(version) = read_local_version(version_id) else { continue };
+
+        println!("Reusing game files of {}...", version_id);
+
+        // Client JAR
+        let client_jar = PathBuf::from("versions").join(version_id).join(format!("{}.jar", version_id));
+        link_or_copy(&minecraft_path.join(&client_jar), &mc_instance_dir_path.join(&client_jar))?;
+
+        // Libraries
+        let libraries = version.get("libraries").and_then(|l| l.as_array()).cloned().unwrap_or_default();
+        for library in libraries {
+            let Some(library_name) = library.get("name").and_then(|n| n.as_str()) else { continue };
+            let Some(relative_path) = api::library_path(library_name) else { continue };
+
+            let relative_path = PathBuf::from("libraries").join(relative_path);
+            link_or_copy(&minecraft_path.join(&relative_path), &mc_instance_dir_path.join(&relative_path))?;
+        }
+
+        // Assets
+        let Some(asset_index_id) = version.pointer("/assetIndex/id").and_then(|id| id.as_str()) else { continue };
+
+        let index_path = PathBuf::from("assets").join("indexes").join(format!("{}.json", asset_index_id));
+        let Ok(index_content) = fs::read_to_string(minecraft_path.join(&index_path)) else { continue };
+        link_or_copy(&minecraft_path.join(&index_path), &mc_instance_dir_path.join(&index_path))?;
+
+        let asset_index: serde_json::Value = serde_json::from_str(&index_content)?;
+        if let Some(objects) = asset_index.get("objects").and_then(|o| o.as_object()) {
+            for asset_info in objects.values() {
+                let Some(hash) = asset_info.get("hash").and_then(|h| h.as_str()) else { continue };
+                if hash.len() < 2 {
+                    continue;
+                }
+
+                let object_path = PathBuf::from("assets").join("objects").join(&hash[0..2]).join(hash);
+                link_or_copy(&minecraft_path.join(&object_path), &mc_instance_dir_path.join(&object_path))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Copies game directory content and links files already downloaded by official launcher
+fn official_copy_files(profile: &LauncherProfile, version_id: &str, minecraft_version: &str, mc_instance_dir_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    // Game directory content
+    let game_dir = profile.game_dir.as_ref().map(PathBuf::from).unwrap_or_else(official_minecraft_path);
+    for entry in IMPORT_ENTRIES {
+        let entry_path = game_dir.join(entry);
+        if entry_path.exists() {
+            println!("Copying {}...", entry);
+            instance::copy_path(&entry_path, &mc_instance_dir_path.join(entry))?;
+        }
+    }
+
+    // Files already downloaded by official launcher
+    let mut version_ids = vec![minecraft_version];
+    if version_id != minecraft_version {
+        version_ids.push(version_id);
+    }
+    reuse_game_files(&version_ids, mc_instance_dir_path)
+}
+
+// Creates instance from official launcher profile
+// When instance_name is None, profile name is used
+pub fn official_import(profile: &LauncherProfile, instance_name: Option<&str>) -> Result<String, Box<dyn std::error::Error>> {
+    let version_id = resolve_version_id(&profile.last_version_id)?;
+
+    // Modded versions inherit from vanilla version
+    let inherits_from = read_local_version(&version_id)
+        .and_then(|v| v.get("inheritsFrom").and_then(|i| i.as_str()).map(|i| i.to_string()));
+
+    let (minecraft_version, loader) = match &inherits_from {
+        Some(minecraft_version) => (minecraft_version.clone(), parse_loader(&version_id, minecraft_version)),
+        None => (version_id.clone(), None),
+    };
+
+    let mut metadata = InstanceMetadata {
+        name: instance_name.map(|n| n.to_string()).unwrap_or_else(|| instance::sanitize_instance_name(&profile.display_name())),
+        minecraft_version,
+        loader,
+        settings: profile.java_args.as_deref().map(parse_java_args).unwrap_or_default(),
+        version_id: None,
+    };
+
+    println!("Importing Minecraft Launcher profile {}...", metadata.name);
+
+    let instance_dir_path = instance::instance_new(&metadata)?;
+
+    // Removing instance only removes hard links, official launcher's files stay
+    if let Err(e) = official_copy_files(profile, &version_id, &metadata.minecraft_version, &instance_dir_path.join("minecraft"))
+        .and_then(|_| instance::instance_install_game(&mut metadata))
+    {
+        instance::remove_failed_instance(&instance_dir_path);
+        return Err(e);
+    }
+
+    println!("Instance {} imported succesfully!", metadata.name);
+    Ok(metadata.name)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn loader(version_id: &str, minecraft_version: &str) -> Option<(LoaderKind, String)> {
+        parse_loader(version_id, minecraft_version).map(|loader| (loader.kind, loader.version))
+    }
+
+    #[test]
+    fn detects_loaders_from_version_ids() {
+        assert_eq!(loader("fabric-loader-0.16.9-1.21.1", "1.21.1"), Some((LoaderKind::Fabric, "0.16.9".to_string())));
+        assert_eq!(loader("quilt-loader-0.27.1-1.21.1", "1.21.1"), Some((LoaderKind::Quilt, "0.27.1".to_string())));
+        assert_eq!(loader("neoforge-21.1.77", "1.21.1"), Some((LoaderKind::NeoForge, "21.1.77".to_string())));
+        assert_eq!(loader("1.20.1-forge-47.3.0", "1.20.1"), Some((LoaderKind::Forge, "47.3.0".to_string())));
+        assert_eq!(loader("1.12.2-forge1.12.2-14.23.5.2847", "1.12.2"), Some((LoaderKind::Forge, "14.23.5.2847".to_string())));
+        assert_eq!(loader("1.21.1", "1.21.1"), None);
+    }
+
+    #[test]
+    fn detects_capitalized_legacy_forge() {
+        assert_eq!(
+            loader("1.7.10-Forge10.13.4.1614-1.7.10", "1.7.10"),
+            Some((LoaderKind::Forge, "10.13.4.1614-1.7.10".to_string()))
+        );
+    }
+}
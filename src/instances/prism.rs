@@ -0,0 +1,237 @@
+// This is where instances from Prism Launcher and MultiMC are imported.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use zip::ZipArchive;
+
+use crate::instances::instance::{self, InstanceMetadata, InstanceSettings, LoaderKind, ModLoader};
+
+
+// Entries copied from Prism's .minecraft directory
+const IMPORT_ENTRIES: &[&str] = &["mods", "saves", "options.txt", "config", "resourcepacks", "shaderpacks", "servers.dat"];
+
+#[derive(Debug, Deserialize)]
+pub struct MmcPack {
+    pub components: Vec<MmcComponent>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MmcComponent {
+    pub uid: String,
+    pub version: Option<String>,
+}
+
+// Instance can come either as folder or as exported zip
+enum PrismSource {
+    Folder(PathBuf),
+    // Archive and path of instance root inside it
+    Archive(ZipArchive<File>, String),
+}
+
+impl PrismSource {
+    fn open(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        if path.is_dir() {
+            return Ok(PrismSource::Folder(path.to_path_buf()));
+        }
+
+        let zip = ZipArchive::new(File::open(path)?)?;
+
+        // Exported instances can have instance.cfg at root or inside single folder
+        let root = zip
+            .file_names()
+            .filter(|name| *name == "instance.cfg" || name.ends_with("/instance.cfg"))
+            .min_by_key(|name| name.len())
+            .map(|name| name.trim_end_matches("instance.cfg").to_string())
+            .ok_or("Archive is not Prism Launcher or MultiMC instance")?;
+
+        Ok(PrismSource::Archive(zip, root))
+    }
+
+    fn read_to_string(&mut self, name: &str) -> Result<String, Box<dyn std::error::Error>> {
+        match self {
+            PrismSource::Folder(path) => Ok(fs::read_to_string(path.join(name))?),
+            PrismSource::Archive(zip, root) => {
+                let mut file = zip.by_name(&format!("{}{}", root, name))?;
+                let mut content = String::new();
+                file.read_to_string(&mut content)?;
+                Ok(content)
+            }
+        }
+    }
+
+    // Game directory is .minecraft in Prism and minecraft in older MultiMC
+    fn game_dir(&self) -> String {
+        let exists = |dir: &str| match self {
+            PrismSource::Folder(path) => path.join(dir).is_dir(),
+            PrismSource::Archive(zip, root) => {
+                let prefix = format!("{}{}/", root, dir);
+                zip.file_names().any(|name| name.starts_with(&prefix))
+            }
+        };
+
+        if exists(".minecraft") { ".minecraft".to_string() } else { "minecraft".to_string() }
+    }
+
+    fn copy_entry(&mut self, entry: &str, destination: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let game_dir = self.game_dir();
+
+        match self {
+            PrismSource::Folder(path) => instance::copy_path(&path.join(&game_dir).join(entry), destination),
+            PrismSource::Archive(zip, root) => {
+                let prefix = format!("{}{}/{}", root, game_dir, entry);
+
+                // Single file entries like options.txt
+                if let Ok(mut file) = zip.by_name(&prefix)
+                    && file.is_file()
+                {
+                    if let Some(parent) = destination.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    let mut out_file = File::create(destination)?;
+                    std::io::copy(&mut file, &mut out_file)?;
+                    return Ok(());
+                }
+
+                instance::zip_extract_dir(zip, &prefix, destination, &[])
+            }
+        }
+    }
+}
+
+// Parses instance.cfg, which is INI file with optional [General] section
+fn parse_instance_cfg(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('[') && !line.starts_with('#') && !line.starts_with(';'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| {
+            let value = value.trim();
+            let value = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')).unwrap_or(value);
+            (key.trim().to_string(), value.to_string())
+        })
+        .collect()
+}
+
+// Maps mmc-pack.json components to minecraft version and mod loader
+fn parse_components(pack: &MmcPack) -> Result<(String, Option<ModLoader>), Box<dyn std::error::Error>> {
+    let mut minecraft_version = None;
+    let mut loader = None;
+
+    for component in &pack.components {
+        let Some(version) = &component.version else { continue };
+
+        let kind = match component.uid.as_str() {
+            "net.minecraft" => {
+                minecraft_version = Some(version.clone());
+                continue;
+            }
+            "net.fabricmc.fabric-loader" => LoaderKind::Fabric,
+            "org.quiltmc.quilt-loader" => LoaderKind::Quilt,
+            "net.minecraftforge" => LoaderKind::Forge,
+            "net.neoforged" => LoaderKind::NeoForge,
+            _ => continue,
+        };
+
+        loader = Some(ModLoader { kind, version: version.clone() });
+    }
+
+    let minecraft_version = minecraft_version.ok_or("Instance does not contain net.minecraft component")?;
+    Ok((minecraft_version, loader))
+}
+
+// Maps instance.cfg JVM and window settings, they are only used when instance overrides global ones
+fn parse_settings(cfg: &HashMap<String, String>) -> InstanceSettings {
+    let enabled = |key: &str| cfg.get(key).is_some_and(|v| v == "true");
+    let number = |key: &str| cfg.get(key).and_then(|v| v.parse::<u32>().ok());
+
+    let mut settings = InstanceSettings::default();
+
+    if enabled("OverrideMemory") {
+        settings.min_memory = number("MinMemAlloc");
+        settings.max_memory = number("MaxMemAlloc");
+    }
+
+    if enabled("OverrideWindow") {
+        settings.window_width = number("MinecraftWinWidth");
+        settings.window_height = number("MinecraftWinHeight");
+    }
+
+    if enabled("OverrideJavaLocation")
+        && let Some(java_path) = cfg.get("JavaPath").filter(|p| !p.is_empty())
+    {
+        settings.java_path = Some(PathBuf::from(java_path));
+    }
+
+    if enabled("OverrideJavaArgs")
+        && let Some(jvm_args) = cfg.get("JvmArgs")
+    {
+        settings.jvm_args = jvm_args.split_whitespace().map(|a| a.to_string()).collect();
+    }
+
+    settings
+}
+
+// Imports Prism Launcher or MultiMC instance from folder or exported zip
+// When instance_name is None, name from instance.cfg is used
+pub fn prism_import(path: &Path, instance_name: Option<&str>) -> Result<String, Box<dyn std::error::Error>> {
+    let mut source = PrismSource::open(path)?;
+
+    let cfg = parse_instance_cfg(&source.read_to_string("instance.cfg")?);
+    let pack: MmcPack = serde_json::from_str(&source.read_to_string("mmc-pack.json")?)?;
+
+    let (minecraft_version, loader) = parse_components(&pack)?;
+
+    // Prism allows names like "1.20/Fabric: Create", they can't be used as directory names
+    let name = match instance_name {
+        Some(name) => name.to_string(),
+        None => instance::sanitize_instance_name(cfg.get("name").ok_or("Instance has no name")?),
+    };
+
+    let mut metadata = InstanceMetadata {
+        name,
+        minecraft_version,
+        loader,
+        settings: match source {
+            PrismSource::Folder(_) => parse_settings(&cfg),
+            // Exported zip may come from anyone, JVM arguments and Java path can run code
+            PrismSource::Archive(..) => parse_settings(&cfg).without_commands(),
+        },
+        version_id: None,
+    };
+
+    println!("Importing Prism Launcher instance {}...", metadata.name);
+
+    let instance_dir_path = instance::instance_new(&metadata)?;
+    let mc_instance_dir_path = instance_dir_path.join("minecraft");
+
+    let result = IMPORT_ENTRIES
+        .iter()
+        .try_for_each(|entry| {
+            println!("Copying {}...", entry);
+            source.copy_entry(entry, &mc_instance_dir_path.join(entry))
+        })
+        .and_then(|_| instance::instance_install_game(&mut metadata));
+
+    if let Err(e) = result {
+        instance::remove_failed_instance(&instance_dir_path);
+        return Err(e);
+    }
+
+    println!("Instance {} imported succesfully!", metadata.name);
+    Ok(metadata.name)
+}
+
+// Checks if zip file is exported Prism Launcher or MultiMC instance
+pub fn is_prism_archive(path: &Path) -> bool {
+    matches!(PrismSource::open(path), Ok(PrismSource::Archive(..)))
+}
@@ -0,0 +1,243 @@
+// This is where Minecraft version and mod loader of existing instances are changed.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// Instance is backed up before every change, backup can be imported back as new instance
+// Game files of previous version are kept, missing files of target version are downloaded
+
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::instances::instance::{self, ExportOptions, InstanceMetadata, LoaderKind, ModLoader};
+use crate::minecraft::api;
+use crate::modrinth::api::{ModrinthApi, modrinth_loader_names};
+
+
+// Backups directory inside instance - .copper-launcher/instances/{instance}/backups
+const BACKUPS_DIR: &str = "backups";
+
+// Things user should know before changing instance
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpgradeWarning {
+    // Worlds opened in newer version can't be opened in older version anymore
+    WorldUpgrade { worlds: usize },
+    // Worlds from newer version can get corrupted in older version
+    WorldDowngrade { worlds: usize },
+    // Mods made for one loader don't load with another one
+    LoaderChanged { from: Option<LoaderKind>, to: Option<LoaderKind> },
+    // Installed mod file does not support target version or loader
+    IncompatibleMod { file_name: String, reason: String },
+    // Mod is not on Modrinth, so it can't be checked
+    UnknownMod { file_name: String },
+}
+
+impl fmt::Display for UpgradeWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let loader_name = |kind: &Option<LoaderKind>| match kind {
+            Some(kind) => format!("{:?}", kind),
+            None => "Vanilla".to_string(),
+        };
+
+        match self {
+            UpgradeWarning::WorldUpgrade { worlds } => write!(f, "{} world(s) will be upgraded and won't open in older versions", worlds),
+            UpgradeWarning::WorldDowngrade { worlds } => write!(f, "{} world(s) come from newer version and can get corrupted", worlds),
+            UpgradeWarning::LoaderChanged { from, to } => write!(f, "Mods for {} won't work with {}", loader_name(from), loader_name(to)),
+            UpgradeWarning::IncompatibleMod { file_name, reason } => write!(f, "{}: {}", file_name, reason),
+            UpgradeWarning::UnknownMod { file_name } => write!(f, "{}: compatibility can't be checked", file_name),
+        }
+    }
+}
+
+
+// Release order of minecraft versions, newer versions have lower index in manifest
+// None when one of versions is unknown
+fn compare_versions(current: &str, target: &str) -> Result<Option<std::cmp::Ordering>, Box<dyn std::error::Error>> {
+    let manifest = api::fetch_minecraft_versions()?;
+    let position = |id: &str| manifest.versions.iter().position(|v| v.id == id);
+
+    Ok(match (position(current), position(target)) {
+        (Some(current), Some(target)) => Some(target.cmp(&current).reverse()),
+        _ => None,
+    })
+}
+
+fn count_worlds(mc_instance_dir_path: &Path) -> usize {
+    match fs::read_dir(mc_instance_dir_path.join("saves")) {
+        Ok(entries) => entries.filter_map(|e| e.ok()).filter(|e| e.path().is_dir()).count(),
+        Err(_) => 0,
+    }
+}
+
+// Enabled mod files in instance's mods directory
+fn mod_files(mc_instance_dir_path: &Path) -> Vec<PathBuf> {
+    let mut mods: Vec<PathBuf> = match fs::read_dir(mc_instance_dir_path.join("mods")) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "jar"))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+
+    mods.sort();
+    mods
+}
+
+// Checks installed mods against target version and loader using Modrinth
+fn check_mods(
+    mc_instance_dir_path: &Path,
+    target_version: &str,
+    target_loader: Option<&ModLoader>,
+    modrinth: &dyn ModrinthApi,
+) -> Result<Vec<UpgradeWarning>, Box<dyn std::error::Error>> {
+    let mut hashed_mods = Vec::new();
+    for path in mod_files(mc_instance_dir_path) {
+        let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        hashed_mods.push((file_name, api::sha1_hex(&fs::read(&path)?)));
+    }
+
+    let hashes: Vec<String> = hashed_mods.iter().map(|(_, hash)| hash.clone()).collect();
+    let versions = modrinth.versions_from_hashes(&hashes, "sha1")?;
+
+    let mut warnings = Vec::new();
+    for (file_name, hash) in hashed_mods {
+        let Some(version) = versions.get(&hash) else {
+            warnings.push(UpgradeWarning::UnknownMod { file_name });
+            continue;
+        };
+
+        if !version.game_versions.iter().any(|v| v == target_version) {
+            warnings.push(UpgradeWarning::IncompatibleMod {
+                file_name,
+                reason: format!("installed version does not support Minecraft {}", target_version),
+            });
+            continue;
+        }
+
+        if let Some(loader) = target_loader
+            && !version.loaders.iter().any(|l| modrinth_loader_names(loader.kind).contains(&l.as_str()))
+        {
+            warnings.push(UpgradeWarning::IncompatibleMod {
+                file_name,
+                reason: format!("installed version does not support {:?}", loader.kind),
+            });
+        }
+    }
+
+    Ok(warnings)
+}
+
+// Lists risks of changing instance to target version and loader
+pub fn upgrade_warnings(
+    metadata: &InstanceMetadata,
+    target_version: &str,
+    target_loader: Option<&ModLoader>,
+    modrinth: &dyn ModrinthApi,
+) -> Result<Vec<UpgradeWarning>, Box<dyn std::error::Error>> {
+    let mc_instance_dir_path = instance::instance_path(&metadata.name).join("minecraft");
+    let mut warnings = Vec::new();
+
+    // Worlds
+    let worlds = count_worlds(&mc_instance_dir_path);
+    if worlds > 0 && target_version != metadata.minecraft_version {
+        match compare_versions(&metadata.minecraft_version, target_version)? {
+            Some(std::cmp::Ordering::Greater) => warnings.push(UpgradeWarning::WorldUpgrade { worlds }),
+            Some(std::cmp::Ordering::Less) => warnings.push(UpgradeWarning::WorldDowngrade { worlds }),
+            // Unknown versions are treated as downgrade, that is the riskier case
+            _ => warnings.push(UpgradeWarning::WorldDowngrade { worlds }),
+        }
+    }
+
+    // Mod loader
+    let current_kind = metadata.loader.as_ref().map(|l| l.kind);
+    let target_kind = target_loader.map(|l| l.kind);
+    let has_mods = !mod_files(&mc_instance_dir_path).is_empty();
+
+    if current_kind != target_kind && has_mods {
+        warnings.push(UpgradeWarning::LoaderChanged { from: current_kind, to: target_kind });
+    }
+
+    // Mods, only checked when they are going to be loaded
+    if has_mods && target_loader.is_some() {
+        warnings.extend(check_mods(&mc_instance_dir_path, target_version, target_loader, modrinth)?);
+    }
+
+    Ok(warnings)
+}
+
+// Backs up everything except downloaded game files
+// Backup is regular instance archive - .copper-launcher/instances/{instance}/backups/{timestamp}.zip
+pub fn instance_backup(instance_name: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let instance_dir_path = instance::instance_path(instance_name);
+    let mc_instance_dir_path = instance_dir_path.join("minecraft");
+
+    let mut include = Vec::new();
+    for entry in fs::read_dir(&mc_instance_dir_path)? {
+        let file_name = entry?.file_name().to_string_lossy().to_string();
+        if !instance::GAME_FILE_ENTRIES.contains(&file_name.as_str()) {
+            include.push(file_name);
+        }
+    }
+    include.sort();
+
+    let options = ExportOptions {
+        include,
+        exclude: Vec::new(),
+        include_saves: true,
+        keep_settings: true,
+    };
+
+    let backups_dir = instance_dir_path.join(BACKUPS_DIR);
+    fs::create_dir_all(&backups_dir)?;
+
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let backup_path = backups_dir.join(format!("{}.zip", timestamp));
+
+    println!("Backing up instance {}...", instance_name);
+    instance::instance_export(instance_name, &backup_path, &options)?;
+
+    Ok(backup_path)
+}
+
+// Checks if archive is backup made by instance_backup - .copper-launcher/instances/{instance}/backups/{timestamp}.zip
+// Only launcher writes there, so backups keep settings that run commands when they are imported
+pub fn is_instance_backup(archive_path: &Path) -> bool {
+    let (Ok(archive_path), Ok(instances_dir_path)) = (archive_path.canonicalize(), instance::instances_path().canonicalize()) else {
+        return false;
+    };
+
+    archive_path.parent().is_some_and(|backups_dir| {
+        backups_dir.file_name().is_some_and(|name| name == BACKUPS_DIR)
+            && backups_dir.parent().and_then(|p| p.parent()) == Some(instances_dir_path.as_path())
+    })
+}
+
+// Changes minecraft version and mod loader of instance in place
+// Loader version has to be made for target minecraft version, None switches to vanilla
+// Returns path of backup made before the change
+pub fn instance_upgrade(instance_name: &str, target_version: &str, target_loader: Option<ModLoader>) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let previous = instance::instance_metadata(instance_name)?;
+    let backup_path = instance_backup(instance_name)?;
+
+    let mut metadata = previous.clone();
+    metadata.minecraft_version = target_version.to_string();
+    metadata.loader = target_loader;
+    metadata.version_id = None;
+
+    println!("Changing instance {} to Minecraft {}...", instance_name, target_version);
+
+    // Files of previous version are still there, so previous metadata keeps instance playable
+    if let Err(e) = instance::instance_install_game(&mut metadata) {
+        instance::write_instance_metadata(&instance::instance_path(instance_name), &previous)?;
+        return Err(format!("Failed to change instance, backup is at {}: {}", backup_path.display(), e).into());
+    }
+
+    println!("Instance {} changed succesfully!", instance_name);
+    Ok(backup_path)
+}
@@ -0,0 +1,119 @@
+// This is where launcher preferences are stored.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+
+use gtk::{glib};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::auth::microsoft;
+use crate::curseforge::api::CURSEFORGE_API_URL;
+use crate::instances::instance::InstanceSettings;
+
+
+// Launcher config file - .copper-launcher/config.json
+const CONFIG_FILE: &str = "config.json";
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct LauncherConfig {
+    #[serde(default)]
+    pub curseforge: CurseForgeConfig,
+    #[serde(default)]
+    pub microsoft: MicrosoftConfig,
+    // Account tokens are kept in Secret Service instead of encrypted file
+    #[serde(default)]
+    pub secret_service: bool,
+    // Used for every instance setting that instance does not set itself
+    #[serde(default)]
+    pub instance_defaults: InstanceSettings,
+}
+
+// CurseForge API needs key from https://console.curseforge.com
+// URL can point to local stand-in for testing
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CurseForgeConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    #[serde(default = "default_curseforge_url")]
+    pub api_url: String,
+}
+
+impl Default for CurseForgeConfig {
+    fn default() -> Self {
+        CurseForgeConfig {
+            api_key: None,
+            api_url: default_curseforge_url(),
+        }
+    }
+}
+
+fn default_curseforge_url() -> String {
+    CURSEFORGE_API_URL.to_string()
+}
+
+// Microsoft login needs client ID of Azure application with Minecraft API access
+// Endpoints can point to local mock for testing
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct MicrosoftConfig {
+    pub client_id: String,
+    pub device_code_url: String,
+    pub token_url: String,
+    pub xbox_live_url: String,
+    pub xsts_url: String,
+    pub minecraft_login_url: String,
+    pub entitlements_url: String,
+    pub profile_url: String,
+}
+
+impl Default for MicrosoftConfig {
+    fn default() -> Self {
+        MicrosoftConfig {
+            client_id: String::new(),
+            device_code_url: microsoft::DEVICE_CODE_URL.to_string(),
+            token_url: microsoft::TOKEN_URL.to_string(),
+            xbox_live_url: microsoft::XBOX_LIVE_URL.to_string(),
+            xsts_url: microsoft::XSTS_URL.to_string(),
+            minecraft_login_url: microsoft::MINECRAFT_LOGIN_URL.to_string(),
+            entitlements_url: microsoft::ENTITLEMENTS_URL.to_string(),
+            profile_url: microsoft::PROFILE_URL.to_string(),
+        }
+    }
+}
+
+
+// Launcher home directory - .copper-launcher
+pub fn launcher_path() -> PathBuf {
+    let home_dir = glib::home_dir();
+
+    let mut launcher_path = home_dir;
+            launcher_path.push(".copper-launcher");
+
+    launcher_path
+}
+
+// Missing or broken config falls back to defaults
+pub fn load_config() -> LauncherConfig {
+    let config_path = launcher_path().join(CONFIG_FILE);
+
+    let Ok(config_content) = fs::read_to_string(&config_path) else {
+        return LauncherConfig::default();
+    };
+
+    serde_json::from_str(&config_content).unwrap_or_else(|e| {
+        eprintln!("Failed to read launcher config, using defaults: {}", e);
+        LauncherConfig::default()
+    })
+}
+
+pub fn save_config(config: &LauncherConfig) -> Result<(), Box<dyn std::error::Error>> {
+    let config_json = serde_json::to_string_pretty(config)?;
+    fs::write(launcher_path().join(CONFIG_FILE), config_json)?;
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+// This is where logs are uploaded for sharing.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// Logs go to mclo.gs, it removes IP addresses and access tokens from them
+// API documentation: https://mclo.gs/api
+
+
+use serde::Deserialize;
+
+
+const MCLOGS_API_URL: &str = "https://api.mclo.gs/1/log";
+
+#[derive(Debug, Deserialize)]
+struct PasteResponse {
+    success: bool,
+    url: Option<String>,
+    error: Option<String>,
+}
+
+
+// Returns URL of uploaded log
+pub fn upload_log(content: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let response: PasteResponse = reqwest::blocking::Client::new()
+        .post(MCLOGS_API_URL)
+        .form(&[("content", content)])
+        .send()?
+        .json()?;
+
+    match (response.success, response.url) {
+        (true, Some(url)) => Ok(url),
+        _ => Err(response.error.unwrap_or_else(|| "Failed to upload log".to_string()).into()),
+    }
+}
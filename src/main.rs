@@ -11,14 +11,55 @@ use gtk::{Application, glib};
 use std::{fs, path::PathBuf};
 
 mod ui {
+    pub mod crash_window;
     pub mod launcher_ui;
+    pub mod log_console;
+}
+mod auth {
+    pub mod accounts;
+    pub mod authlib_injector;
+    pub mod microsoft;
+    pub mod offline;
+    pub mod skins;
+    pub mod store;
+}
+mod curseforge {
+    pub mod api;
+    pub mod install;
+    pub mod modpack;
+}
+mod launcher {
+    pub mod config;
+    pub mod paste;
 }
 mod instances {
     pub mod instance;
+    pub mod official;
+    pub mod prism;
+    pub mod upgrade;
 }
 mod minecraft {
     pub mod api;
+    pub mod crash;
+    pub mod fabric;
+    pub mod forge;
     pub mod game_launch;
+    pub mod game_process;
+    pub mod hooks;
+    pub mod log4j;
+    pub mod neoforge;
+    pub mod quilt;
+}
+mod modrinth {
+    pub mod api;
+    pub mod install;
+    pub mod mrpack;
+}
+mod mods {
+    pub mod checker;
+    pub mod manager;
+    pub mod metadata;
+    pub mod updates;
 }
 
 const APP_ID: &str = "com.github.suverent-shiro.Copper-Launcher";
@@ -27,6 +68,7 @@ fn main() -> glib::ExitCode {
     println!("Lauching Copper Launcher");
 
     launcher_home();
+    auth::accounts::spawn_refresh_thread();
 
 
     let app = Application::builder().application_id(APP_ID).build();
@@ -7,9 +7,11 @@
 
 
 use serde::{Deserialize, Serialize};
-use std::fs::{self, File};
-use std::io::{Write, copy};
-use std::path::PathBuf;
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha512};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 
 #[derive(Debug, Deserialize)]
@@ -46,6 +48,106 @@ pub struct VersionDetails {
     pub assets: String,
     #[serde(rename = "mainClass")]
     pub main_class: String,
+    #[serde(rename = "type", default)]
+    pub version_type: String,
+    // Versions since 1.13 use arguments, older ones use minecraftArguments
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<Arguments>,
+    #[serde(rename = "minecraftArguments", default, skip_serializing_if = "Option::is_none")]
+    pub minecraft_arguments: Option<String>,
+    #[serde(rename = "javaVersion", default, skip_serializing_if = "Option::is_none")]
+    pub java_version: Option<JavaVersion>,
+    // Versions since 1.7 have log4j config
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logging: Option<Logging>,
+}
+
+// Partial version JSON written by mod loaders, it inherits everything else from vanilla version
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LoaderProfile {
+    pub id: String,
+    #[serde(rename = "inheritsFrom")]
+    pub inherits_from: String,
+    #[serde(rename = "mainClass")]
+    pub main_class: String,
+    #[serde(default)]
+    pub libraries: Vec<Library>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<Arguments>,
+    #[serde(rename = "minecraftArguments", default, skip_serializing_if = "Option::is_none")]
+    pub minecraft_arguments: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Arguments {
+    #[serde(default)]
+    pub game: Vec<Argument>,
+    #[serde(default)]
+    pub jvm: Vec<Argument>,
+}
+
+// Argument is either plain string or value that applies only when rules match
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Argument {
+    Plain(String),
+    Conditional {
+        rules: Vec<Rule>,
+        value: ArgumentValue,
+    },
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ArgumentValue {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Rule {
+    // "allow" or "disallow"
+    pub action: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub os: Option<OsRule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub features: Option<HashMap<String, bool>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OsRule {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arch: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Logging {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client: Option<LoggingConfig>,
+}
+
+// "argument" has ${path} placeholder for config file
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LoggingConfig {
+    pub argument: String,
+    pub file: LoggingFile,
+    #[serde(rename = "type")]
+    pub config_type: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LoggingFile {
+    pub id: String,
+    pub sha1: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct JavaVersion {
+    #[serde(rename = "majorVersion")]
+    pub major_version: u32,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -54,22 +156,38 @@ pub struct Downloads {
     pub server: Option<DownloadInfo>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DownloadInfo {
     pub sha1: String,
     pub size: u64,
     pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Library {
     pub name: String,
+    #[serde(default)]
     pub downloads: LibraryDownloads,
+    // Maven repository for libraries without downloads (Fabric, Quilt, Forge)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha1: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<Vec<Rule>>,
+    // Natives of versions before 1.19, maps OS name to classifier
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub natives: Option<HashMap<String, String>>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct LibraryDownloads {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub artifact: Option<DownloadInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub classifiers: Option<HashMap<String, DownloadInfo>>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -134,23 +252,172 @@ pub fn get_version_url(version_id: &str) -> Result<String, Box<dyn std::error::E
 
 // Fetch detailed version information
 pub fn fetch_version_details(version_id: &str) -> Result<VersionDetails, Box<dyn std::error::Error>> {
-    let version_url = get_version_url(version_id)?;
-    let response = reqwest::blocking::get(&version_url)?;
-    let details: VersionDetails = response.json()?;
+    let details: VersionDetails = serde_json::from_str(&fetch_version_json(version_id)?)?;
     Ok(details)
 }
 
-// Download a file from URL to a path
-fn download_file(url: &str, path: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
-    // Create parent directories if they don't exist
+// Version JSON exactly as Mojang serves it, VersionDetails doesn't have every field of it
+pub fn fetch_version_json(version_id: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let version_url = get_version_url(version_id)?;
+    let response = reqwest::blocking::get(&version_url)?.error_for_status()?;
+    Ok(response.text()?)
+}
+
+// Lowercase hex encoding of hash output
+fn hex_digest(digest: &[u8]) -> String {
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn sha1_hex(data: &[u8]) -> String {
+    hex_digest(&Sha1::digest(data))
+}
+
+pub fn sha256_hex(data: &[u8]) -> String {
+    hex_digest(&Sha256::digest(data))
+}
+
+pub fn sha512_hex(data: &[u8]) -> String {
+    hex_digest(&Sha512::digest(data))
+}
+
+// Download a file and check it against expected hashes
+// File is only written when all given hashes match
+pub fn download_file_verified(url: &str, path: &PathBuf, sha1: Option<&str>, sha512: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let response = reqwest::blocking::get(url)?.error_for_status()?;
+    let content = response.bytes()?;
+
+    if let Some(expected) = sha1 {
+        let actual = sha1_hex(&content);
+        if !actual.eq_ignore_ascii_case(expected) {
+            return Err(format!("SHA-1 mismatch for {}: expected {}, got {}", url, expected, actual).into());
+        }
+    }
+
+    if let Some(expected) = sha512 {
+        let actual = sha512_hex(&content);
+        if !actual.eq_ignore_ascii_case(expected) {
+            return Err(format!("SHA-512 mismatch for {}: expected {}, got {}", url, expected, actual).into());
+        }
+    }
+
+    replace_file(path, &content)?;
+
+    Ok(())
+}
+
+// Writes file under temporary name and renames it over the old one
+// Game files can be hard links to official launcher's files (see instances/official.rs),
+// rename only replaces the link, so the other launcher's copy stays untouched
+pub fn replace_file(path: &Path, content: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
-    
-    let response = reqwest::blocking::get(url)?;
-    let mut file = File::create(path)?;
+
+    let mut temp_name = path.as_os_str().to_owned();
+        temp_name.push(".part");
+    let temp_path = PathBuf::from(temp_name);
+
+    fs::write(&temp_path, content)?;
+    if let Err(e) = fs::rename(&temp_path, path) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(e.into());
+    }
+
+    Ok(())
+}
+
+// Checks if file was already downloaded and is not corrupted
+pub fn is_file_valid(path: &PathBuf, sha1: &str) -> bool {
+    match fs::read(path) {
+        Ok(content) => sha1_hex(&content).eq_ignore_ascii_case(sha1),
+        Err(_) => false,
+    }
+}
+
+// OS name as used in version JSON rules
+pub fn os_name() -> &'static str {
+    match std::env::consts::OS {
+        "macos" => "osx",
+        os => os,
+    }
+}
+
+// Checks if rules allow something on this system
+// Without rules everything is allowed, otherwise last matching rule decides
+// Features are things like has_custom_resolution, missing features count as disabled
+pub fn rules_allow(rules: &[Rule], features: &HashMap<String, bool>) -> bool {
+    if rules.is_empty() {
+        return true;
+    }
+
+    let mut allowed = false;
+
+    for rule in rules {
+        let os_matches = rule.os.as_ref().is_none_or(|os| {
+            os.name.as_deref().is_none_or(|name| name == os_name())
+                && os.arch.as_deref().is_none_or(|arch| arch == "x86" && std::env::consts::ARCH == "x86")
+        });
+
+        let features_match = rule.features.as_ref().is_none_or(|rule_features| {
+            rule_features.iter().all(|(feature, value)| features.get(feature).copied().unwrap_or(false) == *value)
+        });
+
+        if os_matches && features_match {
+            allowed = rule.action == "allow";
+        }
+    }
+
+    allowed
+}
+
+impl Library {
+    pub fn is_allowed(&self) -> bool {
+        rules_allow(self.rules.as_deref().unwrap_or_default(), &HashMap::new())
+    }
+
+    // Natives classifier for this system, only for versions before 1.19
+    pub fn native_classifier(&self) -> Option<String> {
+        let classifier = self.natives.as_ref()?.get(os_name())?;
+        let arch = if cfg!(target_pointer_width = "64") { "64" } else { "32" };
+
+        Some(classifier.replace("${arch}", arch))
+    }
+}
+
+// Library path from its maven name, relative to libraries directory
+// Format: "com.mojang:authlib:1.5.25" -> "com/mojang/authlib/1.5.25/authlib-1.5.25.jar"
+// Classifier and extension are supported: "group:name:version:classifier@ext"
+pub fn library_path(library_name: &str) -> Option<PathBuf> {
+    let (coordinates, extension) = library_name.split_once('@').unwrap_or((library_name, "jar"));
+
+    let parts: Vec<&str> = coordinates.split(':').collect();
+    if parts.len() < 3 || parts.len() > 4 {
+        return None;
+    }
+
+    let group = parts[0].replace('.', "/");
+    let name = parts[1];
+    let version = parts[2];
+
+    let file_name = match parts.get(3) {
+        Some(classifier) => format!("{}-{}-{}.{}", name, version, classifier, extension),
+        None => format!("{}-{}.{}", name, version, extension),
+    };
+
+    let mut lib_path = PathBuf::from(group);
+            lib_path.push(name);
+            lib_path.push(version);
+            lib_path.push(file_name);
+
+    Some(lib_path)
+}
+
+// Download a file from URL to a path
+pub fn download_file(url: &str, path: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+    // Error pages must not end up in place of game files
+    let response = reqwest::blocking::get(url)?.error_for_status()?;
     let content = response.bytes()?;
-    file.write_all(&content)?;
+    replace_file(path, &content)?;
     
     Ok(())
 }
@@ -158,7 +425,8 @@ fn download_file(url: &str, path: &PathBuf) -> Result<(), Box<dyn std::error::Er
 // Download Minecraft client JAR
 pub fn download_minecraft_client(version_id: &str, instance_path: &PathBuf,) -> Result<(), Box<dyn std::error::Error>> {
     println!("Fetching version details for {}...", version_id);
-    let details = fetch_version_details(version_id)?;
+    let version_json = fetch_version_json(version_id)?;
+    let details: VersionDetails = serde_json::from_str(&version_json)?;
     
     // Create versions directory
     let mut versions_dir = instance_path.clone();
@@ -171,15 +439,18 @@ pub fn download_minecraft_client(version_id: &str, instance_path: &PathBuf,) ->
     let mut client_jar_path = versions_dir.clone();
             client_jar_path.push(format!("{}.jar", version_id));
     
-    println!("Downloading client JAR...");
-    download_file(&details.downloads.client.url, &client_jar_path)?;
+    if is_file_valid(&client_jar_path, &details.downloads.client.sha1) {
+        println!("Client JAR already downloaded");
+    } else {
+        println!("Downloading client JAR...");
+        download_file_verified(&details.downloads.client.url, &client_jar_path, Some(&details.downloads.client.sha1), None)?;
+    }
     
-    // Save version JSON
+    // Save version JSON, kept as is so fields launcher learns to use later are there
     let mut version_json_path = versions_dir.clone();
             version_json_path.push(format!("{}.json", version_id));
     
-    let version_json = serde_json::to_string_pretty(&details)?;
-    fs::write(&version_json_path, version_json)?;
+    replace_file(&version_json_path, version_json.as_bytes())?;
     
     println!("Client downloaded successfully!");
     Ok(())
@@ -197,31 +468,83 @@ pub fn download_libraries(version_id: &str, instance_path: &PathBuf,) -> Result<
     println!("Found {} libraries to download", details.libraries.len());
     
     for (i, library) in details.libraries.iter().enumerate() {
-        if let Some(artifact) = &library.downloads.artifact {
-            // Parse library name to create directory structure
-            // Format: "com.mojang:authlib:1.5.25" -> "com/mojang/authlib/1.5.25/authlib-1.5.25.jar"
-            let parts: Vec<&str> = library.name.split(':').collect();
-            if parts.len() == 3 {
-                let group = parts[0].replace('.', "/");
-                let name = parts[1];
-                let version = parts[2];
-                
-                let mut lib_path = libraries_dir.clone();
-                        lib_path.push(&group);
-                        lib_path.push(name);
-                        lib_path.push(version);
-                        lib_path.push(format!("{}-{}.jar", name, version));
-                
-                println!("Downloading library {}/{}: {}", i + 1, details.libraries.len(), library.name);
-                download_file(&artifact.url, &lib_path)?;
+        if !library.is_allowed() {
+            continue;
+        }
+
+        // Natives of versions before 1.19 are separate classifier downloads
+        if let Some(classifier) = library.native_classifier()
+            && let Some(native) = library.downloads.classifiers.as_ref().and_then(|c| c.get(&classifier))
+            && let Some(relative_path) = library_path(&format!("{}:{}", library.name, classifier))
+        {
+            let native_path = libraries_dir.join(relative_path);
+            if !is_file_valid(&native_path, &native.sha1) {
+                println!("Downloading natives {}/{}: {}", i + 1, details.libraries.len(), library.name);
+                download_file_verified(&native.url, &native_path, Some(&native.sha1), None)?;
             }
         }
+
+        if let Some(artifact) = &library.downloads.artifact
+            && let Some(relative_path) = library_path(&library.name)
+        {
+            let lib_path = libraries_dir.join(relative_path);
+
+            // Libraries that are already there are kept
+            if is_file_valid(&lib_path, &artifact.sha1) {
+                continue;
+            }
+
+            println!("Downloading library {}/{}: {}", i + 1, details.libraries.len(), library.name);
+            download_file_verified(&artifact.url, &lib_path, Some(&artifact.sha1), None)?;
+        }
     }
     
     println!("Libraries downloaded successfully!");
     Ok(())
 }
 
+// Download libraries listed by mod loader profiles
+// They either have Mojang style downloads or just maven repository URL
+pub fn download_loader_libraries(libraries: &[Library], instance_path: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+    let mut libraries_dir = instance_path.clone();
+            libraries_dir.push("minecraft");
+            libraries_dir.push("libraries");
+
+    for (i, library) in libraries.iter().enumerate() {
+        if !library.is_allowed() {
+            continue;
+        }
+
+        let Some(relative_path) = library_path(&library.name) else { continue };
+        let lib_path = libraries_dir.join(&relative_path);
+
+        let (url, sha1) = match (&library.downloads.artifact, &library.url) {
+            (Some(artifact), _) => (artifact.url.clone(), Some(artifact.sha1.clone())),
+            (None, Some(repository)) => (
+                format!("{}/{}", repository.trim_end_matches('/'), relative_path.to_string_lossy()),
+                library.sha1.clone(),
+            ),
+            (None, None) => continue,
+        };
+
+        // Some loader libraries are generated locally and have no URL
+        if url.is_empty() {
+            continue;
+        }
+
+        match &sha1 {
+            Some(sha1) if is_file_valid(&lib_path, sha1) => continue,
+            None if lib_path.exists() => continue,
+            _ => {}
+        }
+
+        println!("Downloading library {}/{}: {}", i + 1, libraries.len(), library.name);
+        download_file_verified(&url, &lib_path, sha1.as_deref(), None)?;
+    }
+
+    Ok(())
+}
+
 // Download assets
 pub fn download_assets(version_id: &str, instance_path: &PathBuf,) -> Result<(), Box<dyn std::error::Error>> {
     println!("Fetching assets for {}...", version_id);
@@ -239,8 +562,10 @@ pub fn download_assets(version_id: &str, instance_path: &PathBuf,) -> Result<(),
     let mut index_path = indexes_dir.clone();
             index_path.push(format!("{}.json", details.asset_index.id));
     
-    println!("Downloading asset index...");
-    download_file(&details.asset_index.url, &index_path)?;
+    if !is_file_valid(&index_path, &details.asset_index.sha1) {
+        println!("Downloading asset index...");
+        download_file_verified(&details.asset_index.url, &index_path, Some(&details.asset_index.sha1), None)?;
+    }
     
     // Parse asset index to download individual assets
     let index_content = fs::read_to_string(&index_path)?;
@@ -265,7 +590,7 @@ pub fn download_assets(version_id: &str, instance_path: &PathBuf,) -> Result<(),
                     if i % 100 == 0 {
                         println!("Downloading assets: {}/{}", i, total_assets);
                     }
-                    download_file(&asset_url, &asset_path)?;
+                    download_file_verified(&asset_url, &asset_path, Some(hash), None)?;
                 }
             }
         }
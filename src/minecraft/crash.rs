@@ -0,0 +1,298 @@
+// This is where game crashes are detected and analyzed.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// Game crashed when it exited with non-zero code, died to signal nobody sent, or wrote crash files:
+//  - minecraft/crash-reports/crash-*.txt    written by the game
+//  - minecraft/hs_err_pid*.log              written by JVM when it crashes itself
+// Report and end of game log are searched for known errors and for mods in stack traces
+
+
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use zip::ZipArchive;
+
+use crate::instances::instance;
+use crate::minecraft::game_process::ProcessState;
+use crate::mods::manager::{self, InstalledMod};
+
+
+// Lines of game log shown when game wrote no crash file
+const LOG_TAIL_LINES: usize = 200;
+
+// Stack frames are looked up in mod jars only up to this many classes
+const MAX_CLASS_LOOKUPS: usize = 40;
+
+// Classes of Java, the game, mod loaders and common libraries, they never point at a mod
+const KNOWN_PACKAGES: &[&str] = &[
+    "java.", "javax.", "jdk.", "sun.", "com.sun.",
+    "net.minecraft.", "com.mojang.", "org.lwjgl.",
+    "net.fabricmc.", "org.quiltmc.", "cpw.mods.", "net.minecraftforge.", "net.neoforged.",
+    "org.spongepowered.", "org.objectweb.", "com.llamalad7.",
+    "com.google.", "org.apache.", "io.netty.", "it.unimi.", "org.slf4j.", "oshi.", "kotlin.",
+];
+
+#[derive(Debug, Clone)]
+pub struct CrashReport {
+    pub state: ProcessState,
+    // Crash files written during session, newest first
+    pub files: Vec<PathBuf>,
+    // Newest crash file, or end of game log when there is none
+    pub text: String,
+    // Known errors with what to do about them
+    pub problems: Vec<String>,
+    // Mods that appear in report, most likely first
+    pub suspects: Vec<InstalledMod>,
+}
+
+// Something in report that can point at a mod
+enum Clue {
+    ModId(String),
+    FileName(String),
+    // Path of class inside jar, e.g. "com/example/Thing.class"
+    Class(String),
+}
+
+
+// Game directory of instance - .copper-launcher/instances/{instance}/minecraft
+fn game_path(instance_name: &str) -> PathBuf {
+    instance::instance_path(instance_name).join("minecraft")
+}
+
+// Files in directory with name matching, modified after session started
+fn new_files(dir: &Path, started: SystemTime, matches: impl Fn(&str) -> bool) -> Vec<(SystemTime, PathBuf)> {
+    let Ok(entries) = fs::read_dir(dir) else { return Vec::new() };
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| matches(&entry.file_name().to_string_lossy()))
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            (modified >= started).then(|| (modified, entry.path()))
+        })
+        .collect()
+}
+
+fn crash_files(instance_name: &str, started: SystemTime) -> Vec<PathBuf> {
+    let game_dir_path = game_path(instance_name);
+
+    let mut files = new_files(&game_dir_path.join("crash-reports"), started, |name| name.ends_with(".txt"));
+    files.extend(new_files(&game_dir_path, started, |name| name.starts_with("hs_err_pid") && name.ends_with(".log")));
+
+    files.sort_by_key(|(modified, _)| Reverse(*modified));
+    files.into_iter().map(|(_, path)| path).collect()
+}
+
+fn log_tail(log: &str) -> String {
+    let lines: Vec<&str> = log.lines().collect();
+    lines[lines.len().saturating_sub(LOG_TAIL_LINES)..].join("\n")
+}
+
+// Java version from "class file version 65.0", class file versions start at 45 for Java 1
+fn required_java(text: &str) -> Option<u32> {
+    let (_, rest) = text.split_once("class file version ")?;
+    let major: u32 = rest.split(|c: char| !c.is_ascii_digit()).next()?.parse().ok()?;
+    major.checked_sub(44)
+}
+
+// Known errors found in text
+fn find_problems(text: &str) -> Vec<String> {
+    let mut problems = Vec::new();
+    let has = |needle: &str| text.contains(needle);
+
+    if has("UnsupportedClassVersionError") || has("compiled by a more recent version") {
+        problems.push(match required_java(text) {
+            Some(java) => format!("Game or a mod needs Java {} or newer, choose it in instance settings", java),
+            None => "Game or a mod needs newer Java, choose it in instance settings".to_string(),
+        });
+    }
+    if has("java.lang.OutOfMemoryError") {
+        problems.push("Game ran out of memory, raise maximum memory in instance settings".to_string());
+    }
+    if has("There is insufficient memory for the Java Runtime") {
+        problems.push("Computer ran out of memory, lower maximum memory or close other programs".to_string());
+    }
+    if has("Could not reserve enough space for object heap") {
+        problems.push("Java could not reserve memory, lower maximum memory or use 64-bit Java".to_string());
+    }
+    if has("Unrecognized VM option") || has("Unrecognized option") || has("Could not create the Java Virtual Machine") {
+        problems.push("Java rejected its arguments, check JVM arguments and garbage collector in instance settings".to_string());
+    }
+    if has("GLFW error 65542") || has("Pixel format not accelerated") || has("The driver does not appear to support OpenGL") {
+        problems.push("Graphics driver does not support OpenGL, update graphics drivers".to_string());
+    }
+    if has("EXCEPTION_ACCESS_VIOLATION") || has("SIGSEGV") {
+        problems.push("Java crashed in native code, usually caused by graphics driver or mod with native code".to_string());
+    }
+    if has("Mixin apply failed") || has("MixinApplyError") || has("InvalidInjectionException") {
+        problems.push("Mod failed to modify game code, it's probably made for other game or loader version".to_string());
+    }
+    if has("Incompatible mods found") || has("Mod resolution failed") || has("ModResolutionException") {
+        problems.push("Mod loader found missing or incompatible mods, see report for details".to_string());
+    }
+    if has("java.lang.NoClassDefFoundError") || has("java.lang.ClassNotFoundException") || has("java.lang.NoSuchMethodError") {
+        problems.push("Some code is missing, a mod or its dependency is missing or has wrong version".to_string());
+    }
+
+    problems
+}
+
+// Word after "for mod " or "from mod ", like in "Mixin apply for mod sodium failed"
+fn words_after<'a>(text: &'a str, prefix: &'a str) -> impl Iterator<Item = String> + 'a {
+    text.match_indices(prefix).filter_map(move |(index, _)| {
+        let word: String = text[index + prefix.len()..]
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '-')
+            .collect();
+        (!word.is_empty()).then_some(word)
+    })
+}
+
+// Mod ids in Forge "Suspected Mods:" section, lines like "Example Mod (examplemod), Version: 1.0"
+fn suspected_section(text: &str) -> Vec<String> {
+    let mut ids = Vec::new();
+    let mut in_section = false;
+
+    for line in text.lines() {
+        let line = line.trim();
+        if line.starts_with("Suspected Mod") {
+            in_section = true;
+            continue;
+        }
+        if !in_section {
+            continue;
+        }
+
+        match line.split_once('(').and_then(|(_, rest)| rest.split_once(')')) {
+            Some((id, _)) => ids.push(id.to_string()),
+            None => in_section = false,
+        }
+    }
+
+    ids
+}
+
+// Clue from stack frame like "at TRANSFORMER/examplemod@1.0/com.example.Thing.tick(Thing.java:10) ~[examplemod-1.0.jar:?]"
+fn frame_clues(line: &str, clues: &mut Vec<Clue>) {
+    let Some(frame) = line.trim_start().strip_prefix("at ") else { return };
+    let Some((location, rest)) = frame.split_once('(') else { return };
+
+    // Module path before class, Forge puts mod id there
+    let mut segments: Vec<&str> = location.split('/').collect();
+    let Some(method) = segments.pop() else { return };
+    for module in segments {
+        if let Some((id, _)) = module.split_once('@') {
+            clues.push(Clue::ModId(id.to_string()));
+        }
+    }
+
+    // Jar that class came from
+    let jar = rest.split_once('[').and_then(|(_, jar)| jar.split([':', ']']).next());
+    if let Some(file_name) = jar.filter(|f| f.ends_with(".jar")) {
+        clues.push(Clue::FileName(file_name.to_string()));
+    }
+
+    if KNOWN_PACKAGES.iter().any(|package| method.starts_with(package)) {
+        return;
+    }
+
+    // "com.example.Thing$Inner.tick" - class is in com/example/Thing.class
+    let Some((class, _)) = method.rsplit_once('.') else { return };
+    let class = class.split('$').next().unwrap_or(class);
+    if class.contains('.') {
+        clues.push(Clue::Class(format!("{}.class", class.replace('.', "/"))));
+    }
+}
+
+// Clues in order they appear, explicit mentions of mods first
+fn find_clues(text: &str) -> Vec<Clue> {
+    let mut clues: Vec<Clue> = suspected_section(text).into_iter().map(Clue::ModId).collect();
+    clues.extend(words_after(text, "for mod ").map(Clue::ModId));
+    clues.extend(words_after(text, "from mod ").map(Clue::ModId));
+
+    for line in text.lines() {
+        frame_clues(line, &mut clues);
+    }
+
+    clues
+}
+
+// Installed mods clues point at, in order of clues
+fn find_suspects(instance_name: &str, text: &str) -> Vec<InstalledMod> {
+    let clues = find_clues(text);
+    if clues.is_empty() {
+        return Vec::new();
+    }
+
+    let mods = match manager::list_mods(instance_name) {
+        Ok(mods) => mods,
+        Err(e) => {
+            eprintln!("Failed to list mods: {}", e);
+            return Vec::new();
+        }
+    };
+
+    // Jars are opened once, only when some class has to be looked up
+    let mut jars: Option<Vec<Option<ZipArchive<File>>>> = None;
+    let mut looked_up = HashSet::new();
+    let mut suspects: Vec<usize> = Vec::new();
+
+    for clue in clues {
+        let found = match &clue {
+            Clue::ModId(id) => mods.iter().position(|m| {
+                m.metadata.as_ref().is_some_and(|metadata| metadata.id == *id || metadata.provides.contains(id))
+            }),
+            Clue::FileName(file_name) => mods.iter().position(|m| m.file_name == *file_name),
+            Clue::Class(class_path) => {
+                if looked_up.len() >= MAX_CLASS_LOOKUPS || !looked_up.insert(class_path.clone()) {
+                    continue;
+                }
+
+                let jars = jars.get_or_insert_with(|| {
+                    mods.iter().map(|m| File::open(&m.path).ok().and_then(|f| ZipArchive::new(f).ok())).collect()
+                });
+                jars.iter_mut().position(|jar| jar.as_mut().is_some_and(|jar| jar.by_name(class_path).is_ok()))
+            }
+        };
+
+        if let Some(index) = found.filter(|index| !suspects.contains(index)) {
+            suspects.push(index);
+        }
+    }
+
+    suspects.into_iter().map(|index| mods[index].clone()).collect()
+}
+
+// Called when game stops, None when it exited normally
+pub fn detect_crash(instance_name: &str, state: ProcessState, started: SystemTime, log: &str) -> Option<CrashReport> {
+    let files = crash_files(instance_name, started);
+    if state == ProcessState::Exited(0) && files.is_empty() {
+        return None;
+    }
+
+    let text = match files.first().map(fs::read) {
+        Some(Ok(content)) => String::from_utf8_lossy(&content).to_string(),
+        Some(Err(e)) => {
+            eprintln!("Failed to read crash report: {}", e);
+            log_tail(log)
+        }
+        None => log_tail(log),
+    };
+
+    // Crash file may not have the error that stopped the game, log usually has it
+    let searched = format!("{}\n{}", text, log_tail(log));
+
+    Some(CrashReport {
+        state,
+        problems: find_problems(&searched),
+        suspects: find_suspects(instance_name, &searched),
+        files,
+        text,
+    })
+}
@@ -0,0 +1,79 @@
+// This is where Fabric loader is installed.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// Fabric meta documentation: https://github.com/FabricMC/fabric-meta
+
+
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::minecraft::api::{self, LoaderProfile};
+
+
+pub const FABRIC_META_URL: &str = "https://meta.fabricmc.net/v2";
+
+#[derive(Debug, Deserialize)]
+pub struct LoaderVersionEntry {
+    pub loader: LoaderInfo,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LoaderInfo {
+    pub version: String,
+    #[serde(default)]
+    pub stable: bool,
+}
+
+
+// Fetches loader versions available for minecraft version, newest first
+// Same response format is used by Quilt meta
+pub fn fetch_loader_versions_from(meta_url: &str, game_version: &str) -> Result<Vec<LoaderInfo>, Box<dyn std::error::Error>> {
+    let url = format!("{}/versions/loader/{}", meta_url, game_version);
+
+    let response = reqwest::blocking::get(&url)?.error_for_status()?;
+    let entries: Vec<LoaderVersionEntry> = response.json()?;
+
+    Ok(entries.into_iter().map(|e| e.loader).collect())
+}
+
+pub fn fetch_loader_versions(game_version: &str) -> Result<Vec<LoaderInfo>, Box<dyn std::error::Error>> {
+    fetch_loader_versions_from(FABRIC_META_URL, game_version)
+}
+
+// Downloads loader profile JSON and its libraries into instance
+// Profile inherits from vanilla version, so vanilla has to be installed too
+// Returns version id of installed profile
+pub fn install_profile(profile_url: &str, instance_path: &PathBuf) -> Result<String, Box<dyn std::error::Error>> {
+    let response = reqwest::blocking::get(profile_url)?.error_for_status()?;
+    let profile_json = response.text()?;
+    let profile: LoaderProfile = serde_json::from_str(&profile_json)?;
+
+    // Save profile JSON next to vanilla versions
+    let mut version_dir = instance_path.clone();
+            version_dir.push("minecraft");
+            version_dir.push("versions");
+            version_dir.push(&profile.id);
+    fs::create_dir_all(&version_dir)?;
+
+    api::replace_file(&version_dir.join(format!("{}.json", profile.id)), profile_json.as_bytes())?;
+
+    println!("Found {} loader libraries to download", profile.libraries.len());
+    api::download_loader_libraries(&profile.libraries, instance_path)?;
+
+    Ok(profile.id)
+}
+
+pub fn install_fabric(game_version: &str, loader_version: &str, instance_path: &PathBuf) -> Result<String, Box<dyn std::error::Error>> {
+    println!("Installing Fabric loader {} for Minecraft {}...", loader_version, game_version);
+
+    let profile_url = format!("{}/versions/loader/{}/{}/profile/json", FABRIC_META_URL, game_version, loader_version);
+    let version_id = install_profile(&profile_url, instance_path)?;
+
+    println!("Fabric loader {} installed!", loader_version);
+    Ok(version_id)
+}
@@ -0,0 +1,413 @@
+// This is where Forge is installed.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// Forge ships an installer jar, everything is installed from its install_profile.json
+// Minecraft 1.13 and newer: version.json plus processors that patch the client
+// Minecraft 1.12.2 and older: versionInfo plus universal jar embedded in installer
+
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use zip::ZipArchive;
+
+use crate::minecraft::api::{self, Library, VersionDetails};
+use crate::minecraft::fabric::LoaderInfo;
+use crate::minecraft::game_launch;
+
+
+pub const FORGE_MAVEN_URL: &str = "https://maven.minecraftforge.net";
+const FORGE_PROMOTIONS_URL: &str = "https://files.minecraftforge.net/net/minecraftforge/forge/promotions_slim.json";
+
+// Default repository of libraries in legacy profiles without URL
+const MOJANG_LIBRARIES_URL: &str = "https://libraries.minecraft.net/";
+
+// install_profile.json of Minecraft 1.13 and newer
+#[derive(Debug, Deserialize)]
+pub struct InstallProfile {
+    // Path of version JSON inside installer, usually "/version.json"
+    pub json: String,
+    #[serde(default)]
+    pub data: HashMap<String, SidedData>,
+    #[serde(default)]
+    pub processors: Vec<Processor>,
+    #[serde(default)]
+    pub libraries: Vec<Library>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SidedData {
+    pub client: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Processor {
+    pub jar: String,
+    #[serde(default)]
+    pub classpath: Vec<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub outputs: HashMap<String, String>,
+    // Processors without sides run on both client and server
+    #[serde(default)]
+    pub sides: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Promotions {
+    promos: HashMap<String, String>,
+}
+
+
+// Extracts versions from maven-metadata.xml, in the order they are listed
+pub fn parse_maven_versions(metadata_xml: &str) -> Vec<String> {
+    metadata_xml
+        .split("<version>")
+        .skip(1)
+        .filter_map(|part| part.split_once("</version>"))
+        .map(|(version, _)| version.trim().to_string())
+        .collect()
+}
+
+// Fetches Forge versions for minecraft version, newest first
+// Recommended version is marked as stable
+pub fn fetch_loader_versions(game_version: &str) -> Result<Vec<LoaderInfo>, Box<dyn std::error::Error>> {
+    let metadata_url = format!("{}/net/minecraftforge/forge/maven-metadata.xml", FORGE_MAVEN_URL);
+    let metadata_xml = reqwest::blocking::get(&metadata_url)?.error_for_status()?.text()?;
+
+    // Maven versions look like "1.20.1-47.3.0"
+    let prefix = format!("{}-", game_version);
+    let mut versions: Vec<String> = parse_maven_versions(&metadata_xml)
+        .into_iter()
+        .filter_map(|v| v.strip_prefix(&prefix).map(|v| v.to_string()))
+        .collect();
+    versions.reverse();
+
+    // Promotions are only used to preselect version, so failing to get them is fine
+    let recommended = reqwest::blocking::get(FORGE_PROMOTIONS_URL)
+        .and_then(|r| r.json::<Promotions>())
+        .ok()
+        .and_then(|p| p.promos.get(&format!("{}-recommended", game_version)).cloned());
+
+    Ok(versions
+        .into_iter()
+        .map(|version| LoaderInfo {
+            stable: recommended.as_ref() == Some(&version),
+            version,
+        })
+        .collect())
+}
+
+// Main-Class from jar manifest, manifest lines longer than 72 bytes continue on lines starting with space
+fn jar_main_class(jar_path: &Path) -> Result<String, Box<dyn std::error::Error>> {
+    let mut zip = ZipArchive::new(File::open(jar_path)?)?;
+    let mut manifest = String::new();
+    zip.by_name("META-INF/MANIFEST.MF")?.read_to_string(&mut manifest)?;
+
+    let manifest = manifest.replace("\r\n", "\n").replace("\n ", "");
+    manifest
+        .lines()
+        .find_map(|line| line.strip_prefix("Main-Class:"))
+        .map(|main_class| main_class.trim().to_string())
+        .ok_or_else(|| format!("{} has no Main-Class", jar_path.display()).into())
+}
+
+fn zip_read_to_string(zip: &mut ZipArchive<File>, name: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let mut content = String::new();
+    zip.by_name(name.trim_start_matches('/'))?.read_to_string(&mut content)?;
+    Ok(content)
+}
+
+// Libraries may be hard links to official launcher's files, so they are replaced instead of overwritten
+fn zip_extract_file(zip: &mut ZipArchive<File>, name: &str, destination: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let mut file = zip.by_name(name.trim_start_matches('/'))?;
+    let mut content = Vec::new();
+    file.read_to_end(&mut content)?;
+    api::replace_file(destination, &content)?;
+
+    Ok(())
+}
+
+// Absolute path of library given as "[group:name:version:classifier@ext]"
+fn artifact_path(artifact: &str, libraries_dir: &Path) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let relative_path = api::library_path(artifact).ok_or_else(|| format!("Invalid artifact: {}", artifact))?;
+    Ok(libraries_dir.join(relative_path))
+}
+
+// Resolves data and argument values
+// "[artifact]" is library path, "'literal'" is literal, "{KEY}" is data entry, anything else is used as is
+fn resolve_value(value: &str, data: &HashMap<String, String>, libraries_dir: &Path) -> Result<String, Box<dyn std::error::Error>> {
+    if let Some(artifact) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+        return Ok(artifact_path(artifact, libraries_dir)?.to_string_lossy().to_string());
+    }
+
+    if let Some(literal) = value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+        return Ok(literal.to_string());
+    }
+
+    if let Some(key) = value.strip_prefix('{').and_then(|v| v.strip_suffix('}')) {
+        return data.get(key).cloned().ok_or_else(|| format!("Unknown installer data: {}", key).into());
+    }
+
+    // Data can also be embedded in arguments like "--input={MINECRAFT_JAR}"
+    let mut resolved = value.to_string();
+    for (key, data_value) in data {
+        resolved = resolved.replace(&format!("{{{}}}", key), data_value);
+    }
+    Ok(resolved)
+}
+
+// Checks if all processor outputs already exist with expected hashes
+fn outputs_valid(outputs: &[(PathBuf, String)]) -> bool {
+    !outputs.is_empty() && outputs.iter().all(|(path, sha1)| api::is_file_valid(path, sha1))
+}
+
+// Runs single install processor with Java
+fn run_processor(
+    processor: &Processor,
+    java_path: &Path,
+    data: &HashMap<String, String>,
+    libraries_dir: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Expected outputs with their hashes
+    let mut outputs = Vec::new();
+    for (path, sha1) in &processor.outputs {
+        let path = PathBuf::from(resolve_value(path, data, libraries_dir)?);
+        let sha1 = resolve_value(sha1, data, libraries_dir)?;
+        outputs.push((path, sha1));
+    }
+
+    if outputs_valid(&outputs) {
+        println!("Processor {} already done", processor.jar);
+        return Ok(());
+    }
+
+    // Processors write outputs in place, old outputs may be hard links to official launcher's files
+    // Removing them first only removes the links, processor then creates new files
+    for (path, _) in &outputs {
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+    }
+
+    let jar_path = artifact_path(&processor.jar, libraries_dir)?;
+    let main_class = jar_main_class(&jar_path)?;
+
+    let mut classpath = vec![jar_path.to_string_lossy().to_string()];
+    for artifact in &processor.classpath {
+        classpath.push(artifact_path(artifact, libraries_dir)?.to_string_lossy().to_string());
+    }
+
+    let mut args = Vec::new();
+    for arg in &processor.args {
+        args.push(resolve_value(arg, data, libraries_dir)?);
+    }
+
+    println!("Running processor {}...", processor.jar);
+    let status = Command::new(java_path)
+        .arg("-cp")
+        .arg(classpath.join(":"))
+        .arg(&main_class)
+        .args(&args)
+        .status()?;
+
+    if !status.success() {
+        return Err(format!("Processor {} failed: {}", processor.jar, status).into());
+    }
+
+    for (path, sha1) in &outputs {
+        if !api::is_file_valid(path, sha1) {
+            return Err(format!("Processor {} produced invalid output {}", processor.jar, path.display()).into());
+        }
+    }
+
+    Ok(())
+}
+
+// Installs Forge style installer with processors (Minecraft 1.13 and newer)
+fn install_modern(
+    zip: &mut ZipArchive<File>,
+    install_profile: InstallProfile,
+    installer_path: &Path,
+    game_version: &str,
+    instance_path: &PathBuf,
+    work_dir: &Path,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mc_instance_dir_path = instance_path.join("minecraft");
+    let libraries_dir = mc_instance_dir_path.join("libraries");
+
+    // Version JSON
+    let version_json = zip_read_to_string(zip, &install_profile.json)?;
+    let version_profile: api::LoaderProfile = serde_json::from_str(&version_json)?;
+
+    let version_dir = mc_instance_dir_path.join("versions").join(&version_profile.id);
+    fs::create_dir_all(&version_dir)?;
+    api::replace_file(&version_dir.join(format!("{}.json", version_profile.id)), version_json.as_bytes())?;
+
+    // Libraries embedded in installer
+    println!("Extracting embedded libraries...");
+    crate::instances::instance::zip_extract_dir(zip, "maven", &libraries_dir, &[])?;
+
+    // Libraries of game and of processors
+    println!("Found {} libraries to download", version_profile.libraries.len() + install_profile.libraries.len());
+    api::download_loader_libraries(&version_profile.libraries, instance_path)?;
+    api::download_loader_libraries(&install_profile.libraries, instance_path)?;
+
+    // Installer data
+    let client_jar_path = mc_instance_dir_path
+        .join("versions")
+        .join(game_version)
+        .join(format!("{}.jar", game_version));
+
+    let mut data: HashMap<String, String> = HashMap::new();
+    data.insert("SIDE".to_string(), "client".to_string());
+    data.insert("MINECRAFT_JAR".to_string(), client_jar_path.to_string_lossy().to_string());
+    data.insert("MINECRAFT_VERSION".to_string(), game_version.to_string());
+    data.insert("ROOT".to_string(), mc_instance_dir_path.to_string_lossy().to_string());
+    data.insert("INSTALLER".to_string(), installer_path.to_string_lossy().to_string());
+    data.insert("LIBRARY_DIR".to_string(), libraries_dir.to_string_lossy().to_string());
+
+    for (key, value) in &install_profile.data {
+        let value = &value.client;
+
+        // Files inside installer, like "/data/client.lzma"
+        let resolved = if value.starts_with('/') {
+            let extracted_path = work_dir.join(value.trim_start_matches('/'));
+            zip_extract_file(zip, value, &extracted_path)?;
+            extracted_path.to_string_lossy().to_string()
+        } else {
+            resolve_value(value, &data, &libraries_dir)?
+        };
+
+        data.insert(key.clone(), resolved);
+    }
+
+    // Processors need the Java version the game uses
+    let vanilla_json = fs::read_to_string(
+        mc_instance_dir_path.join("versions").join(game_version).join(format!("{}.json", game_version)),
+    )?;
+    let vanilla: VersionDetails = serde_json::from_str(&vanilla_json)?;
+    let java_path = game_launch::find_java(vanilla.java_version.map(|j| j.major_version));
+
+    let client_processors: Vec<&Processor> = install_profile.processors
+        .iter()
+        .filter(|p| p.sides.as_ref().is_none_or(|sides| sides.iter().any(|s| s == "client")))
+        .collect();
+
+    for (i, processor) in client_processors.iter().enumerate() {
+        println!("Processor {}/{}", i + 1, client_processors.len());
+        run_processor(processor, &java_path, &data, &libraries_dir)?;
+    }
+
+    Ok(version_profile.id)
+}
+
+// Installs old installer format with universal jar (Minecraft 1.12.2 and older)
+fn install_legacy(
+    zip: &mut ZipArchive<File>,
+    install_profile: serde_json::Value,
+    game_version: &str,
+    instance_path: &PathBuf,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mc_instance_dir_path = instance_path.join("minecraft");
+    let libraries_dir = mc_instance_dir_path.join("libraries");
+
+    let install = install_profile.get("install").ok_or("Installer has no install section")?;
+    let mut version_info = install_profile.get("versionInfo").cloned().ok_or("Installer has no versionInfo")?;
+
+    // Universal jar goes to libraries under Forge's maven name
+    let forge_artifact = install.get("path").and_then(|p| p.as_str()).ok_or("Installer has no Forge path")?;
+    let universal_jar = install.get("filePath").and_then(|p| p.as_str()).ok_or("Installer has no universal jar")?;
+
+    println!("Extracting {}...", universal_jar);
+    zip_extract_file(zip, universal_jar, &artifact_path(forge_artifact, &libraries_dir)?)?;
+
+    // Very old versions list all libraries instead of inheriting from vanilla
+    if version_info.get("inheritsFrom").is_none() {
+        version_info["inheritsFrom"] = serde_json::Value::String(game_version.to_string());
+    }
+
+    let version_id = version_info
+        .get("id")
+        .and_then(|id| id.as_str())
+        .ok_or("versionInfo has no id")?
+        .to_string();
+
+    // Libraries needed on client, ones without URL come from Mojang
+    let libraries: Vec<Library> = version_info
+        .get("libraries")
+        .and_then(|l| l.as_array())
+        .cloned()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|l| l.get("clientreq").and_then(|c| c.as_bool()).unwrap_or(false))
+        .filter_map(|l| serde_json::from_value::<Library>(l).ok())
+        .filter(|l| l.name != forge_artifact)
+        .map(|mut l| {
+            if l.url.is_none() {
+                l.url = Some(MOJANG_LIBRARIES_URL.to_string());
+            }
+            l
+        })
+        .collect();
+
+    println!("Found {} libraries to download", libraries.len());
+    api::download_loader_libraries(&libraries, instance_path)?;
+
+    let version_dir = mc_instance_dir_path.join("versions").join(&version_id);
+    fs::create_dir_all(&version_dir)?;
+    api::replace_file(&version_dir.join(format!("{}.json", version_id)), serde_json::to_string_pretty(&version_info)?.as_bytes())?;
+
+    Ok(version_id)
+}
+
+// Downloads installer jar and installs it into instance
+// Used for Forge and NeoForge, which share installer format
+// Returns version id of installed profile
+pub fn install_from_installer(installer_url: &str, game_version: &str, instance_path: &PathBuf) -> Result<String, Box<dyn std::error::Error>> {
+    let work_dir = instance_path.join("minecraft").join(".installer");
+    fs::create_dir_all(&work_dir)?;
+
+    let installer_path = work_dir.join("installer.jar");
+    println!("Downloading installer {}...", installer_url);
+    api::download_file(installer_url, &installer_path)?;
+
+    let mut zip = ZipArchive::new(File::open(&installer_path)?)?;
+    let install_profile: serde_json::Value = serde_json::from_str(&zip_read_to_string(&mut zip, "install_profile.json")?)?;
+
+    let result = if install_profile.get("versionInfo").is_some() {
+        install_legacy(&mut zip, install_profile, game_version, instance_path)
+    } else {
+        let install_profile: InstallProfile = serde_json::from_value(install_profile)?;
+        install_modern(&mut zip, install_profile, &installer_path, game_version, instance_path, &work_dir)
+    };
+
+    // Installer and extracted data are not needed anymore
+    if let Err(e) = fs::remove_dir_all(&work_dir) {
+        eprintln!("Failed to remove installer files: {}", e);
+    }
+
+    result
+}
+
+pub fn install_forge(game_version: &str, forge_version: &str, instance_path: &PathBuf) -> Result<String, Box<dyn std::error::Error>> {
+    println!("Installing Forge {} for Minecraft {}...", forge_version, game_version);
+
+    let maven_version = format!("{}-{}", game_version, forge_version);
+    let installer_url = format!(
+        "{}/net/minecraftforge/forge/{}/forge-{}-installer.jar",
+        FORGE_MAVEN_URL, maven_version, maven_version
+    );
+    let version_id = install_from_installer(&installer_url, game_version, instance_path)?;
+
+    println!("Forge {} installed!", forge_version);
+    Ok(version_id)
+}
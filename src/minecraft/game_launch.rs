@@ -1,18 +1,456 @@
-// // This is where the game is launched.
+// This is where the game is launched.
 
-// // Copper Launcher is licensed under GNU General Public License v3.0.
-// // Copyright (c) 2026 Suverent_Shiro
-// //
-// // See LICENSE file for more details.
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
 
-// use std::os::unix::process;
 
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::os::unix::process::CommandExt;
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use zip::ZipArchive;
 
-// fn check_java() {
-//     let java_dir = "/usr/lib/jvm/java-21-openjdk/bin/java";
-// }
+use crate::auth::offline;
+use crate::instances::instance::{self, InstanceMetadata};
+use crate::launcher::config;
+use crate::minecraft::api::{self, Argument, ArgumentValue, LoaderProfile, Library, LoggingConfig, VersionDetails};
+use crate::minecraft::game_process::{self, GameSession};
+use crate::minecraft::hooks::LaunchHooks;
 
-// fn launch_jvm() {
-//     let java_dir
-//     process
-// }
\ No newline at end of file
+
+// Account the game is launched with
+#[derive(Debug, Clone)]
+pub struct LaunchAccount {
+    pub username: String,
+    pub uuid: String,
+    pub access_token: String,
+    pub xuid: String,
+    pub user_type: String,
+    // Extra JVM arguments account needs, like authlib-injector agent
+    pub jvm_arguments: Vec<String>,
+}
+
+impl LaunchAccount {
+    pub fn offline(username: &str) -> Self {
+        LaunchAccount {
+            username: username.to_string(),
+            uuid: offline::offline_uuid(username),
+            access_token: "0".to_string(),
+            xuid: "0".to_string(),
+            user_type: "legacy".to_string(),
+            jvm_arguments: Vec::new(),
+        }
+    }
+}
+
+// Version JSON merged with vanilla version it inherits from
+pub struct LaunchProfile {
+    pub id: String,
+    pub minecraft_version: String,
+    pub version_type: String,
+    pub main_class: String,
+    pub libraries: Vec<Library>,
+    pub game_arguments: Vec<Argument>,
+    pub jvm_arguments: Vec<Argument>,
+    pub asset_index: String,
+    pub java_version: Option<u32>,
+    pub logging: Option<LoggingConfig>,
+}
+
+
+fn read_version_json(mc_instance_dir_path: &Path, version_id: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let mut version_json_path = mc_instance_dir_path.to_path_buf();
+            version_json_path.push("versions");
+            version_json_path.push(version_id);
+            version_json_path.push(format!("{}.json", version_id));
+
+    Ok(fs::read_to_string(&version_json_path)?)
+}
+
+// Legacy versions only have single argument string
+fn split_legacy_arguments(minecraft_arguments: &str) -> Vec<Argument> {
+    minecraft_arguments
+        .split_whitespace()
+        .map(|a| Argument::Plain(a.to_string()))
+        .collect()
+}
+
+// Library key without version, used to let mod loader libraries replace vanilla ones
+// "org.ow2.asm:asm:9.6" -> "org.ow2.asm:asm", classifier is kept
+fn library_key(library_name: &str) -> String {
+    let parts: Vec<&str> = library_name.split(':').collect();
+    match parts.len() {
+        0..=2 => library_name.to_string(),
+        3 => format!("{}:{}", parts[0], parts[1]),
+        _ => format!("{}:{}:{}", parts[0], parts[1], parts[3..].join(":")),
+    }
+}
+
+// Version JSONs saved by older launcher versions were cut down to VersionDetails fields and lost logging config
+// Mojang's JSON always has releaseTime, VersionDetails never had it, so cut down JSON is easy to tell apart
+fn is_cut_down(version_value: &serde_json::Value) -> bool {
+    version_value.get("releaseTime").is_none()
+}
+
+// Reads cut down version JSON from Mojang again and saves it as is, so this happens only once
+// Versions without logging config (before 1.7) are saved too and are not fetched again
+fn restore_version_json(mc_instance_dir_path: &Path, version_id: &str) -> Option<LoggingConfig> {
+    let version_json = match api::fetch_version_json(version_id) {
+        Ok(version_json) => version_json,
+        Err(e) => {
+            eprintln!("Failed to fetch logging config of {}: {}", version_id, e);
+            return None;
+        }
+    };
+
+    let version_json_path = mc_instance_dir_path.join("versions").join(version_id).join(format!("{}.json", version_id));
+    if let Err(e) = api::replace_file(&version_json_path, version_json.as_bytes()) {
+        eprintln!("Failed to update version JSON of {}: {}", version_id, e);
+    }
+
+    serde_json::from_str::<VersionDetails>(&version_json).ok()?.logging?.client
+}
+
+// Reads version JSON from instance, resolving inheritsFrom
+pub fn resolve_launch_profile(mc_instance_dir_path: &Path, version_id: &str) -> Result<LaunchProfile, Box<dyn std::error::Error>> {
+    let version_json = read_version_json(mc_instance_dir_path, version_id)?;
+    let version_value: serde_json::Value = serde_json::from_str(&version_json)?;
+
+    // Vanilla version
+    if version_value.get("inheritsFrom").is_none() {
+        let cut_down = is_cut_down(&version_value);
+        let details: VersionDetails = serde_json::from_value(version_value)?;
+
+        let (game_arguments, jvm_arguments) = match (details.arguments, &details.minecraft_arguments) {
+            (Some(arguments), _) => (arguments.game, arguments.jvm),
+            (None, Some(minecraft_arguments)) => (split_legacy_arguments(minecraft_arguments), Vec::new()),
+            (None, None) => (Vec::new(), Vec::new()),
+        };
+
+        return Ok(LaunchProfile {
+            id: details.id.clone(),
+            minecraft_version: details.id,
+            version_type: details.version_type,
+            main_class: details.main_class,
+            libraries: details.libraries,
+            game_arguments,
+            jvm_arguments,
+            asset_index: details.asset_index.id,
+            java_version: details.java_version.map(|j| j.major_version),
+            logging: match details.logging.and_then(|l| l.client) {
+                None if cut_down => restore_version_json(mc_instance_dir_path, version_id),
+                logging => logging,
+            },
+        });
+    }
+
+    // Mod loader profile on top of vanilla version
+    let loader_profile: LoaderProfile = serde_json::from_value(version_value)?;
+    let parent = resolve_launch_profile(mc_instance_dir_path, &loader_profile.inherits_from)?;
+
+    // Loader libraries go first and replace vanilla libraries with same name
+    let loader_keys: Vec<String> = loader_profile.libraries.iter().map(|l| library_key(&l.name)).collect();
+    let mut libraries = loader_profile.libraries;
+    libraries.extend(parent.libraries.into_iter().filter(|l| !loader_keys.contains(&library_key(&l.name))));
+
+    // Legacy loaders replace whole argument string, modern ones add to vanilla arguments
+    let mut game_arguments = match &loader_profile.minecraft_arguments {
+        Some(minecraft_arguments) => split_legacy_arguments(minecraft_arguments),
+        None => parent.game_arguments,
+    };
+    let mut jvm_arguments = parent.jvm_arguments;
+
+    if let Some(arguments) = loader_profile.arguments {
+        game_arguments.extend(arguments.game);
+        jvm_arguments.extend(arguments.jvm);
+    }
+
+    Ok(LaunchProfile {
+        id: loader_profile.id,
+        minecraft_version: parent.minecraft_version,
+        version_type: parent.version_type,
+        main_class: loader_profile.main_class,
+        libraries,
+        game_arguments,
+        jvm_arguments,
+        asset_index: parent.asset_index,
+        java_version: parent.java_version,
+        logging: parent.logging,
+    })
+}
+
+// Finds Java executable, preferring version required by the game
+pub fn find_java(major_version: Option<u32>) -> PathBuf {
+    if let Some(major_version) = major_version
+        && let Ok(entries) = fs::read_dir("/usr/lib/jvm")
+    {
+        let prefix = format!("java-{}-", major_version);
+        for entry in entries.flatten() {
+            let java_path = entry.path().join("bin").join("java");
+            if entry.file_name().to_string_lossy().starts_with(&prefix) && java_path.exists() {
+                return java_path;
+            }
+        }
+    }
+
+    if let Ok(java_home) = std::env::var("JAVA_HOME") {
+        let java_path = PathBuf::from(java_home).join("bin").join("java");
+        if java_path.exists() {
+            return java_path;
+        }
+    }
+
+    PathBuf::from("java")
+}
+
+// Extracts natives of versions before 1.19
+fn extract_natives(libraries: &[Library], libraries_dir: &Path, natives_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    fs::create_dir_all(natives_dir)?;
+
+    for library in libraries {
+        if !library.is_allowed() {
+            continue;
+        }
+
+        let Some(classifier) = library.native_classifier() else { continue };
+        let Some(relative_path) = api::library_path(&format!("{}:{}", library.name, classifier)) else { continue };
+
+        let native_path = libraries_dir.join(relative_path);
+        if !native_path.exists() {
+            continue;
+        }
+
+        let mut zip = ZipArchive::new(File::open(&native_path)?)?;
+        for i in 0..zip.len() {
+            let mut file = zip.by_index(i)?;
+            let Some(file_path) = file.enclosed_name() else { continue };
+
+            if file.is_dir() || file_path.starts_with("META-INF") {
+                continue;
+            }
+
+            let mut content = Vec::new();
+            file.read_to_end(&mut content)?;
+            api::replace_file(&natives_dir.join(file_path), &content)?;
+        }
+    }
+
+    Ok(())
+}
+
+// Replaces ${placeholders} in argument
+fn substitute(argument: &str, values: &HashMap<&str, String>) -> String {
+    let mut result = argument.to_string();
+    for (key, value) in values {
+        result = result.replace(&format!("${{{}}}", key), value);
+    }
+    result
+}
+
+// Expands arguments whose rules allow them
+fn expand_arguments(arguments: &[Argument], features: &HashMap<String, bool>, values: &HashMap<&str, String>) -> Vec<String> {
+    let mut expanded = Vec::new();
+
+    for argument in arguments {
+        match argument {
+            Argument::Plain(value) => expanded.push(substitute(value, values)),
+            Argument::Conditional { rules, value } => {
+                if !api::rules_allow(rules, features) {
+                    continue;
+                }
+                match value {
+                    ArgumentValue::Single(value) => expanded.push(substitute(value, values)),
+                    ArgumentValue::Multiple(values_list) => {
+                        expanded.extend(values_list.iter().map(|v| substitute(v, values)));
+                    }
+                }
+            }
+        }
+    }
+
+    expanded
+}
+
+// Downloads log4j config of version into assets/log_configs, it makes game print XML log events
+// Game still launches without it, log is just plain text then
+fn logging_argument(logging: &LoggingConfig, assets_dir: &Path) -> Option<String> {
+    if logging.config_type != "log4j2-xml" {
+        return None;
+    }
+
+    let config_path = assets_dir.join("log_configs").join(&logging.file.id);
+    if !api::is_file_valid(&config_path, &logging.file.sha1)
+        && let Err(e) = api::download_file_verified(&logging.file.url, &config_path, Some(&logging.file.sha1), None)
+    {
+        eprintln!("Failed to download logging config: {}", e);
+        return None;
+    }
+
+    Some(logging.argument.replace("${path}", &config_path.to_string_lossy()))
+}
+
+// Replaces values of game arguments that are already there, adds the rest
+// "--width 1280" changes the width, "--demo" is added when missing
+fn apply_game_overrides(arguments: &mut Vec<String>, overrides: &[String]) {
+    let mut i = 0;
+    while i < overrides.len() {
+        let name = &overrides[i];
+        let value = overrides.get(i + 1).filter(|v| !v.starts_with("--"));
+        i += if value.is_some() { 2 } else { 1 };
+
+        let position = arguments.iter().position(|a| a == name);
+        match (position, value) {
+            (Some(position), Some(value)) if arguments.get(position + 1).is_some_and(|v| !v.starts_with("--")) => {
+                arguments[position + 1] = value.clone();
+            }
+            (Some(_), None) => {}
+            _ => {
+                arguments.push(name.clone());
+                arguments.extend(value.cloned());
+            }
+        }
+    }
+}
+
+// Builds the whole java command for instance, with hooks that go around it
+pub fn build_launch_command(metadata: &InstanceMetadata, account: &LaunchAccount) -> Result<(Command, LaunchHooks), Box<dyn std::error::Error>> {
+    let instance_dir_path = instance::instance_path(&metadata.name);
+    let mc_instance_dir_path = instance_dir_path.join("minecraft");
+    let libraries_dir = mc_instance_dir_path.join("libraries");
+    let assets_dir = mc_instance_dir_path.join("assets");
+
+    let profile = resolve_launch_profile(&mc_instance_dir_path, metadata.launch_version_id())?;
+    let settings = metadata.settings.with_defaults(&config::load_config().instance_defaults);
+
+    // Natives
+    let mut natives_dir = mc_instance_dir_path.clone();
+            natives_dir.push("versions");
+            natives_dir.push(&profile.minecraft_version);
+            natives_dir.push("natives");
+    extract_natives(&profile.libraries, &libraries_dir, &natives_dir)?;
+
+    // Classpath, client JAR goes last
+    let mut classpath: Vec<String> = Vec::new();
+    for library in &profile.libraries {
+        if !library.is_allowed() || (library.natives.is_some() && library.downloads.artifact.is_none()) {
+            continue;
+        }
+
+        let Some(relative_path) = api::library_path(&library.name) else { continue };
+        let lib_path = libraries_dir.join(relative_path).to_string_lossy().to_string();
+        if !classpath.contains(&lib_path) {
+            classpath.push(lib_path);
+        }
+    }
+
+    let mut client_jar_path = mc_instance_dir_path.clone();
+            client_jar_path.push("versions");
+            client_jar_path.push(&profile.minecraft_version);
+            client_jar_path.push(format!("{}.jar", profile.minecraft_version));
+    classpath.push(client_jar_path.to_string_lossy().to_string());
+
+    // Placeholder values
+    let mut values: HashMap<&str, String> = HashMap::new();
+    values.insert("auth_player_name", account.username.clone());
+    values.insert("auth_uuid", account.uuid.clone());
+    values.insert("auth_access_token", account.access_token.clone());
+    values.insert("auth_session", account.access_token.clone());
+    values.insert("auth_xuid", account.xuid.clone());
+    values.insert("clientid", String::new());
+    values.insert("user_type", account.user_type.clone());
+    values.insert("user_properties", "{}".to_string());
+    values.insert("version_name", profile.id.clone());
+    values.insert("version_type", profile.version_type.clone());
+    values.insert("game_directory", mc_instance_dir_path.to_string_lossy().to_string());
+    values.insert("assets_root", assets_dir.to_string_lossy().to_string());
+    values.insert("game_assets", assets_dir.join("virtual").join("legacy").to_string_lossy().to_string());
+    values.insert("assets_index_name", profile.asset_index.clone());
+    values.insert("natives_directory", natives_dir.to_string_lossy().to_string());
+    values.insert("library_directory", libraries_dir.to_string_lossy().to_string());
+    values.insert("classpath_separator", ":".to_string());
+    values.insert("classpath", classpath.join(":"));
+    values.insert("launcher_name", "copper-launcher".to_string());
+    values.insert("launcher_version", env!("CARGO_PKG_VERSION").to_string());
+
+    // Window size
+    let mut features: HashMap<String, bool> = HashMap::new();
+    let custom_resolution = settings.window_width.zip(settings.window_height);
+    if let Some((width, height)) = custom_resolution {
+        features.insert("has_custom_resolution".to_string(), true);
+        values.insert("resolution_width", width.to_string());
+        values.insert("resolution_height", height.to_string());
+    }
+
+    // JVM arguments, versions before 1.13 do not list them
+    let mut jvm_arguments = Vec::new();
+    if let Some(min_memory) = settings.min_memory {
+        jvm_arguments.push(format!("-Xms{}M", min_memory));
+    }
+    if let Some(max_memory) = settings.max_memory {
+        jvm_arguments.push(format!("-Xmx{}M", max_memory));
+    }
+    if let Some(gc_preset) = settings.gc_preset {
+        jvm_arguments.extend(gc_preset.jvm_args().iter().map(|a| a.to_string()));
+    }
+    jvm_arguments.extend(settings.jvm_args.iter().cloned());
+    jvm_arguments.extend(account.jvm_arguments.iter().cloned());
+    if let Some(logging_argument) = profile.logging.as_ref().and_then(|l| logging_argument(l, &assets_dir)) {
+        jvm_arguments.push(logging_argument);
+    }
+
+    if profile.jvm_arguments.is_empty() {
+        jvm_arguments.push(substitute("-Djava.library.path=${natives_directory}", &values));
+        jvm_arguments.push("-cp".to_string());
+        jvm_arguments.push(classpath.join(":"));
+    } else {
+        jvm_arguments.extend(expand_arguments(&profile.jvm_arguments, &features, &values));
+    }
+
+    let mut game_arguments = expand_arguments(&profile.game_arguments, &features, &values);
+
+    // Legacy versions have no resolution arguments
+    if let Some((width, height)) = custom_resolution
+        && !game_arguments.iter().any(|a| a == "--width")
+    {
+        game_arguments.extend(["--width".to_string(), width.to_string(), "--height".to_string(), height.to_string()]);
+    }
+    if settings.fullscreen == Some(true) && !game_arguments.iter().any(|a| a == "--fullscreen") {
+        game_arguments.push("--fullscreen".to_string());
+    }
+    apply_game_overrides(&mut game_arguments, &settings.game_args);
+
+    let java_path = settings.java_path.clone().unwrap_or_else(|| find_java(profile.java_version));
+    let hooks = LaunchHooks::new(&metadata.name, &java_path, &settings);
+
+    let mut command = hooks.game_command(&java_path);
+    command
+        .current_dir(&mc_instance_dir_path)
+        .args(&jvm_arguments)
+        .arg(&profile.main_class)
+        .args(&game_arguments);
+
+    Ok((command, hooks))
+}
+
+// Launches instance and returns supervised game session, pre-launch command can stop it
+pub fn launch_instance(instance_name: &str, account: &LaunchAccount) -> Result<Arc<GameSession>, Box<dyn std::error::Error>> {
+    let metadata = instance::instance_metadata(instance_name)?;
+
+    println!("Launching {} ({})...", instance_name, metadata.launch_version_id());
+    let (mut command, hooks) = build_launch_command(&metadata, account)?;
+    hooks.pre_launch()?;
+
+    // Own process group, so game can be killed with everything it started
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .process_group(0);
+
+    Ok(game_process::supervise(instance_name, command.spawn()?, hooks))
+}
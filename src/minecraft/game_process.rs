@@ -0,0 +1,384 @@
+// This is where running game is supervised.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// Game runs in its own process group, so Kill also stops wrapper commands and everything game started
+// stdout and stderr are read into log buffer that log console reads from
+// log4j XML events on stdout become lines with logger and thread, see log4j.rs
+// Last session of every instance is kept, UI asks it for process state
+// Crash check runs before state changes, so UI sees crash report together with exit
+
+
+use gtk::{glib};
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Read};
+use std::os::unix::process::ExitStatusExt;
+use std::process::{Child, ExitStatus};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::minecraft::crash::{self, CrashReport};
+use crate::minecraft::hooks::LaunchHooks;
+use crate::minecraft::log4j::{self, LogRecord};
+
+
+// Oldest lines are dropped after this, so long sessions don't eat memory
+const MAX_LOG_LINES: usize = 100_000;
+
+// How often waiter thread checks if game is still running
+const WAIT_INTERVAL: Duration = Duration::from_millis(200);
+
+// How long output is still read after game exited
+// Processes started by game or wrapper can keep output open, game must not stay "Running" because of them
+const OUTPUT_DRAIN_TIMEOUT: Duration = Duration::from_secs(2);
+
+// Sessions of running and exited games
+static SESSIONS: Mutex<Vec<Arc<GameSession>>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Fatal,
+}
+
+impl LogLevel {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_uppercase().as_str() {
+            "TRACE" => Some(LogLevel::Trace),
+            "DEBUG" => Some(LogLevel::Debug),
+            "INFO" => Some(LogLevel::Info),
+            "WARN" | "WARNING" => Some(LogLevel::Warn),
+            "ERROR" | "SEVERE" => Some(LogLevel::Error),
+            "FATAL" => Some(LogLevel::Fatal),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LogLevel::Trace => "TRACE",
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+            LogLevel::Fatal => "FATAL",
+        }
+    }
+}
+
+// Untagged stdout lines are info, untagged stderr lines are errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogStream {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    pub level: LogLevel,
+    pub text: String,
+    // Milliseconds since Unix epoch, plain lines get time they were read
+    pub timestamp: u64,
+    // Only log4j events have these
+    pub logger: Option<String>,
+    pub thread: Option<String>,
+}
+
+impl LogLine {
+    fn plain(level: LogLevel, text: String) -> Self {
+        LogLine {
+            level,
+            text,
+            timestamp: now_millis(),
+            logger: None,
+            thread: None,
+        }
+    }
+
+    // Shown like vanilla console does it, with short logger name
+    fn from_record(record: LogRecord) -> Self {
+        let time = glib::DateTime::from_unix_local((record.timestamp / 1000) as i64)
+            .and_then(|t| t.format("%H:%M:%S"))
+            .map(|t| t.to_string())
+            .unwrap_or_default();
+        let logger_name = record.logger.rsplit('.').next().unwrap_or_default();
+
+        LogLine {
+            level: record.level,
+            text: format!("[{}] [{}/{}] [{}]: {}", time, record.thread, record.level.label(), logger_name, record.message.trim_end()),
+            timestamp: record.timestamp,
+            logger: Some(record.logger),
+            thread: Some(record.thread),
+        }
+    }
+}
+
+pub fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessState {
+    Running,
+    Exited(i32),
+    // Kill button
+    Killed,
+    // Signal nobody in launcher sent, e.g. SIGSEGV or SIGABRT when JVM crashes
+    Signaled(i32),
+}
+
+impl ProcessState {
+    pub fn label(&self) -> String {
+        match self {
+            ProcessState::Running => "Running".to_string(),
+            ProcessState::Exited(code) => format!("Exited with code {}", code),
+            ProcessState::Killed => "Killed".to_string(),
+            ProcessState::Signaled(signal) => format!("Stopped by signal {}", signal),
+        }
+    }
+}
+
+// Lines are numbered from session start, numbers stay same when old lines are dropped
+#[derive(Default)]
+struct LogBuffer {
+    first: usize,
+    lines: VecDeque<LogLine>,
+}
+
+pub struct GameSession {
+    pub instance_name: String,
+    pid: u32,
+    child: Mutex<Child>,
+    state: Mutex<ProcessState>,
+    log: Mutex<LogBuffer>,
+    kill_requested: AtomicBool,
+    // Crash files older than this belong to earlier sessions
+    started: SystemTime,
+    crash: Mutex<Option<CrashReport>>,
+}
+
+impl GameSession {
+    pub fn state(&self) -> ProcessState {
+        *self.state.lock().unwrap()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.state() == ProcessState::Running
+    }
+
+    // Set after game crashed, killing game is not a crash
+    pub fn crash_report(&self) -> Option<CrashReport> {
+        self.crash.lock().unwrap().clone()
+    }
+
+    // Lines from line number on, with number of next line
+    pub fn lines_since(&self, start: usize) -> (Vec<LogLine>, usize) {
+        let log = self.log.lock().unwrap();
+        let skip = start.saturating_sub(log.first);
+
+        (log.lines.iter().skip(skip).cloned().collect(), log.first + log.lines.len())
+    }
+
+    // Whole log as text, for saving and uploading
+    pub fn log_text(&self) -> String {
+        let log = self.log.lock().unwrap();
+        log.lines.iter().map(|l| format!("{}\n", l.text)).collect()
+    }
+
+    fn push_line(&self, line: LogLine) {
+        let mut log = self.log.lock().unwrap();
+        log.lines.push_back(line);
+        if log.lines.len() > MAX_LOG_LINES {
+            log.lines.pop_front();
+            log.first += 1;
+        }
+    }
+
+    // Kills whole process group of the game
+    pub fn kill(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if !self.is_running() {
+            return Ok(());
+        }
+
+        self.kill_requested.store(true, Ordering::SeqCst);
+        // Safe, only sends signal
+        if unsafe { libc::killpg(self.pid as libc::pid_t, libc::SIGKILL) } != 0 {
+            // Group is gone, game itself may still be there
+            self.child.lock().unwrap().kill()?;
+        }
+
+        Ok(())
+    }
+}
+
+
+// Level of game output line
+// Vanilla "[12:00:00] [Render thread/INFO]: ...", Forge "[12:00:00] [main/INFO] [mod/]: ..."
+fn bracket_level(text: &str) -> Option<LogLevel> {
+    if !text.starts_with('[') {
+        return None;
+    }
+
+    text.split(']').take(3).find_map(|part| {
+        let inside = part.rsplit('[').next()?;
+        LogLevel::parse(inside.rsplit(['/', ' ']).next()?)
+    })
+}
+
+// Stack traces continue level of line before them
+fn is_continuation(text: &str) -> bool {
+    text.starts_with(char::is_whitespace) || text.starts_with("Caused by:") || text.starts_with("at ")
+}
+
+fn read_output(session: Arc<GameSession>, output: impl Read, stream: LogStream) {
+    let mut reader = BufReader::new(output);
+    let mut previous = LogLevel::Info;
+    let mut buffer = Vec::new();
+    // Lines of log4j event that is not complete yet
+    let mut event = String::new();
+
+    loop {
+        buffer.clear();
+        match reader.read_until(b'\n', &mut buffer) {
+            Ok(0) => {
+                // Output ended in the middle of event
+                if !event.is_empty() {
+                    session.push_line(LogLine::plain(previous, event.trim_end().to_string()));
+                }
+                break;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("Failed to read game output: {}", e);
+                break;
+            }
+        }
+
+        let text = String::from_utf8_lossy(&buffer).trim_end_matches(['\r', '\n']).to_string();
+
+        if stream == LogStream::Stdout && (!event.is_empty() || log4j::is_event_start(&text)) {
+            event.push_str(&text);
+            event.push('\n');
+            if !log4j::is_event_end(&text) {
+                continue;
+            }
+
+            match log4j::parse_event(&event) {
+                Ok(record) => {
+                    previous = record.level;
+                    session.push_line(LogLine::from_record(record));
+                }
+                Err(e) => {
+                    eprintln!("Failed to parse log event: {}", e);
+                    session.push_line(LogLine::plain(previous, event.trim_end().to_string()));
+                }
+            }
+            event.clear();
+            continue;
+        }
+
+        let level = bracket_level(&text).unwrap_or(match stream {
+            _ if is_continuation(&text) => previous,
+            LogStream::Stdout => LogLevel::Info,
+            LogStream::Stderr => LogLevel::Error,
+        });
+        previous = level;
+
+        session.push_line(LogLine::plain(level, text));
+    }
+}
+
+// Game exited or was killed
+fn finish(session: &GameSession, status: ExitStatus) {
+    let state = match status.signal() {
+        _ if session.kill_requested.load(Ordering::SeqCst) => ProcessState::Killed,
+        Some(signal) => ProcessState::Signaled(signal),
+        None => ProcessState::Exited(status.code().unwrap_or(-1)),
+    };
+
+    println!("{} {}", session.instance_name, state.label().to_lowercase());
+
+    if !session.kill_requested.load(Ordering::SeqCst) {
+        let report = crash::detect_crash(&session.instance_name, state, session.started, &session.log_text());
+        if report.is_some() {
+            println!("{} crashed", session.instance_name);
+        }
+        *session.crash.lock().unwrap() = report;
+    }
+
+    *session.state.lock().unwrap() = state;
+}
+
+// Takes over spawned game, stdout and stderr have to be piped
+pub fn supervise(instance_name: &str, mut child: Child, hooks: LaunchHooks) -> Arc<GameSession> {
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+
+    let session = Arc::new(GameSession {
+        instance_name: instance_name.to_string(),
+        pid: child.id(),
+        child: Mutex::new(child),
+        state: Mutex::new(ProcessState::Running),
+        log: Mutex::new(LogBuffer::default()),
+        kill_requested: AtomicBool::new(false),
+        started: SystemTime::now(),
+        crash: Mutex::new(None),
+    });
+
+    let mut readers = Vec::new();
+    if let Some(stdout) = stdout {
+        let session = session.clone();
+        readers.push(thread::spawn(move || read_output(session, stdout, LogStream::Stdout)));
+    }
+    if let Some(stderr) = stderr {
+        let session = session.clone();
+        readers.push(thread::spawn(move || read_output(session, stderr, LogStream::Stderr)));
+    }
+
+    // Child is only locked for a moment, so Kill can get to it
+    let waited = session.clone();
+    thread::spawn(move || {
+        let status = loop {
+            match waited.child.lock().unwrap().try_wait() {
+                Ok(Some(status)) => break Some(status),
+                Ok(None) => {}
+                Err(e) => {
+                    eprintln!("Failed to wait for game: {}", e);
+                    break None;
+                }
+            }
+            thread::sleep(WAIT_INTERVAL);
+        };
+
+        // Rest of output is read before game counts as exited, readers left after timeout keep going on their own
+        let deadline = Instant::now() + OUTPUT_DRAIN_TIMEOUT;
+        while readers.iter().any(|reader| !reader.is_finished()) && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(50));
+        }
+
+        // Unknown status is reported as exit code 255
+        let status = status.unwrap_or_else(|| ExitStatus::from_raw(255 << 8));
+        finish(&waited, status);
+        hooks.post_exit(status);
+    });
+
+    let mut sessions = SESSIONS.lock().unwrap();
+    sessions.retain(|s| s.instance_name != instance_name);
+    sessions.push(session.clone());
+
+    session
+}
+
+// Last session of instance
+pub fn session(instance_name: &str) -> Option<Arc<GameSession>> {
+    SESSIONS.lock().unwrap().iter().find(|s| s.instance_name == instance_name).cloned()
+}
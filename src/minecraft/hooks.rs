@@ -0,0 +1,108 @@
+// This is where launch hooks are run.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// Hooks are shell commands from instance settings (or launcher defaults)
+// Pre-launch command runs before the game, launch is aborted when it fails
+// Wrapper command goes in front of java, like "gamemoderun" or "prime-run"
+// Post-exit command runs after the game, exit code is in INST_EXIT_CODE and $1
+// Every hook gets INST_NAME, INST_DIR, INST_MC_DIR, INST_JAVA and custom variables from settings
+
+
+use std::collections::BTreeMap;
+use std::os::unix::process::ExitStatusExt;
+use std::path::Path;
+use std::process::{Command, ExitStatus};
+
+use crate::instances::instance::{self, InstanceSettings};
+
+
+pub struct LaunchHooks {
+    env: BTreeMap<String, String>,
+    pre_launch_command: Option<String>,
+    wrapper_command: Option<String>,
+    post_exit_command: Option<String>,
+}
+
+impl LaunchHooks {
+    // Settings have to be merged with launcher defaults already
+    pub fn new(instance_name: &str, java_path: &Path, settings: &InstanceSettings) -> Self {
+        let instance_dir_path = instance::instance_path(instance_name);
+
+        let mut env = BTreeMap::from([
+            ("INST_NAME".to_string(), instance_name.to_string()),
+            ("INST_DIR".to_string(), instance_dir_path.to_string_lossy().to_string()),
+            ("INST_MC_DIR".to_string(), instance_dir_path.join("minecraft").to_string_lossy().to_string()),
+            ("INST_JAVA".to_string(), java_path.to_string_lossy().to_string()),
+        ]);
+        env.extend(settings.env.iter().map(|(k, v)| (k.clone(), v.clone())));
+
+        // Empty command is same as none
+        let command = |c: &Option<String>| c.as_ref().map(|c| c.trim().to_string()).filter(|c| !c.is_empty());
+
+        LaunchHooks {
+            env,
+            pre_launch_command: command(&settings.pre_launch_command),
+            wrapper_command: command(&settings.wrapper_command),
+            post_exit_command: command(&settings.post_exit_command),
+        }
+    }
+
+    fn shell(&self, command: &str) -> Command {
+        let mut shell = Command::new("sh");
+        shell.arg("-c").arg(command).envs(&self.env);
+        shell
+    }
+
+    // Command for java, wrapper gets java and its arguments as "$@"
+    pub fn game_command(&self, java_path: &Path) -> Command {
+        match &self.wrapper_command {
+            Some(wrapper_command) => {
+                let mut command = self.shell(&format!("{} \"$@\"", wrapper_command));
+                command.arg("sh").arg(java_path);
+                command
+            }
+            None => {
+                let mut command = Command::new(java_path);
+                command.envs(&self.env);
+                command
+            }
+        }
+    }
+
+    // Err aborts the launch
+    pub fn pre_launch(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(pre_launch_command) = &self.pre_launch_command else { return Ok(()) };
+
+        println!("Running pre-launch command: {}", pre_launch_command);
+        let status = self.shell(pre_launch_command).status()?;
+        if !status.success() {
+            return Err(format!("Pre-launch command failed ({}), launch aborted", status).into());
+        }
+
+        Ok(())
+    }
+
+    pub fn post_exit(&self, status: ExitStatus) {
+        let Some(post_exit_command) = &self.post_exit_command else { return };
+
+        // Killed by signal is reported like shell does it
+        let exit_code = status.code().or_else(|| status.signal().map(|s| 128 + s)).unwrap_or(-1).to_string();
+
+        println!("Running post-exit command: {}", post_exit_command);
+        let result = self.shell(post_exit_command)
+            .arg("sh")
+            .arg(&exit_code)
+            .env("INST_EXIT_CODE", &exit_code)
+            .status();
+
+        match result {
+            Ok(status) if !status.success() => eprintln!("Post-exit command failed ({})", status),
+            Ok(_) => {}
+            Err(e) => eprintln!("Failed to run post-exit command: {}", e),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+// This is where log4j XML output of the game is parsed.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// Version JSON has logging.client config that makes game print log4j XMLLayout events to stdout:
+// <log4j:Event logger="..." timestamp="..." level="INFO" thread="Render thread">
+//   <log4j:Message><![CDATA[...]]></log4j:Message>
+//   <log4j:Throwable><![CDATA[...]]></log4j:Throwable>
+// </log4j:Event>
+
+
+use quick_xml::Reader;
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::Event;
+
+use crate::minecraft::game_process::LogLevel;
+
+
+const EVENT_TAG: &[u8] = b"log4j:Event";
+const MESSAGE_TAG: &[u8] = b"log4j:Message";
+const THROWABLE_TAG: &[u8] = b"log4j:Throwable";
+
+#[derive(Debug, Clone)]
+pub struct LogRecord {
+    pub logger: String,
+    pub level: LogLevel,
+    pub thread: String,
+    // Milliseconds since Unix epoch
+    pub timestamp: u64,
+    // Stack trace of throwable is added after message
+    pub message: String,
+}
+
+// Part of event that text goes to
+enum Section {
+    Message,
+    Throwable,
+}
+
+
+// Event starts on this line
+pub fn is_event_start(line: &str) -> bool {
+    line.trim_start().starts_with("<log4j:Event")
+}
+
+// Event ends on this line
+pub fn is_event_end(line: &str) -> bool {
+    line.contains("</log4j:Event>")
+}
+
+// Parses single <log4j:Event> element
+pub fn parse_event(xml: &str) -> Result<LogRecord, Box<dyn std::error::Error>> {
+    let mut reader = Reader::from_str(xml);
+
+    let mut record = LogRecord {
+        logger: String::new(),
+        level: LogLevel::Info,
+        thread: String::new(),
+        timestamp: 0,
+        message: String::new(),
+    };
+    let mut throwable = String::new();
+    let mut section: Option<Section> = None;
+    let mut found = false;
+
+    loop {
+        let text = match reader.read_event()? {
+            Event::Start(element) | Event::Empty(element) if element.name().as_ref() == EVENT_TAG => {
+                found = true;
+                for attribute in element.attributes() {
+                    let attribute = attribute?;
+                    let value = attribute.unescape_value()?.to_string();
+                    match attribute.key.as_ref() {
+                        b"logger" => record.logger = value,
+                        b"level" => record.level = LogLevel::parse(&value).unwrap_or(LogLevel::Info),
+                        b"thread" => record.thread = value,
+                        b"timestamp" => record.timestamp = value.parse().unwrap_or(0),
+                        _ => {}
+                    }
+                }
+                continue;
+            }
+            Event::Start(element) => {
+                section = match element.name().as_ref() {
+                    MESSAGE_TAG => Some(Section::Message),
+                    THROWABLE_TAG => Some(Section::Throwable),
+                    _ => None,
+                };
+                continue;
+            }
+            Event::End(_) => {
+                section = None;
+                continue;
+            }
+            Event::CData(data) => String::from_utf8_lossy(&data.into_inner()).to_string(),
+            Event::Text(text) => text.decode()?.to_string(),
+            Event::GeneralRef(reference) => match reference.resolve_char_ref()? {
+                Some(character) => character.to_string(),
+                None => resolve_predefined_entity(&reference.decode()?).unwrap_or_default().to_string(),
+            },
+            Event::Eof => break,
+            _ => continue,
+        };
+
+        match section {
+            Some(Section::Message) => record.message.push_str(&text),
+            Some(Section::Throwable) => throwable.push_str(&text),
+            None => {}
+        }
+    }
+
+    if !found {
+        return Err("Not a log4j event".into());
+    }
+
+    let throwable = throwable.trim_end();
+    if !throwable.is_empty() {
+        record.message = format!("{}\n{}", record.message.trim_end(), throwable);
+    }
+
+    Ok(record)
+}
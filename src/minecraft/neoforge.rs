@@ -0,0 +1,82 @@
+// This is where NeoForge is installed.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// NeoForge installer has same format as Forge installer, so Forge installer is reused
+// Versions are "<minor>.<patch>.<build>" of minecraft version, e.g. 21.1.77 for 1.21.1
+// Minecraft 1.20.1 is the exception, it uses net.neoforged:forge with Forge style versions
+
+
+use std::path::PathBuf;
+
+use crate::minecraft::fabric::LoaderInfo;
+use crate::minecraft::forge;
+
+
+pub const NEOFORGE_MAVEN_URL: &str = "https://maven.neoforged.net/releases";
+
+// Only version NeoForge supports with old Forge artifact
+const LEGACY_GAME_VERSION: &str = "1.20.1";
+
+
+// Prefix of NeoForge versions for minecraft version
+// "1.21.1" -> "21.1.", "1.21" -> "21.0.", versions without "1." like "26.1" -> "26.1."
+fn version_prefix(game_version: &str) -> String {
+    match game_version.strip_prefix("1.") {
+        Some(rest) => match rest.split_once('.') {
+            Some((minor, patch)) => format!("{}.{}.", minor, patch),
+            None => format!("{}.0.", rest),
+        },
+        None => format!("{}.", game_version),
+    }
+}
+
+// Fetches NeoForge versions for minecraft version, newest first
+// Beta versions are not stable
+pub fn fetch_loader_versions(game_version: &str) -> Result<Vec<LoaderInfo>, Box<dyn std::error::Error>> {
+    let (artifact, prefix) = if game_version == LEGACY_GAME_VERSION {
+        ("forge", format!("{}-", game_version))
+    } else {
+        ("neoforge", version_prefix(game_version))
+    };
+
+    let metadata_url = format!("{}/net/neoforged/{}/maven-metadata.xml", NEOFORGE_MAVEN_URL, artifact);
+    let metadata_xml = reqwest::blocking::get(&metadata_url)?.error_for_status()?.text()?;
+
+    let mut versions: Vec<LoaderInfo> = forge::parse_maven_versions(&metadata_xml)
+        .into_iter()
+        .filter(|v| v.starts_with(&prefix))
+        .map(|v| {
+            // Legacy versions keep only part after minecraft version, like Forge
+            let version = if artifact == "forge" { v[prefix.len()..].to_string() } else { v };
+            LoaderInfo {
+                stable: !version.contains("-beta"),
+                version,
+            }
+        })
+        .collect();
+    versions.reverse();
+
+    Ok(versions)
+}
+
+pub fn install_neoforge(game_version: &str, neoforge_version: &str, instance_path: &PathBuf) -> Result<String, Box<dyn std::error::Error>> {
+    println!("Installing NeoForge {} for Minecraft {}...", neoforge_version, game_version);
+
+    let installer_url = if game_version == LEGACY_GAME_VERSION {
+        let maven_version = format!("{}-{}", game_version, neoforge_version);
+        format!("{}/net/neoforged/forge/{}/forge-{}-installer.jar", NEOFORGE_MAVEN_URL, maven_version, maven_version)
+    } else {
+        format!(
+            "{}/net/neoforged/neoforge/{}/neoforge-{}-installer.jar",
+            NEOFORGE_MAVEN_URL, neoforge_version, neoforge_version
+        )
+    };
+    let version_id = forge::install_from_installer(&installer_url, game_version, instance_path)?;
+
+    println!("NeoForge {} installed!", neoforge_version);
+    Ok(version_id)
+}
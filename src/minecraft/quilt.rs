@@ -0,0 +1,38 @@
+// This is where Quilt loader is installed.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// Quilt meta uses same format as Fabric meta, so Fabric installer is reused
+// Quilt profile also contains hashed mappings library (org.quiltmc:hashed)
+
+
+use std::path::PathBuf;
+
+use crate::minecraft::fabric::{self, LoaderInfo};
+
+
+pub const QUILT_META_URL: &str = "https://meta.quiltmc.org/v3";
+
+// Quilt meta does not mark stable versions, pre-releases have suffix like "-beta.1"
+pub fn fetch_loader_versions(game_version: &str) -> Result<Vec<LoaderInfo>, Box<dyn std::error::Error>> {
+    let mut versions = fabric::fetch_loader_versions_from(QUILT_META_URL, game_version)?;
+
+    for version in &mut versions {
+        version.stable = !version.version.contains('-');
+    }
+
+    Ok(versions)
+}
+
+pub fn install_quilt(game_version: &str, loader_version: &str, instance_path: &PathBuf) -> Result<String, Box<dyn std::error::Error>> {
+    println!("Installing Quilt loader {} for Minecraft {}...", loader_version, game_version);
+
+    let profile_url = format!("{}/versions/loader/{}/{}/profile/json", QUILT_META_URL, game_version, loader_version);
+    let version_id = fabric::install_profile(&profile_url, instance_path)?;
+
+    println!("Quilt loader {} installed!", loader_version);
+    Ok(version_id)
+}
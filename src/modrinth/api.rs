@@ -0,0 +1,235 @@
+// This is where Modrinth API is handled.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// API documentation: https://docs.modrinth.com/api/
+
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::instances::instance::LoaderKind;
+
+
+pub const MODRINTH_API_URL: &str = "https://api.modrinth.com/v2";
+
+// Modrinth asks every client to identify itself
+const USER_AGENT: &str = concat!("Suverent-Shiro/Copper-Launcher/", env!("CARGO_PKG_VERSION"));
+
+// Results per search page
+const SEARCH_LIMIT: u32 = 20;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ModrinthVersion {
+    pub id: String,
+    pub project_id: String,
+    pub name: String,
+    pub version_number: String,
+    pub game_versions: Vec<String>,
+    pub loaders: Vec<String>,
+    pub files: Vec<ModrinthVersionFile>,
+    #[serde(default)]
+    pub dependencies: Vec<ModrinthDependency>,
+    // "release", "beta" or "alpha"
+    #[serde(default)]
+    pub version_type: String,
+    #[serde(default)]
+    pub date_published: String,
+}
+
+impl ModrinthVersion {
+    // File that should be downloaded, first file when none is marked as primary
+    pub fn primary_file(&self) -> Option<&ModrinthVersionFile> {
+        self.files.iter().find(|f| f.primary).or_else(|| self.files.first())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ModrinthVersionFile {
+    pub hashes: HashMap<String, String>,
+    pub url: String,
+    pub filename: String,
+    pub primary: bool,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ModrinthDependency {
+    pub version_id: Option<String>,
+    pub project_id: Option<String>,
+    pub file_name: Option<String>,
+    // "required", "optional", "incompatible" or "embedded"
+    pub dependency_type: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SearchResults {
+    pub hits: Vec<SearchHit>,
+    pub total_hits: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SearchHit {
+    pub project_id: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub author: String,
+    #[serde(default)]
+    pub downloads: u64,
+}
+
+// Search and version filters
+// Empty lists don't filter anything
+#[derive(Debug, Clone, Default)]
+pub struct VersionFilter {
+    pub game_versions: Vec<String>,
+    pub loaders: Vec<String>,
+}
+
+
+// Loader names used by Modrinth that work with loader
+// Quilt also loads Fabric mods
+pub fn modrinth_loader_names(kind: LoaderKind) -> &'static [&'static str] {
+    match kind {
+        LoaderKind::Fabric => &["fabric"],
+        LoaderKind::Quilt => &["quilt", "fabric"],
+        LoaderKind::Forge => &["forge"],
+        LoaderKind::NeoForge => &["neoforge"],
+    }
+}
+
+
+// Everything launcher needs from Modrinth
+// Implemented by ModrinthClient, tests can provide their own offline implementation
+pub trait ModrinthApi {
+    // Finds versions that contain files with given hashes
+    // Returned map is keyed by hash, unknown hashes are missing from it
+    fn versions_from_hashes(&self, hashes: &[String], algorithm: &str) -> Result<HashMap<String, ModrinthVersion>, Box<dyn std::error::Error>>;
+
+    // Finds newest versions matching filter of projects that contain files with given hashes
+    // Returned map is keyed by hash of current file
+    fn latest_versions_from_hashes(&self, hashes: &[String], algorithm: &str, filter: &VersionFilter) -> Result<HashMap<String, ModrinthVersion>, Box<dyn std::error::Error>>;
+
+    // Searches projects of project type matching filter
+    fn search(&self, query: &str, project_type: &str, filter: &VersionFilter, offset: u32) -> Result<SearchResults, Box<dyn std::error::Error>>;
+
+    // Versions of project matching filter, newest first
+    fn project_versions(&self, project_id: &str, filter: &VersionFilter) -> Result<Vec<ModrinthVersion>, Box<dyn std::error::Error>>;
+
+    fn version(&self, version_id: &str) -> Result<ModrinthVersion, Box<dyn std::error::Error>>;
+}
+
+pub struct ModrinthClient {
+    base_url: String,
+    client: reqwest::blocking::Client,
+}
+
+impl ModrinthClient {
+    pub fn new() -> Self {
+        Self::with_base_url(MODRINTH_API_URL)
+    }
+
+    pub fn with_base_url(base_url: &str) -> Self {
+        let client = reqwest::blocking::Client::builder()
+            .user_agent(USER_AGENT)
+            .build()
+            .expect("Failed to create HTTP client");
+
+        ModrinthClient {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client,
+        }
+    }
+}
+
+impl Default for ModrinthClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ModrinthApi for ModrinthClient {
+    fn versions_from_hashes(&self, hashes: &[String], algorithm: &str) -> Result<HashMap<String, ModrinthVersion>, Box<dyn std::error::Error>> {
+        if hashes.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let url = format!("{}/version_files", self.base_url);
+        let body = serde_json::json!({
+            "hashes": hashes,
+            "algorithm": algorithm,
+        });
+
+        let response = self.client.post(&url).json(&body).send()?.error_for_status()?;
+        let versions: HashMap<String, ModrinthVersion> = response.json()?;
+
+        Ok(versions)
+    }
+
+    fn latest_versions_from_hashes(&self, hashes: &[String], algorithm: &str, filter: &VersionFilter) -> Result<HashMap<String, ModrinthVersion>, Box<dyn std::error::Error>> {
+        if hashes.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let url = format!("{}/version_files/update", self.base_url);
+        let body = serde_json::json!({
+            "hashes": hashes,
+            "algorithm": algorithm,
+            "loaders": filter.loaders,
+            "game_versions": filter.game_versions,
+        });
+
+        let response = self.client.post(&url).json(&body).send()?.error_for_status()?;
+        let versions: HashMap<String, ModrinthVersion> = response.json()?;
+
+        Ok(versions)
+    }
+
+    fn search(&self, query: &str, project_type: &str, filter: &VersionFilter, offset: u32) -> Result<SearchResults, Box<dyn std::error::Error>> {
+        // Facets are list of OR groups that are joined with AND
+        let mut facets = vec![vec![format!("project_type:{}", project_type)]];
+        if !filter.game_versions.is_empty() {
+            facets.push(filter.game_versions.iter().map(|v| format!("versions:{}", v)).collect());
+        }
+        if !filter.loaders.is_empty() {
+            facets.push(filter.loaders.iter().map(|l| format!("categories:{}", l)).collect());
+        }
+
+        let url = reqwest::Url::parse_with_params(&format!("{}/search", self.base_url), &[
+            ("query", query.to_string()),
+            ("facets", serde_json::to_string(&facets)?),
+            ("offset", offset.to_string()),
+            ("limit", SEARCH_LIMIT.to_string()),
+        ])?;
+
+        let response = self.client.get(url).send()?.error_for_status()?;
+        Ok(response.json()?)
+    }
+
+    fn project_versions(&self, project_id: &str, filter: &VersionFilter) -> Result<Vec<ModrinthVersion>, Box<dyn std::error::Error>> {
+        let mut params = Vec::new();
+        if !filter.game_versions.is_empty() {
+            params.push(("game_versions", serde_json::to_string(&filter.game_versions)?));
+        }
+        if !filter.loaders.is_empty() {
+            params.push(("loaders", serde_json::to_string(&filter.loaders)?));
+        }
+
+        let url = reqwest::Url::parse_with_params(&format!("{}/project/{}/version", self.base_url, project_id), &params)?;
+
+        let response = self.client.get(url).send()?.error_for_status()?;
+        Ok(response.json()?)
+    }
+
+    fn version(&self, version_id: &str) -> Result<ModrinthVersion, Box<dyn std::error::Error>> {
+        let url = format!("{}/version/{}", self.base_url, version_id);
+
+        let response = self.client.get(&url).send()?.error_for_status()?;
+        Ok(response.json()?)
+    }
+}
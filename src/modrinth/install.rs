@@ -0,0 +1,213 @@
+// This is where Modrinth projects are installed into instances.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// Newest release matching instance is installed, beta and alpha only when there is no release
+// Required dependencies of mods are installed too, projects already in instance are skipped
+
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::instances::instance::{self, InstanceMetadata};
+use crate::minecraft::api;
+use crate::modrinth::api::{ModrinthApi, ModrinthVersion, VersionFilter, modrinth_loader_names};
+use crate::mods::manager;
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectType {
+    Mod,
+    ResourcePack,
+    Shader,
+    Datapack,
+}
+
+impl ProjectType {
+    pub const ALL: [ProjectType; 4] = [ProjectType::Mod, ProjectType::ResourcePack, ProjectType::Shader, ProjectType::Datapack];
+
+    // Project type as used by Modrinth API
+    pub fn api_name(self) -> &'static str {
+        match self {
+            ProjectType::Mod => "mod",
+            ProjectType::ResourcePack => "resourcepack",
+            ProjectType::Shader => "shader",
+            ProjectType::Datapack => "datapack",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ProjectType::Mod => "Mods",
+            ProjectType::ResourcePack => "Resource packs",
+            ProjectType::Shader => "Shader packs",
+            ProjectType::Datapack => "Data packs",
+        }
+    }
+}
+
+
+// Versions that work in instance
+// Shader packs are not filtered by loader, their loaders are shader mods like Iris or OptiFine
+pub fn instance_filter(metadata: &InstanceMetadata, project_type: ProjectType) -> VersionFilter {
+    let loaders = match project_type {
+        ProjectType::Mod => metadata.loader
+            .as_ref()
+            .map(|l| modrinth_loader_names(l.kind).iter().map(|n| n.to_string()).collect())
+            .unwrap_or_default(),
+        ProjectType::ResourcePack => vec!["minecraft".to_string()],
+        ProjectType::Shader => Vec::new(),
+        ProjectType::Datapack => vec!["datapack".to_string()],
+    };
+
+    VersionFilter {
+        game_versions: vec![metadata.minecraft_version.clone()],
+        loaders,
+    }
+}
+
+// Directory project type is installed into
+// Data packs belong to single world - saves/{world}/datapacks
+pub fn destination_dir(instance_name: &str, project_type: ProjectType, world: Option<&str>) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let mc_instance_dir_path = instance::instance_path(instance_name).join("minecraft");
+
+    Ok(match project_type {
+        ProjectType::Mod => manager::mods_path(instance_name),
+        ProjectType::ResourcePack => mc_instance_dir_path.join("resourcepacks"),
+        ProjectType::Shader => mc_instance_dir_path.join("shaderpacks"),
+        ProjectType::Datapack => {
+            let world = world.ok_or("Data packs need a world to be installed into")?;
+            mc_instance_dir_path.join("saves").join(world).join("datapacks")
+        }
+    })
+}
+
+// Worlds of instance, data packs are installed into one of them
+pub fn world_list(instance_name: &str) -> Vec<String> {
+    let saves_path = instance::instance_path(instance_name).join("minecraft").join("saves");
+
+    let mut worlds: Vec<String> = match fs::read_dir(saves_path) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_dir())
+            .filter_map(|e| e.file_name().into_string().ok())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+
+    worlds.sort();
+    worlds
+}
+
+// Versions come newest first, first release wins
+pub fn best_version(versions: &[ModrinthVersion]) -> Option<&ModrinthVersion> {
+    versions.iter().find(|v| v.version_type == "release").or_else(|| versions.first())
+}
+
+// Downloads primary file of version into directory, file is checked against Modrinth hashes
+pub fn download_version_file(version: &ModrinthVersion, destination: &PathBuf) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let file = version.primary_file().ok_or_else(|| format!("Version {} has no files", version.version_number))?;
+
+    // File name comes from server, it must not point outside destination
+    if file.filename.contains(['/', '\\']) || file.filename.starts_with('.') {
+        return Err(format!("Invalid file name: {}", file.filename).into());
+    }
+
+    let path = destination.join(&file.filename);
+    api::download_file_verified(
+        &file.url,
+        &path,
+        file.hashes.get("sha1").map(|h| h.as_str()),
+        file.hashes.get("sha512").map(|h| h.as_str()),
+    )?;
+
+    Ok(path)
+}
+
+// Modrinth projects of mods already in instance, found by file hashes
+pub fn installed_project_ids(instance_name: &str, modrinth: &dyn ModrinthApi) -> Result<HashSet<String>, Box<dyn std::error::Error>> {
+    let mut hashes = Vec::new();
+    for installed_mod in manager::list_mods(instance_name)? {
+        hashes.push(api::sha1_hex(&fs::read(&installed_mod.path)?));
+    }
+
+    let versions = modrinth.versions_from_hashes(&hashes, "sha1")?;
+    Ok(versions.into_values().map(|v| v.project_id).collect())
+}
+
+// Installs project into instance, mods also get their required dependencies
+// Returns names of installed files
+pub fn install_project(
+    metadata: &InstanceMetadata,
+    project_id: &str,
+    project_type: ProjectType,
+    world: Option<&str>,
+    modrinth: &dyn ModrinthApi,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let filter = instance_filter(metadata, project_type);
+    let destination = destination_dir(&metadata.name, project_type, world)?;
+    fs::create_dir_all(&destination)?;
+
+    let versions = modrinth.project_versions(project_id, &filter)?;
+    let version = best_version(&versions)
+        .ok_or_else(|| format!("No version for Minecraft {} found", metadata.minecraft_version))?
+        .clone();
+
+    // Only mods have dependencies worth resolving
+    let mut installed_projects = match project_type {
+        ProjectType::Mod => installed_project_ids(&metadata.name, modrinth)?,
+        _ => HashSet::new(),
+    };
+
+    if installed_projects.contains(&version.project_id) {
+        return Err(format!("{} is already installed", version.name).into());
+    }
+
+    let mut installed_files = Vec::new();
+    let mut pending = vec![version];
+
+    while let Some(version) = pending.pop() {
+        // Same dependency can be required by several mods
+        if !installed_projects.insert(version.project_id.clone()) {
+            continue;
+        }
+
+        println!("Installing {} {}...", version.name, version.version_number);
+        let path = download_version_file(&version, &destination)?;
+        installed_files.push(path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default());
+
+        if project_type != ProjectType::Mod {
+            continue;
+        }
+
+        for dependency in version.dependencies.iter().filter(|d| d.dependency_type == "required") {
+            if dependency.project_id.as_ref().is_some_and(|id| installed_projects.contains(id)) {
+                continue;
+            }
+
+            // Pinned version is used as is, otherwise best version for instance
+            let dependency_version = match (&dependency.version_id, &dependency.project_id) {
+                (Some(version_id), _) => modrinth.version(version_id)?,
+                (None, Some(project_id)) => {
+                    let versions = modrinth.project_versions(project_id, &filter)?;
+                    match best_version(&versions) {
+                        Some(version) => version.clone(),
+                        None => {
+                            eprintln!("No compatible version of dependency {} found", project_id);
+                            continue;
+                        }
+                    }
+                }
+                (None, None) => continue,
+            };
+
+            pending.push(dependency_version);
+        }
+    }
+
+    Ok(installed_files)
+}
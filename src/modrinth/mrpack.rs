@@ -0,0 +1,430 @@
+// This is where Modrinth modpacks (.mrpack) are handled.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// Format specification: https://support.modrinth.com/en/articles/8802351-modrinth-modpack-format-mrpack
+
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Component, Path};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::instances::instance::{self, ExportOptions, InstanceMetadata, InstanceSettings, LoaderKind, ModLoader};
+use crate::minecraft::api;
+use crate::modrinth::api::ModrinthApi;
+
+
+pub const INDEX_FILE: &str = "modrinth.index.json";
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MrpackIndex {
+    #[serde(rename = "formatVersion")]
+    pub format_version: u32,
+    pub game: String,
+    #[serde(rename = "versionId")]
+    pub version_id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    pub files: Vec<MrpackFile>,
+    pub dependencies: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MrpackFile {
+    pub path: String,
+    pub hashes: MrpackHashes,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<MrpackEnv>,
+    pub downloads: Vec<String>,
+    #[serde(rename = "fileSize")]
+    pub file_size: u64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MrpackHashes {
+    pub sha1: String,
+    pub sha512: String,
+}
+
+// Values are "required", "optional" or "unsupported"
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MrpackEnv {
+    pub client: String,
+    pub server: String,
+}
+
+
+// Dependency keys used by mrpack for mod loaders
+pub fn loader_dependency_key(kind: LoaderKind) -> &'static str {
+    match kind {
+        LoaderKind::Fabric => "fabric-loader",
+        LoaderKind::Quilt => "quilt-loader",
+        LoaderKind::Forge => "forge",
+        LoaderKind::NeoForge => "neoforge",
+    }
+}
+
+// Maps mrpack dependencies to minecraft version and mod loader
+fn parse_dependencies(dependencies: &HashMap<String, String>) -> Result<(String, Option<ModLoader>), Box<dyn std::error::Error>> {
+    let minecraft_version = dependencies
+        .get("minecraft")
+        .ok_or("Modpack does not specify minecraft version")?
+        .clone();
+
+    let mut loader = None;
+    for kind in [LoaderKind::Fabric, LoaderKind::Quilt, LoaderKind::Forge, LoaderKind::NeoForge] {
+        if let Some(version) = dependencies.get(loader_dependency_key(kind)) {
+            if loader.is_some() {
+                return Err("Modpack specifies more than one mod loader".into());
+            }
+            loader = Some(ModLoader { kind, version: version.clone() });
+        }
+    }
+
+    Ok((minecraft_version, loader))
+}
+
+// File paths in index must stay inside instance's minecraft directory
+fn is_safe_path(path: &str) -> bool {
+    let path = Path::new(path);
+    !path.as_os_str().is_empty() && path.components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+pub fn read_index(zip: &mut ZipArchive<File>) -> Result<MrpackIndex, Box<dyn std::error::Error>> {
+    let mut index_file = zip.by_name(INDEX_FILE)?;
+    let mut index_content = String::new();
+    index_file.read_to_string(&mut index_content)?;
+
+    let index: MrpackIndex = serde_json::from_str(&index_content)?;
+    if index.game != "minecraft" {
+        return Err(format!("Unsupported game: {}", index.game).into());
+    }
+
+    Ok(index)
+}
+
+// Imports .mrpack file as new instance
+// When instance_name is None, modpack name is used
+pub fn mrpack_import(mrpack_path: &Path, instance_name: Option<&str>) -> Result<String, Box<dyn std::error::Error>> {
+    let mut zip = ZipArchive::new(File::open(mrpack_path)?)?;
+    let index = read_index(&mut zip)?;
+
+    println!("Importing modpack {} {}...", index.name, index.version_id);
+
+    let (minecraft_version, loader) = parse_dependencies(&index.dependencies)?;
+    // Modpack name comes from the archive, so it is sanitized before it becomes directory name
+    let mut metadata = InstanceMetadata {
+        name: instance_name.map(|n| n.to_string()).unwrap_or_else(|| instance::sanitize_instance_name(&index.name)),
+        minecraft_version,
+        loader,
+        settings: InstanceSettings::default(),
+        version_id: None,
+    };
+
+    let instance_dir_path = instance::instance_new(&metadata)?;
+
+    if let Err(e) = mrpack_install_files(&mut zip, &index, &instance_dir_path.join("minecraft"))
+        .and_then(|_| instance::instance_install_game(&mut metadata))
+    {
+        instance::remove_failed_instance(&instance_dir_path);
+        return Err(e);
+    }
+
+    println!("Modpack {} imported succesfully!", index.name);
+    Ok(metadata.name)
+}
+
+// Downloads modpack files and extracts overrides into instance's minecraft directory
+fn mrpack_install_files(zip: &mut ZipArchive<File>, index: &MrpackIndex, mc_instance_dir_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    // Modpack files
+    // Files unsupported on client are skipped
+    let client_files: Vec<&MrpackFile> = index.files
+        .iter()
+        .filter(|f| f.env.as_ref().is_none_or(|env| env.client != "unsupported"))
+        .collect();
+
+    println!("Found {} modpack files to download", client_files.len());
+
+    for (i, file) in client_files.iter().enumerate() {
+        if !is_safe_path(&file.path) {
+            return Err(format!("Modpack file has invalid path: {}", file.path).into());
+        }
+
+        let file_path = mc_instance_dir_path.join(&file.path);
+
+        println!("Downloading file {}/{}: {}", i + 1, client_files.len(), file.path);
+
+        // Every download URL is tried until one succeeds
+        let mut last_error: Option<Box<dyn std::error::Error>> = None;
+        for url in &file.downloads {
+            match api::download_file_verified(url, &file_path, Some(&file.hashes.sha1), Some(&file.hashes.sha512)) {
+                Ok(_) => {
+                    last_error = None;
+                    break;
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        if file.downloads.is_empty() {
+            return Err(format!("Modpack file has no downloads: {}", file.path).into());
+        }
+        if let Some(e) = last_error {
+            return Err(format!("Failed to download {}: {}", file.path, e).into());
+        }
+    }
+
+    // Overrides, client overrides are applied last so they take precedence
+    instance::zip_extract_dir(zip, "overrides", mc_instance_dir_path, instance::GAME_FILE_ENTRIES)?;
+    instance::zip_extract_dir(zip, "client-overrides", mc_instance_dir_path, instance::GAME_FILE_ENTRIES)?;
+
+    Ok(())
+}
+
+
+// Directories whose files can be matched to Modrinth downloads
+// Anything else always goes into overrides
+const MATCHABLE_DIRS: &[&str] = &["mods", "resourcepacks", "shaderpacks"];
+
+// Collects files under path relative to instance's minecraft directory, skipping excluded paths
+fn collect_files(path: &Path, relative_path: &str, exclude: &[String], files: &mut Vec<String>) -> Result<(), Box<dyn std::error::Error>> {
+    if exclude.iter().any(|e| relative_path == e || relative_path.starts_with(&format!("{}/", e))) {
+        return Ok(());
+    }
+
+    if path.is_dir() {
+        for entry in fs::read_dir(path)? {
+            let entry = entry?;
+            let file_name = entry.file_name().to_string_lossy().to_string();
+
+            collect_files(&entry.path(), &format!("{}/{}", relative_path, file_name), exclude, files)?;
+        }
+    } else if path.is_file() {
+        files.push(relative_path.to_string());
+    }
+
+    Ok(())
+}
+
+// Exports instance as .mrpack file
+// Files Modrinth knows by hash become download entries, everything else is packaged into overrides
+pub fn mrpack_export(
+    instance_name: &str,
+    mrpack_path: &Path,
+    version_id: &str,
+    options: &ExportOptions,
+    modrinth: &dyn ModrinthApi,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let metadata = instance::instance_metadata(instance_name)?;
+    let mc_instance_dir_path = instance::instance_path(instance_name).join("minecraft");
+
+    write_mrpack(&metadata, &mc_instance_dir_path, mrpack_path, version_id, options, modrinth)?;
+
+    println!("Instance {} exported to {}", instance_name, mrpack_path.display());
+    Ok(())
+}
+
+// Writes .mrpack of instance whose minecraft directory is mc_instance_dir_path
+fn write_mrpack(
+    metadata: &InstanceMetadata,
+    mc_instance_dir_path: &Path,
+    mrpack_path: &Path,
+    version_id: &str,
+    options: &ExportOptions,
+    modrinth: &dyn ModrinthApi,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Dependencies
+    let mut dependencies = HashMap::new();
+    dependencies.insert("minecraft".to_string(), metadata.minecraft_version.clone());
+    if let Some(loader) = &metadata.loader {
+        dependencies.insert(loader_dependency_key(loader.kind).to_string(), loader.version.clone());
+    }
+
+    // Files to export
+    let mut entries = options.include.clone();
+    if options.include_saves && !entries.iter().any(|e| e == "saves") {
+        entries.push("saves".to_string());
+    }
+
+    let mut files = Vec::new();
+    for entry in &entries {
+        collect_files(&mc_instance_dir_path.join(entry), entry, &options.exclude, &mut files)?;
+    }
+
+    // Hashes of files that may be on Modrinth
+    let mut file_hashes: HashMap<String, String> = HashMap::new();
+    for file in &files {
+        let top_dir = file.split('/').next().unwrap_or_default();
+        if MATCHABLE_DIRS.contains(&top_dir) {
+            let content = fs::read(mc_instance_dir_path.join(file))?;
+            file_hashes.insert(file.clone(), api::sha1_hex(&content));
+        }
+    }
+
+    println!("Looking up {} files on Modrinth...", file_hashes.len());
+    let hashes: Vec<String> = file_hashes.values().cloned().collect();
+    let versions = modrinth.versions_from_hashes(&hashes, "sha1")?;
+
+    let mut index = MrpackIndex {
+        format_version: 1,
+        game: "minecraft".to_string(),
+        version_id: version_id.to_string(),
+        name: metadata.name.clone(),
+        summary: None,
+        files: Vec::new(),
+        dependencies,
+    };
+
+    let mut zip = ZipWriter::new(File::create(mrpack_path)?);
+    let zip_options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for file in &files {
+        let file_path = mc_instance_dir_path.join(file);
+
+        // Modrinth download entry
+        let version_file = file_hashes.get(file).and_then(|hash| {
+            versions.get(hash).and_then(|version| {
+                version.files.iter().find(|f| f.hashes.get("sha1") == Some(hash))
+            })
+        });
+
+        if let Some(version_file) = version_file {
+            let content = fs::read(&file_path)?;
+
+            index.files.push(MrpackFile {
+                path: file.clone(),
+                hashes: MrpackHashes {
+                    sha1: api::sha1_hex(&content),
+                    sha512: api::sha512_hex(&content),
+                },
+                env: None,
+                downloads: vec![version_file.url.clone()],
+                file_size: content.len() as u64,
+            });
+            continue;
+        }
+
+        // Override
+        zip.start_file(format!("overrides/{}", file), zip_options)?;
+        let mut source = File::open(&file_path)?;
+        std::io::copy(&mut source, &mut zip)?;
+    }
+
+    println!("{} files matched on Modrinth, {} packaged into overrides", index.files.len(), files.len() - index.files.len());
+
+    zip.start_file(INDEX_FILE, zip_options)?;
+    zip.write_all(serde_json::to_string_pretty(&index)?.as_bytes())?;
+    zip.finish()?;
+
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modrinth::api::{ModrinthVersion, ModrinthVersionFile, SearchResults, VersionFilter};
+
+    const KNOWN_MOD: &[u8] = b"mod that is on Modrinth";
+    const KNOWN_MOD_URL: &str = "https://cdn.modrinth.com/data/known/versions/1/known.jar";
+
+    // Knows only KNOWN_MOD
+    struct FakeModrinth;
+
+    impl ModrinthApi for FakeModrinth {
+        fn versions_from_hashes(&self, hashes: &[String], _algorithm: &str) -> Result<HashMap<String, ModrinthVersion>, Box<dyn std::error::Error>> {
+            let known_hash = api::sha1_hex(KNOWN_MOD);
+            let version = ModrinthVersion {
+                id: "1".to_string(),
+                project_id: "known".to_string(),
+                name: "Known".to_string(),
+                version_number: "1.0".to_string(),
+                game_versions: vec!["1.20.1".to_string()],
+                loaders: vec!["fabric".to_string()],
+                files: vec![ModrinthVersionFile {
+                    hashes: HashMap::from([("sha1".to_string(), known_hash.clone())]),
+                    url: KNOWN_MOD_URL.to_string(),
+                    filename: "known.jar".to_string(),
+                    primary: true,
+                    size: KNOWN_MOD.len() as u64,
+                }],
+                dependencies: Vec::new(),
+                version_type: "release".to_string(),
+                date_published: String::new(),
+            };
+
+            Ok(hashes.iter().filter(|h| **h == known_hash).map(|h| (h.clone(), version.clone())).collect())
+        }
+
+        fn latest_versions_from_hashes(&self, _hashes: &[String], _algorithm: &str, _filter: &VersionFilter) -> Result<HashMap<String, ModrinthVersion>, Box<dyn std::error::Error>> {
+            Err("offline".into())
+        }
+
+        fn search(&self, _query: &str, _project_type: &str, _filter: &VersionFilter, _offset: u32) -> Result<SearchResults, Box<dyn std::error::Error>> {
+            Err("offline".into())
+        }
+
+        fn project_versions(&self, _project_id: &str, _filter: &VersionFilter) -> Result<Vec<ModrinthVersion>, Box<dyn std::error::Error>> {
+            Err("offline".into())
+        }
+
+        fn version(&self, _version_id: &str) -> Result<ModrinthVersion, Box<dyn std::error::Error>> {
+            Err("offline".into())
+        }
+    }
+
+    #[test]
+    fn export_splits_downloads_and_overrides() {
+        let test_dir_path = std::env::temp_dir().join(format!("copper-mrpack-test-{}", std::process::id()));
+        let mc_instance_dir_path = test_dir_path.join("minecraft");
+        fs::create_dir_all(mc_instance_dir_path.join("mods")).unwrap();
+        fs::create_dir_all(mc_instance_dir_path.join("config")).unwrap();
+        fs::write(mc_instance_dir_path.join("mods/known.jar"), KNOWN_MOD).unwrap();
+        fs::write(mc_instance_dir_path.join("mods/custom.jar"), b"mod made by hand").unwrap();
+        fs::write(mc_instance_dir_path.join("config/known.toml"), KNOWN_MOD).unwrap();
+
+        let metadata = InstanceMetadata {
+            name: "Test".to_string(),
+            minecraft_version: "1.20.1".to_string(),
+            loader: Some(ModLoader { kind: LoaderKind::Fabric, version: "0.16.0".to_string() }),
+            settings: InstanceSettings::default(),
+            version_id: None,
+        };
+        let options = ExportOptions {
+            include: vec!["mods".to_string(), "config".to_string()],
+            exclude: Vec::new(),
+            include_saves: false,
+            keep_settings: false,
+        };
+
+        let mrpack_path = test_dir_path.join("test.mrpack");
+        write_mrpack(&metadata, &mc_instance_dir_path, &mrpack_path, "2.0", &options, &FakeModrinth).unwrap();
+
+        let mut zip = ZipArchive::new(File::open(&mrpack_path).unwrap()).unwrap();
+        let index = read_index(&mut zip).unwrap();
+        let overrides: Vec<&str> = zip.file_names().filter(|n| n.starts_with("overrides/")).collect();
+
+        assert_eq!(index.version_id, "2.0");
+        assert_eq!(index.dependencies.get("fabric-loader").map(String::as_str), Some("0.16.0"));
+
+        // Only matchable directories become downloads, even when hash is known
+        assert_eq!(index.files.len(), 1);
+        assert_eq!(index.files[0].path, "mods/known.jar");
+        assert_eq!(index.files[0].downloads, vec![KNOWN_MOD_URL.to_string()]);
+        assert_eq!(index.files[0].hashes.sha1, api::sha1_hex(KNOWN_MOD));
+
+        assert!(overrides.contains(&"overrides/mods/custom.jar"));
+        assert!(overrides.contains(&"overrides/config/known.toml"));
+        assert!(!overrides.contains(&"overrides/mods/known.jar"));
+
+        fs::remove_dir_all(&test_dir_path).unwrap();
+    }
+}
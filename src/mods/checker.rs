@@ -0,0 +1,460 @@
+// This is where mods are checked before launch.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// Only metadata inside jars is used, so checks work offline
+// Version requirements come in two formats:
+//  - Fabric/Quilt predicates: ">=1.2.3", "~1.20", "1.20.x", "*", alternatives joined by "||"
+//  - Maven ranges used by Forge/NeoForge: "[47,)", "(,1.0]", "[1.0]", "1.0" (any version)
+
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::instances::instance::{InstanceMetadata, LoaderKind};
+use crate::mods::manager::{self, InstalledMod};
+use crate::mods::metadata::{DependencyKind, VersionFormat};
+
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModIssue {
+    MissingDependency { mod_name: String, dependency: String },
+    DependencyVersionMismatch { mod_name: String, dependency: String, required: String, found: String },
+    // Mod declares other installed mod as breaking or incompatible
+    Incompatible { mod_name: String, other: String },
+    DuplicateId { id: String, files: Vec<String> },
+    // None as instance loader means vanilla, which loads no mods at all
+    WrongLoader { mod_name: String, loaders: Vec<LoaderKind>, instance_loader: Option<LoaderKind> },
+    MinecraftVersionMismatch { mod_name: String, required: String, found: String },
+}
+
+impl fmt::Display for ModIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModIssue::MissingDependency { mod_name, dependency } => write!(f, "{} requires {}, which is not installed", mod_name, dependency),
+            ModIssue::DependencyVersionMismatch { mod_name, dependency, required, found } => {
+                write!(f, "{} requires {} {}, but {} is installed", mod_name, dependency, required, found)
+            }
+            ModIssue::Incompatible { mod_name, other } => write!(f, "{} is incompatible with {}", mod_name, other),
+            ModIssue::DuplicateId { id, files } => write!(f, "Mod {} is installed more than once: {}", id, files.join(", ")),
+            ModIssue::WrongLoader { mod_name, loaders, instance_loader } => {
+                let loaders: Vec<String> = loaders.iter().map(|l| format!("{:?}", l)).collect();
+                match instance_loader {
+                    Some(kind) => write!(f, "{} is made for {}, not {:?}", mod_name, loaders.join("/"), kind),
+                    None => write!(f, "{} needs {}, instance has no mod loader", mod_name, loaders.join("/")),
+                }
+            }
+            ModIssue::MinecraftVersionMismatch { mod_name, required, found } => {
+                write!(f, "{} requires Minecraft {}, instance is {}", mod_name, required, found)
+            }
+        }
+    }
+}
+
+
+// Compares versions component by component, numbers numerically
+// Build metadata after '+' is ignored, pre-release after '-' is older than release
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let split = |version: &str| -> (Vec<String>, Option<String>) {
+        let version = version.split('+').next().unwrap_or_default();
+        let (release, pre_release) = match version.split_once('-') {
+            Some((release, pre_release)) => (release, Some(pre_release.to_string())),
+            None => (version, None),
+        };
+        (release.split('.').map(|c| c.to_string()).collect(), pre_release)
+    };
+
+    let compare_component = |a: &str, b: &str| match (a.parse::<u64>(), b.parse::<u64>()) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        _ => a.cmp(b),
+    };
+
+    let (a_release, a_pre) = split(a);
+    let (b_release, b_pre) = split(b);
+
+    for i in 0..a_release.len().max(b_release.len()) {
+        let a_component = a_release.get(i).map(|c| c.as_str()).unwrap_or("0");
+        let b_component = b_release.get(i).map(|c| c.as_str()).unwrap_or("0");
+
+        match compare_component(a_component, b_component) {
+            Ordering::Equal => continue,
+            ordering => return ordering,
+        }
+    }
+
+    match (a_pre, b_pre) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a), Some(b)) => compare_versions(&a.replace('-', "."), &b.replace('-', ".")),
+    }
+}
+
+// Single Fabric predicate like ">=1.2", "~1.20.1", "1.20.x"
+fn predicate_matches(predicate: &str, version: &str) -> bool {
+    if predicate == "*" || predicate.is_empty() {
+        return true;
+    }
+
+    // Version with wildcard, components before it have to match
+    let wildcard_matches = |pattern: &str| -> bool {
+        let prefix: Vec<&str> = pattern.split('.').take_while(|c| !matches!(*c, "x" | "X" | "*")).collect();
+        let components: Vec<&str> = version.split(['.', '-', '+']).collect();
+        prefix.len() <= components.len() && prefix.iter().zip(&components).all(|(p, c)| compare_versions(p, c) == Ordering::Equal)
+    };
+
+    for (operator, matches) in [
+        (">=", &(|o: Ordering| o != Ordering::Less) as &dyn Fn(Ordering) -> bool),
+        ("<=", &|o| o != Ordering::Greater),
+        (">", &|o| o == Ordering::Greater),
+        ("<", &|o| o == Ordering::Less),
+    ] {
+        if let Some(target) = predicate.strip_prefix(operator) {
+            return matches(compare_versions(version, target.trim()));
+        }
+    }
+
+    // "~1.2.3" means same minor, "^1.2.3" means same major
+    for (operator, same_components) in [("~", 2), ("^", 1)] {
+        if let Some(target) = predicate.strip_prefix(operator) {
+            let target = target.trim();
+            let prefix: String = target.split('.').take(same_components).collect::<Vec<&str>>().join(".");
+            return compare_versions(version, target) != Ordering::Less && wildcard_matches(&format!("{}.x", prefix));
+        }
+    }
+
+    let target = predicate.strip_prefix('=').unwrap_or(predicate).trim();
+    if target.split('.').any(|c| matches!(c, "x" | "X" | "*")) {
+        return wildcard_matches(target);
+    }
+
+    compare_versions(version, target) == Ordering::Equal
+}
+
+// Maven range like "[1.0,2.0)", several ranges can be joined with commas
+fn maven_range_matches(range: &str, version: &str) -> bool {
+    let mut segments = Vec::new();
+    let mut start = None;
+    for (i, c) in range.char_indices() {
+        match c {
+            '[' | '(' => start = Some(i),
+            ']' | ')' => {
+                if let Some(start) = start.take() {
+                    segments.push(&range[start..=i]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    segments.iter().any(|segment| {
+        let inclusive_start = segment.starts_with('[');
+        let inclusive_end = segment.ends_with(']');
+        let inner = &segment[1..segment.len() - 1];
+
+        match inner.split_once(',') {
+            Some((lower, upper)) => {
+                let (lower, upper) = (lower.trim(), upper.trim());
+                let lower_ok = lower.is_empty() || match compare_versions(version, lower) {
+                    Ordering::Greater => true,
+                    Ordering::Equal => inclusive_start,
+                    Ordering::Less => false,
+                };
+                let upper_ok = upper.is_empty() || match compare_versions(version, upper) {
+                    Ordering::Less => true,
+                    Ordering::Equal => inclusive_end,
+                    Ordering::Greater => false,
+                };
+                lower_ok && upper_ok
+            }
+            None => compare_versions(version, inner.trim()) == Ordering::Equal,
+        }
+    })
+}
+
+// Checks version against requirement in format of the metadata file it came from
+pub fn version_matches(requirement: &str, version: &str, format: VersionFormat) -> bool {
+    let requirement = requirement.trim();
+
+    if requirement.starts_with('[') || requirement.starts_with('(') {
+        return maven_range_matches(requirement, version);
+    }
+
+    // Bare Maven version is only a recommendation, so it is not enforced
+    if format == VersionFormat::Maven {
+        return true;
+    }
+
+    requirement
+        .split("||")
+        .any(|alternative| alternative.split_whitespace().all(|predicate| predicate_matches(predicate, version)))
+}
+
+// Ids provided by loader and game itself, with versions when they are known
+fn builtin_ids(metadata: &InstanceMetadata) -> HashMap<String, Option<String>> {
+    let mut ids = HashMap::new();
+    ids.insert("minecraft".to_string(), Some(metadata.minecraft_version.clone()));
+    ids.insert("java".to_string(), None);
+
+    let Some(loader) = &metadata.loader else { return ids };
+    let loader_version = Some(loader.version.clone());
+
+    match loader.kind {
+        LoaderKind::Fabric => {
+            ids.insert("fabricloader".to_string(), loader_version);
+        }
+        LoaderKind::Quilt => {
+            ids.insert("quilt_loader".to_string(), loader_version);
+            // Quilt reports its own Fabric loader version, which is not known here
+            ids.insert("fabricloader".to_string(), None);
+        }
+        LoaderKind::Forge => {
+            // Forge 1.12.2 and older use capitalized ids
+            for id in ["forge", "Forge", "fml", "FML", "mcp", "javafml", "lowcodefml"] {
+                ids.insert(id.to_string(), loader_version.clone());
+            }
+        }
+        LoaderKind::NeoForge => {
+            for id in ["neoforge", "forge", "fml", "javafml", "lowcodefml"] {
+                ids.insert(id.to_string(), loader_version.clone());
+            }
+        }
+    }
+
+    ids
+}
+
+// Checks if loader can load mod made for another loader
+fn loader_accepts(instance_loader: LoaderKind, mod_loader: LoaderKind, minecraft_version: &str) -> bool {
+    match (instance_loader, mod_loader) {
+        (instance_loader, mod_loader) if instance_loader == mod_loader => true,
+        (LoaderKind::Quilt, LoaderKind::Fabric) => true,
+        // NeoForge used Forge's mods.toml before 1.20.5
+        (LoaderKind::NeoForge, LoaderKind::Forge) => compare_versions(minecraft_version, "1.20.5") == Ordering::Less,
+        _ => false,
+    }
+}
+
+// Checks list of mods against instance
+pub fn check_installed_mods(metadata: &InstanceMetadata, mods: &[InstalledMod]) -> Vec<ModIssue> {
+    let mut issues = Vec::new();
+
+    let enabled: Vec<&InstalledMod> = mods.iter().filter(|m| m.enabled && m.metadata.is_some()).collect();
+
+    // Mod loader
+    let instance_loader = metadata.loader.as_ref().map(|l| l.kind);
+    for installed_mod in &enabled {
+        let Some(mod_metadata) = &installed_mod.metadata else { continue };
+
+        let accepted = instance_loader.is_some_and(|instance_loader| {
+            mod_metadata.loaders.iter().any(|l| loader_accepts(instance_loader, *l, &metadata.minecraft_version))
+        });
+        if !accepted {
+            issues.push(ModIssue::WrongLoader {
+                mod_name: installed_mod.display_name().to_string(),
+                loaders: mod_metadata.loaders.clone(),
+                instance_loader,
+            });
+        }
+    }
+
+    // Vanilla does not load mods, so nothing else matters
+    if instance_loader.is_none() {
+        return issues;
+    }
+
+    // Duplicate ids
+    let mut files_by_id: HashMap<&str, Vec<String>> = HashMap::new();
+    for installed_mod in &enabled {
+        if let Some(mod_metadata) = &installed_mod.metadata {
+            files_by_id.entry(&mod_metadata.id).or_default().push(installed_mod.file_name.clone());
+        }
+    }
+
+    let mut duplicates: Vec<(&str, Vec<String>)> = files_by_id.into_iter().filter(|(_, files)| files.len() > 1).collect();
+    duplicates.sort();
+    for (id, files) in duplicates {
+        issues.push(ModIssue::DuplicateId { id: id.to_string(), files });
+    }
+
+    // Everything that can satisfy dependency, with version when known
+    let mut available = builtin_ids(metadata);
+    for installed_mod in &enabled {
+        let Some(mod_metadata) = &installed_mod.metadata else { continue };
+
+        available.insert(mod_metadata.id.clone(), Some(mod_metadata.version.clone()));
+        for provided in &mod_metadata.provides {
+            available.entry(provided.clone()).or_insert(None);
+        }
+    }
+
+    // Dependencies
+    for installed_mod in &enabled {
+        let Some(mod_metadata) = &installed_mod.metadata else { continue };
+        let mod_name = installed_mod.display_name().to_string();
+
+        for dependency in &mod_metadata.dependencies {
+            let installed_version = available.get(&dependency.id);
+            let version_ok = match (installed_version, &dependency.version_range) {
+                (Some(Some(version)), Some(range)) => version_matches(range, version, dependency.version_format),
+                _ => true,
+            };
+
+            match dependency.kind {
+                DependencyKind::Required => match installed_version {
+                    None => issues.push(ModIssue::MissingDependency { mod_name: mod_name.clone(), dependency: dependency.id.clone() }),
+                    Some(_) if version_ok => {}
+                    Some(found) => {
+                        let required = dependency.version_range.clone().unwrap_or_default();
+                        let found = found.clone().unwrap_or_default();
+
+                        if dependency.id == "minecraft" {
+                            issues.push(ModIssue::MinecraftVersionMismatch { mod_name: mod_name.clone(), required, found });
+                        } else {
+                            issues.push(ModIssue::DependencyVersionMismatch {
+                                mod_name: mod_name.clone(),
+                                dependency: dependency.id.clone(),
+                                required,
+                                found,
+                            });
+                        }
+                    }
+                },
+                // Breaking entry with version range only applies to matching versions
+                DependencyKind::Breaks => {
+                    if installed_version.is_some() && (dependency.version_range.is_none() || version_ok) {
+                        issues.push(ModIssue::Incompatible { mod_name: mod_name.clone(), other: dependency.id.clone() });
+                    }
+                }
+                DependencyKind::Optional => {}
+            }
+        }
+    }
+
+    issues
+}
+
+// Checks enabled mods of instance, used before launch
+pub fn check_instance_mods(metadata: &InstanceMetadata) -> Result<Vec<ModIssue>, Box<dyn std::error::Error>> {
+    let mods = manager::list_mods(&metadata.name)?;
+    Ok(check_installed_mods(metadata, &mods))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instances::instance::{InstanceSettings, ModLoader};
+    use crate::mods::metadata::{ModDependency, ModMetadata};
+    use std::path::PathBuf;
+
+    fn fabric_instance() -> InstanceMetadata {
+        InstanceMetadata {
+            name: "Test".to_string(),
+            minecraft_version: "1.20.1".to_string(),
+            loader: Some(ModLoader { kind: LoaderKind::Fabric, version: "0.15.11".to_string() }),
+            settings: InstanceSettings::default(),
+            version_id: None,
+        }
+    }
+
+    fn required(id: &str, version_range: Option<&str>) -> ModDependency {
+        ModDependency {
+            id: id.to_string(),
+            version_range: version_range.map(|v| v.to_string()),
+            kind: DependencyKind::Required,
+            version_format: VersionFormat::Predicate,
+        }
+    }
+
+    fn installed(id: &str, loader: LoaderKind, enabled: bool, dependencies: Vec<ModDependency>) -> InstalledMod {
+        InstalledMod {
+            path: PathBuf::from(format!("{}.jar", id)),
+            file_name: format!("{}.jar", id),
+            enabled,
+            metadata: Some(ModMetadata {
+                id: id.to_string(),
+                name: id.to_string(),
+                version: "1.0.0".to_string(),
+                description: String::new(),
+                authors: Vec::new(),
+                icon: None,
+                loaders: vec![loader],
+                dependencies,
+                provides: Vec::new(),
+            }),
+        }
+    }
+
+    #[test]
+    fn missing_dependency() {
+        let mods = [installed("sodium", LoaderKind::Fabric, true, vec![required("fabric-api", None)])];
+
+        assert_eq!(
+            check_installed_mods(&fabric_instance(), &mods),
+            vec![ModIssue::MissingDependency { mod_name: "sodium".to_string(), dependency: "fabric-api".to_string() }]
+        );
+    }
+
+    #[test]
+    fn incompatible_minecraft_and_loader() {
+        let mods = [
+            installed("newmod", LoaderKind::Fabric, true, vec![required("minecraft", Some(">=1.21")), required("fabricloader", Some(">=0.16"))]),
+            installed("forgemod", LoaderKind::Forge, true, Vec::new()),
+        ];
+
+        let issues = check_installed_mods(&fabric_instance(), &mods);
+        assert!(issues.contains(&ModIssue::MinecraftVersionMismatch {
+            mod_name: "newmod".to_string(),
+            required: ">=1.21".to_string(),
+            found: "1.20.1".to_string(),
+        }));
+        assert!(issues.contains(&ModIssue::DependencyVersionMismatch {
+            mod_name: "newmod".to_string(),
+            dependency: "fabricloader".to_string(),
+            required: ">=0.16".to_string(),
+            found: "0.15.11".to_string(),
+        }));
+        assert!(issues.contains(&ModIssue::WrongLoader {
+            mod_name: "forgemod".to_string(),
+            loaders: vec![LoaderKind::Forge],
+            instance_loader: Some(LoaderKind::Fabric),
+        }));
+        assert_eq!(issues.len(), 3);
+    }
+
+    #[test]
+    fn disabled_mods_are_ignored() {
+        // Disabled mod is not checked, and does not satisfy dependencies of others
+        let mods = [
+            installed("broken", LoaderKind::Forge, false, vec![required("missing", None)]),
+            installed("fabric-api", LoaderKind::Fabric, false, Vec::new()),
+            installed("sodium", LoaderKind::Fabric, true, vec![required("fabric-api", None)]),
+        ];
+
+        assert_eq!(
+            check_installed_mods(&fabric_instance(), &mods),
+            vec![ModIssue::MissingDependency { mod_name: "sodium".to_string(), dependency: "fabric-api".to_string() }]
+        );
+    }
+
+    #[test]
+    fn bare_version_is_exact_only_for_fabric_and_quilt() {
+        assert!(version_matches("1.20.1", "1.20.1", VersionFormat::Predicate));
+        assert!(!version_matches("1.20.1", "1.20.4", VersionFormat::Predicate));
+        assert!(version_matches("1.20.1", "1.20.4", VersionFormat::Maven));
+    }
+
+    #[test]
+    fn ranges_and_predicates() {
+        assert!(version_matches("[47,)", "47.2.0", VersionFormat::Maven));
+        assert!(!version_matches("[47,)", "46.0.1", VersionFormat::Maven));
+        assert!(version_matches(">=0.15 <0.17", "0.16.5", VersionFormat::Predicate));
+        assert!(!version_matches("~1.20", "1.21", VersionFormat::Predicate));
+        assert!(version_matches("1.20.x || 1.21", "1.20.6", VersionFormat::Predicate));
+    }
+}
@@ -0,0 +1,105 @@
+// This is where mods of instances are managed.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// Mods are jars in instance's mods directory
+// Disabled mods keep their file with ".disabled" suffix, loaders skip those files
+
+
+use std::fs;
+use std::path::PathBuf;
+
+use crate::instances::instance;
+use crate::mods::metadata::{self, ModMetadata};
+
+
+pub const DISABLED_SUFFIX: &str = ".disabled";
+
+#[derive(Debug, Clone)]
+pub struct InstalledMod {
+    pub path: PathBuf,
+    // File name without ".disabled" suffix
+    pub file_name: String,
+    pub enabled: bool,
+    // None when jar has no known metadata or can't be read
+    pub metadata: Option<ModMetadata>,
+}
+
+impl InstalledMod {
+    pub fn display_name(&self) -> &str {
+        match &self.metadata {
+            Some(metadata) if !metadata.name.is_empty() => &metadata.name,
+            _ => &self.file_name,
+        }
+    }
+}
+
+
+// Mods directory - .copper-launcher/instances/{instance}/minecraft/mods
+pub fn mods_path(instance_name: &str) -> PathBuf {
+    let mut mods_path = instance::instance_path(instance_name);
+            mods_path.push("minecraft");
+            mods_path.push("mods");
+
+    mods_path
+}
+
+// Lists enabled and disabled mod jars, sorted by name
+pub fn list_mods(instance_name: &str) -> Result<Vec<InstalledMod>, Box<dyn std::error::Error>> {
+    let mods_dir_path = mods_path(instance_name);
+    if !mods_dir_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut mods = Vec::new();
+    for entry in fs::read_dir(&mods_dir_path)? {
+        let path = entry?.path();
+        if !path.is_file() {
+            continue;
+        }
+
+        let Some(full_name) = path.file_name().map(|n| n.to_string_lossy().to_string()) else { continue };
+        let (file_name, enabled) = match full_name.strip_suffix(DISABLED_SUFFIX) {
+            Some(file_name) => (file_name.to_string(), false),
+            None => (full_name, true),
+        };
+
+        if !file_name.ends_with(".jar") {
+            continue;
+        }
+
+        // Broken jar should not hide other mods
+        let metadata = metadata::read_mod_metadata(&path).unwrap_or_else(|e| {
+            eprintln!("Failed to read metadata of {}: {}", file_name, e);
+            None
+        });
+
+        mods.push(InstalledMod { path, file_name, enabled, metadata });
+    }
+
+    mods.sort_by_key(|m| m.display_name().to_lowercase());
+    Ok(mods)
+}
+
+// Enables or disables mod by renaming its file
+pub fn set_mod_enabled(instance_name: &str, file_name: &str, enabled: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let mods_dir_path = mods_path(instance_name);
+    let enabled_path = mods_dir_path.join(file_name);
+    let disabled_path = mods_dir_path.join(format!("{}{}", file_name, DISABLED_SUFFIX));
+
+    let (from, to) = if enabled { (disabled_path, enabled_path) } else { (enabled_path, disabled_path) };
+    if !from.exists() {
+        return Err(format!("Mod {} not found", file_name).into());
+    }
+
+    // Both enabled and disabled copy can exist, rename would silently replace the other one
+    if to.exists() {
+        return Err(format!("{} already exists, remove one of the copies first", to.display()).into());
+    }
+
+    fs::rename(&from, &to)?;
+    Ok(())
+}
@@ -0,0 +1,412 @@
+// This is where metadata of mod jars is read.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// Supported metadata files:
+//  - fabric.mod.json                 Fabric (also loaded by Quilt)
+//  - quilt.mod.json                  Quilt
+//  - META-INF/mods.toml              Forge, NeoForge before 20.5
+//  - META-INF/neoforge.mods.toml     NeoForge
+//  - mcmod.info                      Forge 1.12.2 and older
+// One jar can contain several of them, jars like that work on more loaders
+
+
+use serde_json::Value;
+use std::fs::File;
+use std::io::{Cursor, Read, Seek};
+use std::path::Path;
+use zip::ZipArchive;
+
+use crate::instances::instance::LoaderKind;
+
+
+#[derive(Debug, Clone)]
+pub struct ModMetadata {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    pub description: String,
+    pub authors: Vec<String>,
+    // Path of icon inside jar
+    pub icon: Option<String>,
+    // Loaders this jar has metadata for
+    pub loaders: Vec<LoaderKind>,
+    pub dependencies: Vec<ModDependency>,
+    // Other mod ids this jar satisfies, including mods bundled inside it
+    pub provides: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModDependency {
+    pub id: String,
+    // Version requirement in format of the metadata file, e.g. ">=0.15" or "[47,)"
+    pub version_range: Option<String>,
+    pub kind: DependencyKind,
+    pub version_format: VersionFormat,
+}
+
+// How version requirement is read, bare version like "1.20.1" means different things
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionFormat {
+    // fabric.mod.json and quilt.mod.json, bare version has to match exactly
+    Predicate,
+    // mods.toml and mcmod.info, bare version is only a recommendation
+    Maven,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DependencyKind {
+    Required,
+    Optional,
+    // Mod does not work together with dependency
+    Breaks,
+}
+
+
+fn zip_read_to_string<R: Read + Seek>(zip: &mut ZipArchive<R>, name: &str) -> Option<String> {
+    let mut content = String::new();
+    zip.by_name(name).ok()?.read_to_string(&mut content).ok()?;
+    Some(content)
+}
+
+fn json_string(value: &Value, key: &str) -> String {
+    value.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string()
+}
+
+// Version requirement can be single string or list of alternatives
+fn json_version_range(value: &Value) -> Option<String> {
+    match value {
+        Value::String(range) => Some(range.clone()),
+        Value::Array(ranges) => {
+            let ranges: Vec<&str> = ranges.iter().filter_map(|r| r.as_str()).collect();
+            (!ranges.is_empty()).then(|| ranges.join(" || "))
+        }
+        _ => None,
+    }
+}
+
+// Icon is either path or map of sizes to paths, biggest is used
+fn json_icon(value: &Value) -> Option<String> {
+    match value {
+        Value::String(icon) => Some(icon.clone()),
+        Value::Object(sizes) => sizes
+            .iter()
+            .max_by_key(|(size, _)| size.parse::<u32>().unwrap_or(0))
+            .and_then(|(_, icon)| icon.as_str().map(|i| i.to_string())),
+        _ => None,
+    }
+}
+
+// fabric.mod.json - https://wiki.fabricmc.net/documentation:fabric_mod_json
+fn parse_fabric(content: &str) -> Option<ModMetadata> {
+    let json: Value = serde_json::from_str(content).ok()?;
+
+    // Authors are either names or objects with name
+    let authors = json.get("authors").and_then(|a| a.as_array()).map(|authors| {
+        authors
+            .iter()
+            .filter_map(|a| a.as_str().or_else(|| a.get("name").and_then(|n| n.as_str())))
+            .map(|a| a.to_string())
+            .collect()
+    });
+
+    let mut dependencies = Vec::new();
+    for (key, kind) in [
+        ("depends", DependencyKind::Required),
+        ("recommends", DependencyKind::Optional),
+        ("suggests", DependencyKind::Optional),
+        ("breaks", DependencyKind::Breaks),
+    ] {
+        if let Some(entries) = json.get(key).and_then(|d| d.as_object()) {
+            for (id, range) in entries {
+                dependencies.push(ModDependency { id: id.clone(), version_range: json_version_range(range), kind, version_format: VersionFormat::Predicate });
+            }
+        }
+    }
+
+    let provides = json.get("provides").and_then(|p| p.as_array())
+        .map(|p| p.iter().filter_map(|id| id.as_str()).map(|id| id.to_string()).collect())
+        .unwrap_or_default();
+
+    let id = json_string(&json, "id");
+    Some(ModMetadata {
+        name: json.get("name").and_then(|n| n.as_str()).unwrap_or(&id).to_string(),
+        version: json_string(&json, "version"),
+        description: json_string(&json, "description"),
+        authors: authors.unwrap_or_default(),
+        icon: json.get("icon").and_then(json_icon),
+        loaders: vec![LoaderKind::Fabric],
+        dependencies,
+        provides,
+        id,
+    })
+}
+
+// quilt.mod.json - https://github.com/QuiltMC/rfcs/blob/main/specification/0002-quilt.mod.json.md
+fn parse_quilt(content: &str) -> Option<ModMetadata> {
+    let json: Value = serde_json::from_str(content).ok()?;
+    let loader = json.get("quilt_loader")?;
+    let metadata = loader.get("metadata").cloned().unwrap_or(Value::Null);
+
+    // Dependencies are either ids or objects with id, versions and optional flag
+    let parse_dependencies = |key: &str, kind: DependencyKind| -> Vec<ModDependency> {
+        loader.get(key).and_then(|d| d.as_array()).map(|entries| {
+            entries
+                .iter()
+                .filter_map(|entry| match entry {
+                    Value::String(id) => Some(ModDependency { id: id.clone(), version_range: None, kind, version_format: VersionFormat::Predicate }),
+                    Value::Object(_) => {
+                        let optional = entry.get("optional").and_then(|o| o.as_bool()).unwrap_or(false);
+                        Some(ModDependency {
+                            id: entry.get("id")?.as_str()?.to_string(),
+                            version_range: entry.get("versions").and_then(json_version_range),
+                            kind: if optional && kind == DependencyKind::Required { DependencyKind::Optional } else { kind },
+                            version_format: VersionFormat::Predicate,
+                        })
+                    }
+                    _ => None,
+                })
+                .collect()
+        }).unwrap_or_default()
+    };
+
+    let mut dependencies = parse_dependencies("depends", DependencyKind::Required);
+    dependencies.extend(parse_dependencies("breaks", DependencyKind::Breaks));
+
+    // Provided ids are either ids or objects with id
+    let provides = loader.get("provides").and_then(|p| p.as_array())
+        .map(|p| p.iter().filter_map(|e| e.as_str().or_else(|| e.get("id").and_then(|id| id.as_str()))).map(|id| id.to_string()).collect())
+        .unwrap_or_default();
+
+    let id = json_string(loader, "id");
+    Some(ModMetadata {
+        name: metadata.get("name").and_then(|n| n.as_str()).unwrap_or(&id).to_string(),
+        version: json_string(loader, "version"),
+        description: json_string(&metadata, "description"),
+        authors: metadata.get("contributors").and_then(|c| c.as_object()).map(|c| c.keys().cloned().collect()).unwrap_or_default(),
+        icon: metadata.get("icon").and_then(json_icon),
+        loaders: vec![LoaderKind::Quilt],
+        dependencies,
+        provides,
+        id,
+    })
+}
+
+// mods.toml and neoforge.mods.toml - https://docs.neoforged.net/docs/gettingstarted/modfiles
+// ${file.jarVersion} is replaced by version from jar manifest
+fn parse_mods_toml(content: &str, loader: LoaderKind, jar_version: Option<&str>) -> Option<ModMetadata> {
+    let toml: toml::Value = toml::from_str(content).ok()?;
+    let mod_entries = toml.get("mods")?.as_array()?;
+    let mod_entry = mod_entries.first()?;
+
+    let toml_string = |value: &toml::Value, key: &str| -> String {
+        value.get(key).and_then(|v| v.as_str()).unwrap_or_default().trim().to_string()
+    };
+
+    let id = toml_string(mod_entry, "modId");
+
+    let mut version = toml_string(mod_entry, "version");
+    if version == "${file.jarVersion}" {
+        version = jar_version.unwrap_or_default().to_string();
+    }
+
+    // Authors are single string, usually separated by commas
+    let authors = toml.get("authors").or_else(|| mod_entry.get("authors"))
+        .and_then(|a| a.as_str())
+        .map(|a| a.split(',').map(|a| a.trim().to_string()).filter(|a| !a.is_empty()).collect())
+        .unwrap_or_default();
+
+    // Forge uses mandatory flag, NeoForge uses type
+    let mut dependencies = Vec::new();
+    let entries = toml.get("dependencies").and_then(|d| d.get(&id)).and_then(|d| d.as_array());
+    for entry in entries.into_iter().flatten() {
+        let dependency_type = toml_string(entry, "type");
+        let kind = match dependency_type.as_str() {
+            "required" => DependencyKind::Required,
+            "incompatible" => DependencyKind::Breaks,
+            "optional" | "discouraged" => DependencyKind::Optional,
+            _ if entry.get("mandatory").and_then(|m| m.as_bool()).unwrap_or(false) => DependencyKind::Required,
+            _ => DependencyKind::Optional,
+        };
+
+        let version_range = toml_string(entry, "versionRange");
+        dependencies.push(ModDependency {
+            id: toml_string(entry, "modId"),
+            version_range: (!version_range.is_empty()).then_some(version_range),
+            kind,
+            version_format: VersionFormat::Maven,
+        });
+    }
+
+    let logo = toml_string(mod_entry, "logoFile");
+    let logo = if logo.is_empty() { toml_string(&toml, "logoFile") } else { logo };
+
+    let name = toml_string(mod_entry, "displayName");
+    Some(ModMetadata {
+        name: if name.is_empty() { id.clone() } else { name },
+        version,
+        description: toml_string(mod_entry, "description"),
+        authors,
+        icon: (!logo.is_empty()).then_some(logo),
+        loaders: vec![loader],
+        dependencies,
+        // Jar can contain more mods, first one is shown
+        provides: mod_entries.iter().skip(1).map(|m| toml_string(m, "modId")).collect(),
+        id,
+    })
+}
+
+// mcmod.info - list of mods or object with modList
+fn parse_mcmod_info(content: &str) -> Option<ModMetadata> {
+    let json: Value = serde_json::from_str(content).ok()?;
+    let mod_entries = match &json {
+        Value::Array(mods) => mods,
+        Value::Object(_) => json.get("modList")?.as_array()?,
+        _ => return None,
+    };
+    let mod_entry = mod_entries.first()?;
+
+    let string_list = |key: &str| -> Vec<String> {
+        mod_entry.get(key).and_then(|l| l.as_array())
+            .map(|l| l.iter().filter_map(|v| v.as_str()).map(|v| v.to_string()).collect())
+            .unwrap_or_default()
+    };
+
+    // Required mods can have version like "forge@[14.23,)"
+    let dependencies = string_list("requiredMods")
+        .into_iter()
+        .map(|dependency| match dependency.split_once('@') {
+            Some((id, range)) => ModDependency {
+                id: id.to_string(),
+                version_range: Some(range.to_string()),
+                kind: DependencyKind::Required,
+                version_format: VersionFormat::Maven,
+            },
+            None => ModDependency { id: dependency, version_range: None, kind: DependencyKind::Required, version_format: VersionFormat::Maven },
+        })
+        .collect();
+
+    let logo = json_string(mod_entry, "logoFile");
+    let id = json_string(mod_entry, "modid");
+    Some(ModMetadata {
+        name: mod_entry.get("name").and_then(|n| n.as_str()).unwrap_or(&id).to_string(),
+        version: json_string(mod_entry, "version"),
+        description: json_string(mod_entry, "description"),
+        authors: string_list("authorList"),
+        icon: (!logo.is_empty()).then_some(logo),
+        loaders: vec![LoaderKind::Forge],
+        dependencies,
+        provides: mod_entries.iter().skip(1).map(|m| json_string(m, "modid")).collect(),
+        id,
+    })
+}
+
+// Paths of jars bundled inside Fabric or Quilt mod
+fn nested_jar_paths(fabric_json: Option<&str>, quilt_json: Option<&str>) -> Vec<String> {
+    let mut paths = Vec::new();
+
+    if let Some(json) = fabric_json.and_then(|c| serde_json::from_str::<Value>(c).ok())
+        && let Some(jars) = json.get("jars").and_then(|j| j.as_array())
+    {
+        paths.extend(jars.iter().filter_map(|j| j.get("file").and_then(|f| f.as_str())).map(|f| f.to_string()));
+    }
+
+    if let Some(json) = quilt_json.and_then(|c| serde_json::from_str::<Value>(c).ok())
+        && let Some(jars) = json.pointer("/quilt_loader/jars").and_then(|j| j.as_array())
+    {
+        paths.extend(jars.iter().filter_map(|j| j.as_str()).map(|f| f.to_string()));
+    }
+
+    paths
+}
+
+// Ids of mods bundled inside jar, e.g. Fabric API modules
+fn nested_mod_ids<R: Read + Seek>(zip: &mut ZipArchive<R>, nested_paths: &[String]) -> Vec<String> {
+    let mut ids = Vec::new();
+
+    for nested_path in nested_paths {
+        let mut content = Vec::new();
+        let Ok(mut file) = zip.by_name(nested_path) else { continue };
+        if file.read_to_end(&mut content).is_err() {
+            continue;
+        }
+        drop(file);
+
+        let Ok(mut nested_zip) = ZipArchive::new(Cursor::new(content)) else { continue };
+        let nested = zip_read_to_string(&mut nested_zip, "quilt.mod.json").and_then(|c| parse_quilt(&c))
+            .or_else(|| zip_read_to_string(&mut nested_zip, "fabric.mod.json").and_then(|c| parse_fabric(&c)));
+
+        if let Some(nested) = nested {
+            ids.push(nested.id);
+            ids.extend(nested.provides);
+        }
+    }
+
+    ids
+}
+
+// Implementation-Version from jar manifest
+fn manifest_version<R: Read + Seek>(zip: &mut ZipArchive<R>) -> Option<String> {
+    let manifest = zip_read_to_string(zip, "META-INF/MANIFEST.MF")?;
+    manifest
+        .lines()
+        .find_map(|line| line.strip_prefix("Implementation-Version:"))
+        .map(|version| version.trim().to_string())
+}
+
+// Reads metadata from mod jar
+// Metadata of first found format is used, other formats only add their loaders
+// None when jar has no known metadata
+pub fn read_mod_metadata(jar_path: &Path) -> Result<Option<ModMetadata>, Box<dyn std::error::Error>> {
+    let mut zip = ZipArchive::new(File::open(jar_path)?)?;
+    let jar_version = manifest_version(&mut zip);
+
+    let mut found: Vec<ModMetadata> = Vec::new();
+
+    let quilt_json = zip_read_to_string(&mut zip, "quilt.mod.json");
+    let fabric_json = zip_read_to_string(&mut zip, "fabric.mod.json");
+
+    if let Some(metadata) = quilt_json.as_deref().and_then(parse_quilt) {
+        found.push(metadata);
+    }
+    if let Some(metadata) = fabric_json.as_deref().and_then(parse_fabric) {
+        found.push(metadata);
+    }
+    if let Some(metadata) = zip_read_to_string(&mut zip, "META-INF/neoforge.mods.toml")
+        .and_then(|c| parse_mods_toml(&c, LoaderKind::NeoForge, jar_version.as_deref()))
+    {
+        found.push(metadata);
+    }
+    if let Some(metadata) = zip_read_to_string(&mut zip, "META-INF/mods.toml")
+        .and_then(|c| parse_mods_toml(&c, LoaderKind::Forge, jar_version.as_deref()))
+    {
+        found.push(metadata);
+    }
+    if let Some(metadata) = zip_read_to_string(&mut zip, "mcmod.info").and_then(|c| parse_mcmod_info(&c)) {
+        found.push(metadata);
+    }
+
+    let mut found = found.into_iter();
+    let Some(mut metadata) = found.next() else { return Ok(None) };
+    for other in found {
+        metadata.loaders.extend(other.loaders);
+    }
+
+    let nested_paths = nested_jar_paths(fabric_json.as_deref(), quilt_json.as_deref());
+    metadata.provides.extend(nested_mod_ids(&mut zip, &nested_paths));
+
+    Ok(Some(metadata))
+}
+
+// Reads icon image from mod jar
+pub fn read_mod_icon(jar_path: &Path, icon: &str) -> Option<Vec<u8>> {
+    let mut zip = ZipArchive::new(File::open(jar_path).ok()?).ok()?;
+    let mut file = zip.by_name(icon.trim_start_matches('/')).ok()?;
+
+    let mut content = Vec::new();
+    file.read_to_end(&mut content).ok()?;
+    Some(content)
+}
@@ -0,0 +1,211 @@
+// This is where mod updates are checked and applied.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// Mods are identified on Modrinth by SHA-1 of their files
+// Updates are applied all at once: everything is downloaded first, then files are swapped
+// Replaced jars are kept - .copper-launcher/instances/{instance}/mod-updates/{timestamp in milliseconds}
+
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::instances::instance::{self, InstanceMetadata};
+use crate::minecraft::api;
+use crate::modrinth::api::{ModrinthApi, ModrinthVersion};
+use crate::modrinth::install::{self, ProjectType};
+use crate::mods::manager::{self, DISABLED_SUFFIX};
+
+
+// Update backups directory inside instance
+const UPDATES_DIR: &str = "mod-updates";
+
+// Files replaced by update, stored next to old jars
+const ROLLBACK_FILE: &str = "rollback.json";
+
+#[derive(Debug, Clone)]
+pub struct ModUpdate {
+    // Current file name without ".disabled" suffix
+    pub file_name: String,
+    pub enabled: bool,
+    pub current_version: String,
+    pub new_version: ModrinthVersion,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct ReplacedFile {
+    // File names as they are in mods directory, including ".disabled" suffix
+    old_file: String,
+    new_file: String,
+}
+
+
+// Finds newer versions of installed mods compatible with instance
+pub fn check_updates(metadata: &InstanceMetadata, modrinth: &dyn ModrinthApi) -> Result<Vec<ModUpdate>, Box<dyn std::error::Error>> {
+    let mods = manager::list_mods(&metadata.name)?;
+
+    let mut hashes = Vec::new();
+    for installed_mod in &mods {
+        hashes.push(api::sha1_hex(&fs::read(&installed_mod.path)?));
+    }
+
+    println!("Checking {} mods for updates...", mods.len());
+    let filter = install::instance_filter(metadata, ProjectType::Mod);
+    let current_versions = modrinth.versions_from_hashes(&hashes, "sha1")?;
+    let latest_versions = modrinth.latest_versions_from_hashes(&hashes, "sha1", &filter)?;
+
+    let mut updates = Vec::new();
+    for (installed_mod, hash) in mods.iter().zip(&hashes) {
+        let Some(latest) = latest_versions.get(hash) else { continue };
+
+        // Latest file is the installed one
+        if latest.files.iter().any(|f| f.hashes.get("sha1") == Some(hash)) {
+            continue;
+        }
+
+        let current_version = current_versions
+            .get(hash)
+            .map(|v| v.version_number.clone())
+            .or_else(|| installed_mod.metadata.as_ref().map(|m| m.version.clone()))
+            .unwrap_or_default();
+
+        updates.push(ModUpdate {
+            file_name: installed_mod.file_name.clone(),
+            enabled: installed_mod.enabled,
+            current_version,
+            new_version: latest.clone(),
+        });
+    }
+
+    Ok(updates)
+}
+
+fn mod_file_name(file_name: &str, enabled: bool) -> String {
+    if enabled { file_name.to_string() } else { format!("{}{}", file_name, DISABLED_SUFFIX) }
+}
+
+// Moves files back after failed or undone update
+fn restore_files(mods_dir_path: &Path, backup_dir: &Path, replaced: &[ReplacedFile]) -> Result<(), Box<dyn std::error::Error>> {
+    for file in replaced {
+        let new_path = mods_dir_path.join(&file.new_file);
+        if new_path.exists() {
+            fs::remove_file(&new_path)?;
+        }
+
+        let backup_path = backup_dir.join(&file.old_file);
+        if backup_path.exists() {
+            fs::rename(&backup_path, mods_dir_path.join(&file.old_file))?;
+        }
+    }
+
+    Ok(())
+}
+
+// Creates new backup directory, named after current time
+// Directory is created here, so two updates started at the same time never share it
+fn create_backup_dir(instance_name: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let updates_path = instance::instance_path(instance_name).join(UPDATES_DIR);
+    fs::create_dir_all(&updates_path)?;
+
+    let mut timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64;
+    loop {
+        let backup_dir = updates_path.join(timestamp.to_string());
+        match fs::create_dir(&backup_dir) {
+            Ok(_) => return Ok(backup_dir),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => timestamp += 1,
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+// Applies updates, old jars are moved into update backup
+// Nothing in mods directory changes when any download fails
+// Returns backup directory, which can be passed to rollback_updates
+pub fn apply_updates(instance_name: &str, updates: &[ModUpdate]) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    // Empty backup would become "Undo last update" and hide the real one
+    if updates.is_empty() {
+        return Err("No updates selected".into());
+    }
+
+    let mods_dir_path = manager::mods_path(instance_name);
+
+    let backup_dir = create_backup_dir(instance_name)?;
+    let staging_dir = backup_dir.join("new");
+    fs::create_dir_all(&staging_dir)?;
+
+    // Download everything first
+    let mut staged = Vec::new();
+    for update in updates {
+        println!("Downloading {} {}...", update.new_version.name, update.new_version.version_number);
+        match install::download_version_file(&update.new_version, &staging_dir) {
+            Ok(path) => staged.push((update, path)),
+            Err(e) => {
+                fs::remove_dir_all(&backup_dir)?;
+                return Err(format!("Failed to download update of {}: {}", update.file_name, e).into());
+            }
+        }
+    }
+
+    // Swap files, disabled mods stay disabled
+    let mut replaced = Vec::new();
+    for (update, staged_path) in &staged {
+        let staged_name = staged_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let file = ReplacedFile {
+            old_file: mod_file_name(&update.file_name, update.enabled),
+            new_file: mod_file_name(&staged_name, update.enabled),
+        };
+
+        // Old file is only recorded once it was moved, so restoring never deletes it
+        let mut swap = fs::rename(mods_dir_path.join(&file.old_file), backup_dir.join(&file.old_file));
+        if swap.is_ok() {
+            swap = fs::rename(staged_path, mods_dir_path.join(&file.new_file));
+            replaced.push(file);
+        }
+
+        if let Err(e) = swap {
+            restore_files(&mods_dir_path, &backup_dir, &replaced)?;
+            return Err(format!("Failed to replace {}: {}", update.file_name, e).into());
+        }
+    }
+
+    fs::remove_dir_all(&staging_dir)?;
+    fs::write(backup_dir.join(ROLLBACK_FILE), serde_json::to_string_pretty(&replaced)?)?;
+
+    println!("Updated {} mods, old files are in {}", replaced.len(), backup_dir.display());
+    Ok(backup_dir)
+}
+
+// Update backups of instance, newest first
+pub fn update_backups(instance_name: &str) -> Vec<PathBuf> {
+    let updates_path = instance::instance_path(instance_name).join(UPDATES_DIR);
+
+    let mut backups: Vec<PathBuf> = match fs::read_dir(updates_path) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.join(ROLLBACK_FILE).is_file())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+
+    backups.sort_by_key(|p| std::cmp::Reverse(p.file_name().and_then(|n| n.to_str()).and_then(|n| n.parse::<u64>().ok())));
+    backups
+}
+
+// Puts old jars back and removes updated ones
+pub fn rollback_updates(instance_name: &str, backup_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let rollback_content = fs::read_to_string(backup_dir.join(ROLLBACK_FILE))?;
+    let replaced: Vec<ReplacedFile> = serde_json::from_str(&rollback_content)?;
+
+    restore_files(&manager::mods_path(instance_name), backup_dir, &replaced)?;
+    fs::remove_dir_all(backup_dir)?;
+
+    println!("Rolled back {} mods", replaced.len());
+    Ok(())
+}
@@ -0,0 +1,281 @@
+// This is where crash report of the game is shown.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// Launched game is watched until it stops, crash window opens when it crashed
+// Relaunch uses active account, same as Play with account selected in main window
+
+
+use gtk::prelude::*;
+use gtk::{glib};
+use glib::{clone};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::auth::accounts;
+use crate::instances::instance;
+use crate::minecraft::crash::CrashReport;
+use crate::minecraft::game_process::GameSession;
+use crate::mods::manager::{self, InstalledMod};
+use crate::ui::launcher_ui;
+
+
+// How often launched game is checked
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+
+// Shows crash window once game of session stops, if it crashed
+pub fn watch_session(app: &gtk::Application, session: Arc<GameSession>) {
+    glib::timeout_add_local(WATCH_INTERVAL, clone!(
+        #[weak]
+        app,
+        #[upgrade_or]
+        glib::ControlFlow::Break,
+        move || {
+            if session.is_running() {
+                return glib::ControlFlow::Continue;
+            }
+
+            if let Some(report) = session.crash_report() {
+                crash_window(&app, &session.instance_name, report);
+            }
+            glib::ControlFlow::Break
+        }
+    ));
+}
+
+// Suspected mod with button that disables it
+fn suspect_row(instance_name: &str, suspect: &InstalledMod) -> gtk::ListBoxRow {
+    let name_label = gtk::Label::builder()
+        .label(suspect.display_name())
+        .halign(gtk::Align::Start)
+        .build();
+
+    let file_label = gtk::Label::builder()
+        .label(&suspect.file_name)
+        .halign(gtk::Align::Start)
+        .css_classes(["dim-label"])
+        .build();
+
+    let text_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .hexpand(true)
+        .build();
+    text_box.append(&name_label);
+    text_box.append(&file_label);
+
+    let disable_button = gtk::Button::builder()
+        .label(if suspect.enabled { "Disable" } else { "Disabled" })
+        .sensitive(suspect.enabled)
+        .valign(gtk::Align::Center)
+        .build();
+
+    let instance_name = instance_name.to_string();
+    let file_name = suspect.file_name.clone();
+    disable_button.connect_clicked(move |disable_button| {
+        match manager::set_mod_enabled(&instance_name, &file_name, false) {
+            Ok(_) => {
+                println!("Disabled {}", file_name);
+                disable_button.set_label("Disabled");
+                disable_button.set_sensitive(false);
+            }
+            Err(e) => eprintln!("Failed to disable mod {}: {}", file_name, e),
+        }
+    });
+
+    let row_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(12)
+        .margin_start(6)
+        .margin_end(6)
+        .margin_top(6)
+        .margin_bottom(6)
+        .build();
+    row_box.append(&text_box);
+    row_box.append(&disable_button);
+
+    gtk::ListBoxRow::builder()
+        .child(&row_box)
+        .activatable(false)
+        .build()
+}
+
+// Crash report with known problems, suspected mods and quick fixes
+pub fn crash_window(app: &gtk::Application, instance_name: &str, report: CrashReport) {
+    let Some(window) = app.active_window() else { return };
+
+    let title_label = gtk::Label::builder()
+        .label(format!("{} crashed ({})", instance_name, report.state.label().to_lowercase()))
+        .halign(gtk::Align::Start)
+        .css_classes(["title-2"])
+        .build();
+
+    let main_container = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(12)
+        .margin_start(12)
+        .margin_end(12)
+        .margin_top(12)
+        .margin_bottom(12)
+        .build();
+    main_container.append(&title_label);
+
+    // Known problems
+    for problem in &report.problems {
+        let problem_label = gtk::Label::builder()
+            .label(format!("• {}", problem))
+            .halign(gtk::Align::Start)
+            .wrap(true)
+            .xalign(0.0)
+            .build();
+        main_container.append(&problem_label);
+    }
+
+    // Suspected mods
+    if !report.suspects.is_empty() {
+        let suspects_label = gtk::Label::builder()
+            .label("Suspected mods")
+            .halign(gtk::Align::Start)
+            .css_classes(["heading"])
+            .build();
+
+        let suspects_list = gtk::ListBox::builder()
+            .selection_mode(gtk::SelectionMode::None)
+            .css_classes(["boxed-list"])
+            .build();
+        for suspect in &report.suspects {
+            suspects_list.append(&suspect_row(instance_name, suspect));
+        }
+
+        main_container.append(&suspects_label);
+        main_container.append(&suspects_list);
+    }
+
+    // Report text
+    let report_source = match report.files.first().and_then(|f| f.file_name()) {
+        Some(file_name) => file_name.to_string_lossy().to_string(),
+        None => "End of game log".to_string(),
+    };
+
+    let report_label = gtk::Label::builder()
+        .label(report_source)
+        .halign(gtk::Align::Start)
+        .css_classes(["heading"])
+        .build();
+
+    let report_view = gtk::TextView::builder()
+        .editable(false)
+        .cursor_visible(false)
+        .monospace(true)
+        .wrap_mode(gtk::WrapMode::WordChar)
+        .build();
+    report_view.buffer().set_text(&report.text);
+
+    let report_scroll = gtk::ScrolledWindow::builder()
+        .child(&report_view)
+        .vexpand(true)
+        .hexpand(true)
+        .build();
+
+    main_container.append(&report_label);
+    main_container.append(&report_scroll);
+
+    // Actions
+    let open_folder_button = gtk::Button::builder()
+        .label("Open folder")
+        .build();
+
+    let close_button = gtk::Button::builder()
+        .label("Close")
+        .build();
+
+    let relaunch_button = gtk::Button::builder()
+        .label("Relaunch")
+        .css_classes(["suggested-action"])
+        .build();
+
+    let pushbuttonrightpls = gtk::Box::new(gtk::Orientation::Horizontal, 0);
+    pushbuttonrightpls.set_hexpand(true);
+
+    let button_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(6)
+        .build();
+    button_box.append(&open_folder_button);
+    button_box.append(&pushbuttonrightpls);
+    button_box.append(&close_button);
+    button_box.append(&relaunch_button);
+
+    main_container.append(&button_box);
+
+    let crash_window = gtk::Window::builder()
+        .transient_for(&window)
+        .title(format!("{} crashed - Copper Launcher", instance_name))
+        .default_width(900)
+        .default_height(640)
+        .child(&main_container)
+        .build();
+
+    // Crash file is selected in file manager, game directory is opened when there is none
+    let newest_file = report.files.first().cloned();
+    open_folder_button.connect_clicked(clone!(
+        #[weak]
+        crash_window,
+        #[strong(rename_to = instance_name)]
+        instance_name.to_string(),
+        move |_| {
+            let callback = |result: Result<(), glib::Error>| {
+                if let Err(e) = result {
+                    eprintln!("Failed to open directory: {}", e);
+                }
+            };
+
+            match &newest_file {
+                Some(file_path) => {
+                    let file = gio::File::for_path(file_path);
+                    gtk::FileLauncher::new(Some(&file)).open_containing_folder(Some(&crash_window), None::<&gio::Cancellable>, callback);
+                }
+                None => {
+                    let file = gio::File::for_path(instance::instance_path(&instance_name).join("minecraft"));
+                    gtk::FileLauncher::new(Some(&file)).launch(Some(&crash_window), None::<&gio::Cancellable>, callback);
+                }
+            }
+        }
+    ));
+
+    close_button.connect_clicked(clone!(
+        #[weak]
+        crash_window,
+        move |_| crash_window.close()
+    ));
+
+    relaunch_button.connect_clicked(clone!(
+        #[weak]
+        crash_window,
+        #[weak]
+        app,
+        #[strong(rename_to = instance_name)]
+        instance_name.to_string(),
+        move |_| {
+            let Some(account_id) = accounts::list().ok().and_then(|list| list.active_account().map(|a| a.id.clone())) else {
+                println!("No account selected, add one in Accounts");
+                app.activate_action("manage_accounts", None);
+                return;
+            };
+
+            // Expired Microsoft login is refreshed here
+            match accounts::launch_account(&account_id) {
+                Ok(account) => {
+                    crash_window.close();
+                    launcher_ui::launch_game(&app, &instance_name, &account);
+                }
+                Err(e) => eprintln!("Failed to use account: {}", e),
+            }
+        }
+    ));
+
+    crash_window.present();
+}
@@ -9,108 +9,2791 @@
 //  - System that detects if launcher is first run, if yes than it will auto open special instance creation window (Special, for first time)
 
 
-use crate::instances::instance;
+use crate::auth::{accounts, authlib_injector, offline, skins};
+use crate::auth::accounts::AccountKind;
+use crate::auth::authlib_injector::YggdrasilSession;
+use crate::auth::microsoft::{MicrosoftAccount, MicrosoftAuth};
+use crate::auth::skins::{PlayerProfile, ProfileApi, ProfileClient, SkinModel};
+use crate::curseforge::api::{CurseForgeApi, CurseForgeClient};
+use crate::curseforge::{install as curseforge_install, modpack};
+use crate::instances::{instance, official, prism, upgrade};
+use crate::launcher::config;
 use crate::minecraft::api::get_release_versions;
+use crate::minecraft::{fabric, forge, game_launch, game_process, neoforge, quilt};
+use crate::modrinth::api::{ModrinthApi, ModrinthClient};
+use crate::modrinth::install::{self, ProjectType};
+use crate::modrinth::mrpack;
+use crate::mods::{checker, manager, updates};
+use crate::mods::metadata::{self, DependencyKind};
+use crate::ui::{crash_window, log_console};
+
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+use gtk::prelude::*;
+use gtk::{Application, ApplicationWindow, glib, gdk, StringList};
+use glib::{clone};
+
+
+// App logo, currently just copper ingot item lol
+static LOGO_SVG: &[u8] = include_bytes!("Copper_Ingot.svg");
+
+pub fn build_ui(app: &Application) {
+    // Play button
+    let play_button = gtk::Button::builder()
+        .label("Launch game")
+        .build();
+
+
+    // Account selector
+    let account_title = gtk::Label::default();
+    account_title.set_markup("Account");
+
+    let account_ids: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(Vec::new()));
+    let account_dropdown_options = StringList::new(&[]);
+    let account_dropdown = gtk::DropDown::new(Some(account_dropdown_options.clone()), None::<gtk::Expression>);
+
+    let accounts_button = gtk::Button::builder()
+        .label("Accounts")
+        .action_name("app.manage_accounts")
+        .build();
+
+    let account_row = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(6)
+        .build();
+    account_row.append(&account_dropdown);
+    account_row.append(&accounts_button);
+
+    let account_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(6)
+        .build();
+    account_box.append(&account_title);
+    account_box.append(&account_row);
+
+    // Fills account selector, active account is selected
+    let load_accounts = clone!(
+        #[weak]
+        account_dropdown,
+        #[weak]
+        account_dropdown_options,
+        #[strong]
+        account_ids,
+        move || {
+            let list = match accounts::list() {
+                Ok(list) => list,
+                Err(e) => {
+                    eprintln!("Failed to load accounts: {}", e);
+                    return;
+                }
+            };
+
+            let active_id = list.active_account().map(|a| a.id.clone());
+            account_ids.replace(list.accounts.iter().map(|a| a.id.clone()).collect());
+            account_dropdown_options.splice(
+                0,
+                account_dropdown_options.n_items(),
+                &list.accounts.iter().map(|a| a.label()).collect::<Vec<String>>().iter().map(|l| l.as_str()).collect::<Vec<&str>>(),
+            );
+
+            if let Some(position) = account_ids.borrow().iter().position(|id| Some(id) == active_id.as_ref()) {
+                account_dropdown.set_selected(position as u32);
+            }
+        }
+    );
+    load_accounts();
+
+    account_dropdown.connect_selected_notify(clone!(
+        #[strong]
+        account_ids,
+        move |dd| {
+            let Some(id) = account_ids.borrow().get(dd.selected() as usize).cloned() else { return };
+            if let Err(e) = accounts::update(|accounts| accounts.set_active(&id)) {
+                eprintln!("Failed to change account: {}", e);
+            }
+        }
+    ));
+
+    // Label
+    let label = gtk::Label::builder()
+        .label("Copper Launcher")
+        .vexpand(true)
+        .halign(gtk::Align::Center)
+        .valign(gtk::Align::Center)
+        .css_classes(["large-title"])
+        .build();
+
+    // Instance selector
+    let instance_names = instance::instance_list();
+    let instance_dropdown_options = StringList::new(&instance_names.iter().map(|s| s.as_str()).collect::<Vec<&str>>());
+        
+    let instance_dropdown = gtk::DropDown::new(Some(instance_dropdown_options.clone()), None::<gtk::Expression>);
+        instance_dropdown.set_selected(0);
+
+            instance_dropdown.connect_selected_notify(|dd| {
+                println!("Instance selector. Selected: {}", dd.selected())
+            });
+
+    // Launches selected instance
+    play_button.connect_clicked(clone!(
+        #[weak]
+        instance_dropdown,
+        #[weak]
+        account_dropdown,
+        #[strong]
+        account_ids,
+        move |play_button| {
+            let Some(app) = play_button.root().and_then(|root| root.downcast::<gtk::Window>().ok()).and_then(|w| w.application()) else { return };
+
+            let Some(instance_name) = selected_string(&instance_dropdown) else {
+                println!("No instance selected");
+                return;
+            };
+
+            // Same instance can't run twice, its log is shown instead
+            if let Some(session) = game_process::session(&instance_name).filter(|s| s.is_running()) {
+                println!("{} is already running", instance_name);
+                log_console::log_console_window(&app, session);
+                return;
+            }
+
+            let Some(account_id) = account_ids.borrow().get(account_dropdown.selected() as usize).cloned() else {
+                println!("No account selected, add one in Accounts");
+                app.activate_action("manage_accounts", None);
+                return;
+            };
+
+            // Expired Microsoft login is refreshed here
+            let account = match accounts::launch_account(&account_id) {
+                Ok(account) => account,
+                Err(e) => {
+                    eprintln!("Failed to use account: {}", e);
+                    return;
+                }
+            };
+
+            // Mod problems are only warnings, user can still launch
+            let issues = match instance::instance_metadata(&instance_name).and_then(|m| checker::check_instance_mods(&m)) {
+                Ok(issues) => issues,
+                Err(e) => {
+                    eprintln!("Failed to check mods: {}", e);
+                    Vec::new()
+                }
+            };
+
+            if issues.is_empty() {
+                launch_game(&app, &instance_name, &account);
+                return;
+            }
+
+            let issue_lines: Vec<String> = issues.iter().map(|i| format!("• {}", i)).collect();
+            let warning_dialog = gtk::AlertDialog::builder()
+                .modal(true)
+                .message(format!("{} may crash", instance_name))
+                .detail(issue_lines.join("\n"))
+                .buttons(["Cancel", "Launch anyway"])
+                .cancel_button(0)
+                .default_button(0)
+                .build();
+
+            let parent = play_button.root().and_then(|root| root.downcast::<gtk::Window>().ok());
+            warning_dialog.choose(parent.as_ref(), None::<&gio::Cancellable>, move |result| {
+                if result == Ok(1) {
+                    launch_game(&app, &instance_name, &account);
+                }
+            });
+        }
+    ));
+
+    // Reloads account list, used after accounts are changed
+    let refresh_accounts = gio::ActionEntry::builder("refresh_accounts")
+        .activate(move |_: &gtk::Application, _, _| load_accounts())
+        .build();
+
+    // Menu bar - Account manager
+    let manage_accounts = gio::ActionEntry::builder("manage_accounts")
+        .activate(|app: &gtk::Application, _, _| accounts_window(app))
+        .build();
+
+    // Menu bar - Log of selected instance's last game
+    let show_game_log = gio::ActionEntry::builder("show_game_log")
+        .activate(clone!(
+            #[weak]
+            instance_dropdown,
+            move |app: &gtk::Application, _, _| {
+                let Some(instance_name) = selected_string(&instance_dropdown) else { return };
+                match game_process::session(&instance_name) {
+                    Some(session) => log_console::log_console_window(app, session),
+                    None => println!("{} was not launched yet", instance_name),
+                }
+            }
+        ))
+        .build();
+
+    // Menu bar - Skins and capes of active account
+    let manage_skins = gio::ActionEntry::builder("manage_skins")
+        .activate(|app: &gtk::Application, _, _| skins_window(app))
+        .build();
+
+    // Reloads instance list, used after instance is created or imported
+    let refresh_instances = gio::ActionEntry::builder("refresh_instances")
+        .activate(clone!(
+            #[weak]
+            instance_dropdown_options,
+            move |_: &gtk::Application, _, _| {
+                let instance_names = instance::instance_list();
+                instance_dropdown_options.splice(
+                    0,
+                    instance_dropdown_options.n_items(),
+                    &instance_names.iter().map(|s| s.as_str()).collect::<Vec<&str>>(),
+                );
+            }
+        ))
+        .build();
+
+    // Menu bar - Export selected instance
+    let export_instance = gio::ActionEntry::builder("export_instance")
+        .activate(clone!(
+            #[weak]
+            instance_dropdown,
+            move |app: &gtk::Application, _, _| {
+                if let Some(instance_name) = selected_string(&instance_dropdown) {
+                    export_instance_window(app, &instance_name);
+                }
+            }
+        ))
+        .build();
+
+    // Menu bar - Settings of selected instance
+    let instance_settings = gio::ActionEntry::builder("instance_settings")
+        .activate(clone!(
+            #[weak]
+            instance_dropdown,
+            move |app: &gtk::Application, _, _| {
+                if let Some(instance_name) = selected_string(&instance_dropdown) {
+                    instance_settings_window(app, &instance_name);
+                }
+            }
+        ))
+        .build();
+
+    // Menu bar - Mods of selected instance
+    let manage_mods = gio::ActionEntry::builder("manage_mods")
+        .activate(clone!(
+            #[weak]
+            instance_dropdown,
+            move |app: &gtk::Application, _, _| {
+                if let Some(instance_name) = selected_string(&instance_dropdown) {
+                    mod_manager_window(app, &instance_name);
+                }
+            }
+        ))
+        .build();
+
+    // Menu bar - Modrinth browser for selected instance
+    let browse_modrinth = gio::ActionEntry::builder("browse_modrinth")
+        .activate(clone!(
+            #[weak]
+            instance_dropdown,
+            move |app: &gtk::Application, _, _| {
+                if let Some(instance_name) = selected_string(&instance_dropdown) {
+                    modrinth_browser_window(app, &instance_name);
+                }
+            }
+        ))
+        .build();
+
+    // Menu bar - CurseForge browser for selected instance
+    let browse_curseforge = gio::ActionEntry::builder("browse_curseforge")
+        .activate(clone!(
+            #[weak]
+            instance_dropdown,
+            move |app: &gtk::Application, _, _| {
+                if let Some(instance_name) = selected_string(&instance_dropdown) {
+                    curseforge_browser_window(app, &instance_name);
+                }
+            }
+        ))
+        .build();
+
+    // Menu bar - Launcher preferences
+    let open_preferences = gio::ActionEntry::builder("open_preferences")
+        .activate(|app: &gtk::Application, _, _| preferences_window(app))
+        .build();
+
+    // Menu bar - Import instance from archive
+    let import_instance = gio::ActionEntry::builder("import_instance")
+        .activate(|app: &gtk::Application, _, _| {
+            if let Some(window) = app.active_window() {
+                let filter = gtk::FileFilter::new();
+                    filter.set_name(Some("Instance archive or modpack"));
+                    filter.add_suffix("zip");
+                    filter.add_suffix("mrpack");
+
+                let filters = gio::ListStore::new::<gtk::FileFilter>();
+                    filters.append(&filter);
+
+                let dialog = gtk::FileDialog::builder()
+                    .title("Import instance")
+                    .modal(true)
+                    .filters(&filters)
+                    .build();
+
+                dialog.open(
+                    Some(&window),
+                    None::<&gio::Cancellable>,
+                    clone!(
+                        #[weak]
+                        app,
+                        move |result| {
+                            let Ok(file) = result else { return };
+                            let Some(archive_path) = file.path() else { return };
+
+                            let import_result = match archive_path.extension().and_then(|e| e.to_str()) {
+                                Some("mrpack") => mrpack::mrpack_import(&archive_path, None),
+                                _ if modpack::is_curseforge_modpack(&archive_path) => CurseForgeClient::from_config(&config::load_config().curseforge)
+                                    .and_then(|client| modpack::curseforge_import(&archive_path, None, &client)),
+                                _ if prism::is_prism_archive(&archive_path) => prism::prism_import(&archive_path, None),
+                                _ => instance::instance_import(&archive_path, None),
+                            };
+
+                            match import_result {
+                                Ok(instance_name) => println!("Imported instance: {}", instance_name),
+                                Err(e) => eprintln!("Failed to import instance: {}", e),
+                            }
+
+                            app.activate_action("refresh_instances", None);
+                        }
+                    )
+                );
+            }
+        })
+        .build();
+
+    // Menu bar - Import instance folder from Prism Launcher or MultiMC
+    let import_prism_instance = gio::ActionEntry::builder("import_prism_instance")
+        .activate(|app: &gtk::Application, _, _| {
+            if let Some(window) = app.active_window() {
+                let dialog = gtk::FileDialog::builder()
+                    .title("Select Prism Launcher or MultiMC instance folder")
+                    .modal(true)
+                    .build();
+
+                dialog.select_folder(
+                    Some(&window),
+                    None::<&gio::Cancellable>,
+                    clone!(
+                        #[weak]
+                        app,
+                        move |result| {
+                            let Ok(folder) = result else { return };
+                            let Some(folder_path) = folder.path() else { return };
+
+                            match prism::prism_import(&folder_path, None) {
+                                Ok(instance_name) => println!("Imported instance: {}", instance_name),
+                                Err(e) => eprintln!("Failed to import instance: {}", e),
+                            }
+
+                            app.activate_action("refresh_instances", None);
+                        }
+                    )
+                );
+            }
+        })
+        .build();
+
+    // Menu bar - Import profiles from official Minecraft Launcher
+    let import_official_profiles = gio::ActionEntry::builder("import_official_profiles")
+        .activate(|app: &gtk::Application, _, _| {
+            official_import_window(app);
+        })
+        .build();
+
+    app.add_action_entries([refresh_instances, refresh_accounts, manage_accounts, manage_skins, show_game_log, export_instance, instance_settings, manage_mods, browse_modrinth, browse_curseforge, open_preferences, import_instance, import_prism_instance, import_official_profiles]);
+
+    // State of selected instance's game, checked twice a second
+    let game_state_label = gtk::Label::builder()
+        .css_classes(["dim-label"])
+        .build();
+
+    glib::timeout_add_local(std::time::Duration::from_millis(500), clone!(
+        #[weak]
+        game_state_label,
+        #[weak]
+        instance_dropdown,
+        #[upgrade_or]
+        glib::ControlFlow::Break,
+        move || {
+            let state = selected_string(&instance_dropdown)
+                .and_then(|instance_name| game_process::session(&instance_name))
+                .map(|session| session.state().label())
+                .unwrap_or_default();
+            game_state_label.set_label(&state);
+
+            glib::ControlFlow::Continue
+        }
+    ));
+
+    // Containers - For nice layout
+    let main_container = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .halign(gtk::Align::Fill)
+        .valign(gtk::Align::Fill)
+        .hexpand(true)
+        .vexpand(true)
+        .build();
+
+    let pushdownpls = gtk::Box::new(gtk::Orientation::Vertical, 0);
+    pushdownpls.set_vexpand(true);
+
+    let bottom_container = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .halign(gtk::Align::Fill)
+        .spacing(24)
+        .margin_start(24)
+        .margin_end(24)
+        .margin_bottom(24)
+        .build();
+
+    bottom_container.append(&account_box);
+
+    let pushbuttonrightpls = gtk::Box::new(gtk::Orientation::Horizontal, 0);
+    pushbuttonrightpls.set_hexpand(true);
+    
+    bottom_container.append(&pushbuttonrightpls);
+    bottom_container.append(&game_state_label);
+    bottom_container.append(&instance_dropdown);
+    bottom_container.append(&play_button);
+
+    main_container.append(&label);
+    main_container.append(&pushdownpls);
+    main_container.append(&bottom_container);
+
+    // Create main window
+    let window = ApplicationWindow::builder()
+        .application(app)
+        .title("Copper Launcher")
+        .default_width(1280)
+        .default_height(720)
+        .resizable(false)
+        .show_menubar(true)
+        .child(&main_container)
+        .build();
+
+    // Present window
+    window.present();
+}
+
+// Launches instance with account and shows its log, crash report is shown if game crashes
+pub fn launch_game(app: &gtk::Application, instance_name: &str, account: &game_launch::LaunchAccount) {
+    match game_launch::launch_instance(instance_name, account) {
+        Ok(session) => {
+            log_console::log_console_window(app, session.clone());
+            crash_window::watch_session(app, session);
+        }
+        Err(e) => eprintln!("Failed to launch game: {}", e),
+    }
+}
+
+// Mod loaders that can be selected, index matches dropdown position
+const LOADER_OPTIONS: &[&str] = &["Vanilla", "Fabric", "Quilt", "Forge", "NeoForge"];
+
+fn loader_kind(index: u32) -> Option<instance::LoaderKind> {
+    match index {
+        1 => Some(instance::LoaderKind::Fabric),
+        2 => Some(instance::LoaderKind::Quilt),
+        3 => Some(instance::LoaderKind::Forge),
+        4 => Some(instance::LoaderKind::NeoForge),
+        _ => None,
+    }
+}
+
+fn loader_index(kind: instance::LoaderKind) -> u32 {
+    match kind {
+        instance::LoaderKind::Fabric => 1,
+        instance::LoaderKind::Quilt => 2,
+        instance::LoaderKind::Forge => 3,
+        instance::LoaderKind::NeoForge => 4,
+    }
+}
+
+// Fetches loader versions for minecraft version
+// Returns versions and index of version that should be selected by default
+fn fetch_loader_versions(kind: instance::LoaderKind, game_version: &str) -> (Vec<String>, u32) {
+    let result = match kind {
+        instance::LoaderKind::Fabric => fabric::fetch_loader_versions(game_version),
+        instance::LoaderKind::Quilt => quilt::fetch_loader_versions(game_version),
+        instance::LoaderKind::Forge => forge::fetch_loader_versions(game_version),
+        instance::LoaderKind::NeoForge => neoforge::fetch_loader_versions(game_version),
+    };
+
+    match result {
+        Ok(versions) => {
+            let default_index = versions.iter().position(|v| v.stable).unwrap_or(0) as u32;
+            (versions.into_iter().map(|v| v.version).collect(), default_index)
+        }
+        Err(e) => {
+            eprintln!("Failed to fetch {:?} versions: {}", kind, e);
+            (Vec::new(), 0)
+        }
+    }
+}
+
+// Mod loader and loader version dropdowns
+// Used in instance creation and instance settings
+#[derive(Clone)]
+struct LoaderSelector {
+    loader_dropdown: gtk::DropDown,
+    version_list: StringList,
+    version_dropdown: gtk::DropDown,
+}
+
+impl LoaderSelector {
+    fn new() -> Self {
+        let loader_dropdown = gtk::DropDown::from_strings(LOADER_OPTIONS);
+            loader_dropdown.set_selected(0);
+
+        let version_list = StringList::new(&[]);
+        let version_dropdown = gtk::DropDown::new(Some(version_list.clone()), None::<gtk::Expression>);
+            version_dropdown.set_sensitive(false);
+
+        LoaderSelector { loader_dropdown, version_list, version_dropdown }
+    }
+
+    // Reloads loader versions for minecraft version
+    fn refresh(&self, game_version: &str) {
+        self.version_list.splice(0, self.version_list.n_items(), &[]);
+        self.version_dropdown.set_sensitive(false);
+
+        let Some(kind) = loader_kind(self.loader_dropdown.selected()) else { return };
+
+        let (versions, default_index) = fetch_loader_versions(kind, game_version);
+        self.version_list.splice(0, 0, &versions.iter().map(|s| s.as_str()).collect::<Vec<&str>>());
+        self.version_dropdown.set_selected(default_index);
+        self.version_dropdown.set_sensitive(!versions.is_empty());
+    }
+
+    // Selects loader and version, used to show instance's current loader
+    fn select(&self, loader: Option<&instance::ModLoader>, game_version: &str) {
+        self.loader_dropdown.set_selected(loader.map(|l| loader_index(l.kind)).unwrap_or(0));
+        self.refresh(game_version);
+
+        if let Some(loader) = loader
+            && let Some(position) = (0..self.version_list.n_items())
+                .find(|i| self.version_list.string(*i).is_some_and(|v| v == loader.version))
+        {
+            self.version_dropdown.set_selected(position);
+        }
+    }
+
+    // Err when loader is selected without version
+    fn selected_loader(&self) -> Result<Option<instance::ModLoader>, String> {
+        match (loader_kind(self.loader_dropdown.selected()), selected_string(&self.version_dropdown)) {
+            (Some(kind), Some(version)) => Ok(Some(instance::ModLoader { kind, version })),
+            (Some(_), None) => Err("No mod loader version selected".to_string()),
+            (None, _) => Ok(None),
+        }
+    }
+}
+
+// Placeholder of settings field, shows value that is used when field is empty
+fn default_placeholder(default: Option<String>) -> String {
+    match default {
+        Some(default) if !default.is_empty() => format!("Default: {}", default),
+        _ => "Default".to_string(),
+    }
+}
+
+// Empty field is unset
+fn parse_number_field(field: &gtk::Entry, name: &str) -> Result<Option<u32>, String> {
+    let text = field.text().trim().to_string();
+    if text.is_empty() {
+        return Ok(None);
+    }
+
+    text.parse().map(Some).map_err(|_| format!("{} has to be a whole number", name))
+}
+
+// JVM and game settings fields, shared by instance settings and launcher defaults in preferences
+struct SettingsEditor {
+    grid: gtk::Grid,
+    min_memory_field: gtk::Entry,
+    max_memory_field: gtk::Entry,
+    gc_dropdown: gtk::DropDown,
+    jvm_args_field: gtk::Entry,
+    java_field: gtk::Entry,
+    width_field: gtk::Entry,
+    height_field: gtk::Entry,
+    fullscreen_dropdown: gtk::DropDown,
+    game_args_field: gtk::Entry,
+    pre_launch_field: gtk::Entry,
+    wrapper_field: gtk::Entry,
+    post_exit_field: gtk::Entry,
+    env_field: gtk::Entry,
+}
+
+impl SettingsEditor {
+    // Defaults are shown as placeholders of empty fields
+    fn new(settings: &instance::InstanceSettings, defaults: &instance::InstanceSettings) -> Self {
+        let grid = gtk::Grid::builder()
+            .row_spacing(6)
+            .column_spacing(12)
+            .build();
+
+        let number_field = |value: Option<u32>, default: Option<u32>| {
+            gtk::Entry::builder()
+                .text(value.map(|v| v.to_string()).unwrap_or_default())
+                .placeholder_text(default_placeholder(default.map(|d| d.to_string())))
+                .input_purpose(gtk::InputPurpose::Digits)
+                .hexpand(true)
+                .build()
+        };
+        let args_field = |value: &[String], default: &[String]| {
+            gtk::Entry::builder()
+                .text(value.join(" "))
+                .placeholder_text(default_placeholder(Some(default.join(" "))))
+                .hexpand(true)
+                .build()
+        };
+
+        // Memory
+        let min_memory_field = number_field(settings.min_memory, defaults.min_memory);
+        let max_memory_field = number_field(settings.max_memory, defaults.max_memory);
+
+        // Index 0 keeps default
+        let gc_options: Vec<String> = std::iter::once(default_placeholder(defaults.gc_preset.map(|g| g.label().to_string())))
+            .chain(instance::GcPreset::ALL.iter().map(|g| g.label().to_string()))
+            .collect();
+        let gc_dropdown = gtk::DropDown::from_strings(&gc_options.iter().map(|o| o.as_str()).collect::<Vec<&str>>());
+            gc_dropdown.set_selected(settings.gc_preset
+                .and_then(|g| instance::GcPreset::ALL.iter().position(|p| *p == g))
+                .map(|i| i as u32 + 1)
+                .unwrap_or(0));
+
+        let jvm_args_field = args_field(&settings.jvm_args, &defaults.jvm_args);
+
+        let java_field = gtk::Entry::builder()
+            .text(settings.java_path.as_ref().map(|p| p.to_string_lossy().to_string()).unwrap_or_default())
+            .placeholder_text(defaults.java_path.as_ref().map(|p| default_placeholder(Some(p.to_string_lossy().to_string()))).unwrap_or("Found automatically".to_string()))
+            .hexpand(true)
+            .build();
+
+        // Game window
+        let width_field = number_field(settings.window_width, defaults.window_width);
+        let height_field = number_field(settings.window_height, defaults.window_height);
+
+        let fullscreen_default = default_placeholder(defaults.fullscreen.map(|f| if f { "Fullscreen" } else { "Windowed" }.to_string()));
+        let fullscreen_dropdown = gtk::DropDown::from_strings(&[fullscreen_default.as_str(), "Windowed", "Fullscreen"]);
+            fullscreen_dropdown.set_selected(match settings.fullscreen {
+                None => 0,
+                Some(false) => 1,
+                Some(true) => 2,
+            });
+
+        let game_args_field = args_field(&settings.game_args, &defaults.game_args);
+
+        // Hooks
+        let command_field = |value: &Option<String>, default: &Option<String>| {
+            gtk::Entry::builder()
+                .text(value.clone().unwrap_or_default())
+                .placeholder_text(default_placeholder(default.clone()))
+                .hexpand(true)
+                .build()
+        };
+        let pre_launch_field = command_field(&settings.pre_launch_command, &defaults.pre_launch_command);
+            pre_launch_field.set_tooltip_text(Some("Runs before the game, launch is stopped when it fails"));
+        let wrapper_field = command_field(&settings.wrapper_command, &defaults.wrapper_command);
+            wrapper_field.set_tooltip_text(Some("Goes in front of java, like gamemoderun or prime-run"));
+        let post_exit_field = command_field(&settings.post_exit_command, &defaults.post_exit_command);
+            post_exit_field.set_tooltip_text(Some("Runs after the game, exit code is in $INST_EXIT_CODE"));
+
+        let env_text = |env: &std::collections::BTreeMap<String, String>| env.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<String>>().join(" ");
+        let env_field = gtk::Entry::builder()
+            .text(env_text(&settings.env))
+            .placeholder_text(default_placeholder(Some(env_text(&defaults.env))))
+            .tooltip_text("NAME=value pairs, hooks also get INST_NAME, INST_DIR, INST_MC_DIR and INST_JAVA")
+            .hexpand(true)
+            .build();
+
+        let rows: [(&str, &gtk::Widget); 13] = [
+            ("Minimum memory (MB)", min_memory_field.upcast_ref()),
+            ("Maximum memory (MB)", max_memory_field.upcast_ref()),
+            ("Garbage collector", gc_dropdown.upcast_ref()),
+            ("JVM arguments", jvm_args_field.upcast_ref()),
+            ("Java executable", java_field.upcast_ref()),
+            ("Window width", width_field.upcast_ref()),
+            ("Window height", height_field.upcast_ref()),
+            ("Window mode", fullscreen_dropdown.upcast_ref()),
+            ("Game arguments", game_args_field.upcast_ref()),
+            ("Pre-launch command", pre_launch_field.upcast_ref()),
+            ("Wrapper command", wrapper_field.upcast_ref()),
+            ("Post-exit command", post_exit_field.upcast_ref()),
+            ("Environment variables", env_field.upcast_ref()),
+        ];
+        for (row, (title, field)) in rows.into_iter().enumerate() {
+            let label = gtk::Label::builder()
+                .label(title)
+                .halign(gtk::Align::Start)
+                .build();
+            grid.attach(&label, 0, row as i32, 1, 1);
+            grid.attach(field, 1, row as i32, 1, 1);
+        }
+
+        SettingsEditor {
+            grid,
+            min_memory_field,
+            max_memory_field,
+            gc_dropdown,
+            jvm_args_field,
+            java_field,
+            width_field,
+            height_field,
+            fullscreen_dropdown,
+            game_args_field,
+            pre_launch_field,
+            wrapper_field,
+            post_exit_field,
+            env_field,
+        }
+    }
+
+    // Err when number field has something else than number
+    fn settings(&self) -> Result<instance::InstanceSettings, String> {
+        let min_memory = parse_number_field(&self.min_memory_field, "Minimum memory")?;
+        let max_memory = parse_number_field(&self.max_memory_field, "Maximum memory")?;
+        if let (Some(min_memory), Some(max_memory)) = (min_memory, max_memory)
+            && min_memory > max_memory
+        {
+            return Err("Minimum memory can't be bigger than maximum memory".to_string());
+        }
+
+        let java_path = self.java_field.text().trim().to_string();
+        let split_args = |field: &gtk::Entry| field.text().split_whitespace().map(|a| a.to_string()).collect();
+        let command = |field: &gtk::Entry| Some(field.text().trim().to_string()).filter(|c| !c.is_empty());
+
+        let mut env = std::collections::BTreeMap::new();
+        for pair in self.env_field.text().split_whitespace() {
+            let Some((name, value)) = pair.split_once('=').filter(|(name, _)| !name.is_empty()) else {
+                return Err(format!("Environment variable \"{}\" has to be NAME=value", pair));
+            };
+            env.insert(name.to_string(), value.to_string());
+        }
+
+        Ok(instance::InstanceSettings {
+            min_memory,
+            max_memory,
+            jvm_args: split_args(&self.jvm_args_field),
+            gc_preset: (self.gc_dropdown.selected() as usize).checked_sub(1).and_then(|i| instance::GcPreset::ALL.get(i).copied()),
+            java_path: if java_path.is_empty() { None } else { Some(PathBuf::from(java_path)) },
+            window_width: parse_number_field(&self.width_field, "Window width")?,
+            window_height: parse_number_field(&self.height_field, "Window height")?,
+            fullscreen: match self.fullscreen_dropdown.selected() {
+                1 => Some(false),
+                2 => Some(true),
+                _ => None,
+            },
+            game_args: split_args(&self.game_args_field),
+            pre_launch_command: command(&self.pre_launch_field),
+            wrapper_command: command(&self.wrapper_field),
+            post_exit_command: command(&self.post_exit_field),
+            env,
+        })
+    }
+}
+
+// Window for changing settings of instance
+fn instance_settings_window(app: &gtk::Application, instance_name: &str) {
+    let Some(window) = app.active_window() else { return };
+
+    let metadata = match instance::instance_metadata(instance_name) {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            eprintln!("Failed to read instance metadata: {}", e);
+            return;
+        }
+    };
+
+    // Minecraft version, current version stays selectable even when it is not a release
+    let mut versions = get_release_versions().unwrap_or_else(|e| {
+        eprintln!("Failed to fetch versions: {}", e);
+        Vec::new()
+    });
+    if !versions.contains(&metadata.minecraft_version) {
+        versions.insert(0, metadata.minecraft_version.clone());
+    }
+
+    let version_title = gtk::Label::builder()
+        .label("Minecraft version")
+        .halign(gtk::Align::Start)
+        .build();
+
+    let version_dropdown = gtk::DropDown::from_strings(&versions.iter().map(|v| v.as_str()).collect::<Vec<&str>>());
+        version_dropdown.set_selected(versions.iter().position(|v| *v == metadata.minecraft_version).unwrap_or(0) as u32);
+
+    // Mod loader
+    let loader_title = gtk::Label::builder()
+        .label("Mod loader")
+        .halign(gtk::Align::Start)
+        .build();
+
+    let loader_selector = LoaderSelector::new();
+        loader_selector.select(metadata.loader.as_ref(), &metadata.minecraft_version);
+
+    // Loader versions depend on minecraft version
+    let refresh_loader_versions = clone!(
+        #[strong]
+        loader_selector,
+        #[weak]
+        version_dropdown,
+        move || {
+            if let Some(game_version) = selected_string(&version_dropdown) {
+                loader_selector.refresh(&game_version);
+            }
+        }
+    );
+    let refresh_on_loader = refresh_loader_versions.clone();
+    loader_selector.loader_dropdown.connect_selected_notify(move |_| refresh_on_loader());
+    version_dropdown.connect_selected_notify(move |_| refresh_loader_versions());
+
+    let loader_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(12)
+        .build();
+    loader_box.append(&loader_selector.loader_dropdown);
+    loader_box.append(&loader_selector.version_dropdown);
+
+    // JVM and game settings
+    let settings_title = gtk::Label::builder()
+        .label("Java and game window")
+        .halign(gtk::Align::Start)
+        .css_classes(["heading"])
+        .build();
+
+    let settings_editor = SettingsEditor::new(&metadata.settings, &config::load_config().instance_defaults);
+
+    let status_label = gtk::Label::builder()
+        .css_classes(["error"])
+        .wrap(true)
+        .visible(false)
+        .build();
+
+    let apply_button = gtk::Button::builder()
+        .label("Apply")
+        .halign(gtk::Align::End)
+        .build();
+
+    let main_container = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(12)
+        .margin_start(24)
+        .margin_end(24)
+        .margin_top(24)
+        .margin_bottom(24)
+        .build();
+
+    main_container.append(&version_title);
+    main_container.append(&version_dropdown);
+    main_container.append(&loader_title);
+    main_container.append(&loader_box);
+    main_container.append(&settings_title);
+    main_container.append(&settings_editor.grid);
+    main_container.append(&status_label);
+    main_container.append(&apply_button);
+
+    let settings_window = gtk::Window::builder()
+        .transient_for(&window)
+        .modal(true)
+        .title(format!("{} settings - Copper Launcher", instance_name))
+        .default_width(480)
+        .child(&main_container)
+        .build();
+
+    apply_button.connect_clicked(clone!(
+        #[weak]
+        settings_window,
+        #[weak]
+        version_dropdown,
+        #[weak]
+        status_label,
+        move |_| {
+            let loader = match loader_selector.selected_loader() {
+                Ok(loader) => loader,
+                Err(e) => {
+                    println!("{}", e);
+                    return;
+                }
+            };
+            let Some(game_version) = selected_string(&version_dropdown) else { return };
+
+            // Settings are saved right away, version change below asks first
+            let settings = match settings_editor.settings() {
+                Ok(settings) => settings,
+                Err(e) => {
+                    status_label.set_label(&e);
+                    status_label.set_visible(true);
+                    return;
+                }
+            };
+            if settings != metadata.settings {
+                let saved = instance::instance_metadata(&metadata.name).and_then(|mut current| {
+                    current.settings = settings;
+                    instance::write_instance_metadata(&instance::instance_path(&metadata.name), &current)
+                });
+                if let Err(e) = saved {
+                    eprintln!("Failed to save instance settings: {}", e);
+                    return;
+                }
+            }
+
+            if game_version == metadata.minecraft_version && loader == metadata.loader {
+                settings_window.close();
+                return;
+            }
+
+            let warnings = match upgrade::upgrade_warnings(&metadata, &game_version, loader.as_ref(), &ModrinthClient::new()) {
+                Ok(warnings) => warnings,
+                Err(e) => {
+                    eprintln!("Failed to check instance: {}", e);
+                    return;
+                }
+            };
+
+            // Instance is backed up first, so user only confirms risks
+            let detail = if warnings.is_empty() {
+                "Instance will be backed up before the change.".to_string()
+            } else {
+                let warning_lines: Vec<String> = warnings.iter().map(|w| format!("• {}", w)).collect();
+                format!("{}\n\nInstance will be backed up before the change.", warning_lines.join("\n"))
+            };
+
+            let confirm_dialog = gtk::AlertDialog::builder()
+                .modal(true)
+                .message(format!("Change {} to Minecraft {}?", metadata.name, game_version))
+                .detail(detail)
+                .buttons(["Cancel", "Back up and apply"])
+                .cancel_button(0)
+                .default_button(1)
+                .build();
+
+            let instance_name = metadata.name.clone();
+            confirm_dialog.choose(Some(&settings_window), None::<&gio::Cancellable>, clone!(
+                #[weak]
+                settings_window,
+                move |result| {
+                    if result != Ok(1) {
+                        return;
+                    }
+
+                    match upgrade::instance_upgrade(&instance_name, &game_version, loader) {
+                        Ok(backup_path) => println!("Backup saved to {}", backup_path.display()),
+                        Err(e) => eprintln!("{}", e),
+                    }
+
+                    settings_window.close();
+                }
+            ));
+        }
+    ));
+
+    settings_window.present();
+}
+
+// Row of mod manager list - icon, name, version, authors and enable switch
+fn mod_row(instance_name: &str, installed_mod: &manager::InstalledMod) -> gtk::ListBoxRow {
+    let icon = gtk::Image::builder()
+        .pixel_size(32)
+        .icon_name("application-x-addon")
+        .build();
+
+    if let Some(texture) = installed_mod.metadata
+        .as_ref()
+        .and_then(|m| m.icon.as_deref())
+        .and_then(|i| metadata::read_mod_icon(&installed_mod.path, i))
+        .and_then(|bytes| gdk4::Texture::from_bytes(&glib::Bytes::from_owned(bytes)).ok())
+    {
+        icon.set_paintable(Some(&texture));
+    }
+
+    let (version, authors) = match &installed_mod.metadata {
+        Some(metadata) => (metadata.version.clone(), metadata.authors.join(", ")),
+        None => (String::new(), String::new()),
+    };
+
+    // Tooltip - mod id, description and required mods
+    let tooltip = installed_mod.metadata.as_ref().map(|metadata| {
+        let required: Vec<&str> = metadata.dependencies
+            .iter()
+            .filter(|d| d.kind == DependencyKind::Required)
+            .map(|d| d.id.as_str())
+            .collect();
+
+        let mut tooltip = format!("{}\n{}", metadata.id, metadata.description);
+        if !required.is_empty() {
+            tooltip.push_str(&format!("\nRequires: {}", required.join(", ")));
+        }
+        tooltip
+    });
+
+    let name_label = gtk::Label::builder()
+        .label(format!("{} {}", installed_mod.display_name(), version))
+        .halign(gtk::Align::Start)
+        .build();
+
+    let details_label = gtk::Label::builder()
+        .label(if authors.is_empty() { installed_mod.file_name.clone() } else { format!("{} - {}", authors, installed_mod.file_name) })
+        .halign(gtk::Align::Start)
+        .css_classes(["dim-label"])
+        .build();
+
+    let text_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .hexpand(true)
+        .build();
+    text_box.append(&name_label);
+    text_box.append(&details_label);
+
+    let enabled_switch = gtk::Switch::builder()
+        .active(installed_mod.enabled)
+        .valign(gtk::Align::Center)
+        .build();
+
+    let instance_name = instance_name.to_string();
+    let file_name = installed_mod.file_name.clone();
+    enabled_switch.connect_state_set(move |switch, enabled| {
+        if let Err(e) = manager::set_mod_enabled(&instance_name, &file_name, enabled) {
+            eprintln!("Failed to change mod {}: {}", file_name, e);
+            return glib::Propagation::Stop;
+        }
+
+        switch.set_state(enabled);
+        glib::Propagation::Proceed
+    });
+
+    let row_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(12)
+        .margin_start(6)
+        .margin_end(6)
+        .margin_top(6)
+        .margin_bottom(6)
+        .build();
+    row_box.append(&icon);
+    row_box.append(&text_box);
+    row_box.append(&enabled_switch);
+
+    gtk::ListBoxRow::builder()
+        .child(&row_box)
+        .activatable(false)
+        .tooltip_text(tooltip.unwrap_or_default())
+        .build()
+}
+
+// Window listing mods of instance
+fn mod_manager_window(app: &gtk::Application, instance_name: &str) {
+    let Some(window) = app.active_window() else { return };
+
+    let mod_list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .build();
+
+    let reload_mods = {
+        let instance_name = instance_name.to_string();
+        let mod_list = mod_list.clone();
+        move || {
+            mod_list.remove_all();
+
+            match manager::list_mods(&instance_name) {
+                Ok(mods) if mods.is_empty() => mod_list.append(&gtk::Label::new(Some("No mods installed"))),
+                Ok(mods) => {
+                    for installed_mod in &mods {
+                        mod_list.append(&mod_row(&instance_name, installed_mod));
+                    }
+                }
+                Err(e) => eprintln!("Failed to list mods: {}", e),
+            }
+        }
+    };
+    reload_mods();
+
+    let scrolled_window = gtk::ScrolledWindow::builder()
+        .child(&mod_list)
+        .vexpand(true)
+        .min_content_height(360)
+        .build();
+
+    let refresh_button = gtk::Button::builder()
+        .label("Refresh")
+        .build();
+    let reload_on_refresh = reload_mods.clone();
+    refresh_button.connect_clicked(move |_| reload_on_refresh());
+
+    let updates_button = gtk::Button::builder()
+        .label("Check for updates")
+        .build();
+
+    let instance_name_updates = instance_name.to_string();
+    updates_button.connect_clicked(clone!(
+        #[weak]
+        app,
+        move |_| mod_updates_window(&app, &instance_name_updates)
+    ));
+
+    // Rollback of newest update
+    let rollback_button = gtk::Button::builder()
+        .label("Undo last update")
+        .sensitive(!updates::update_backups(instance_name).is_empty())
+        .build();
+
+    let instance_name_rollback = instance_name.to_string();
+    rollback_button.connect_clicked(move |button| {
+        let backups = updates::update_backups(&instance_name_rollback);
+        let Some(backup_dir) = backups.first() else { return };
+
+        if let Err(e) = updates::rollback_updates(&instance_name_rollback, backup_dir) {
+            eprintln!("Failed to undo update: {}", e);
+        }
+
+        button.set_sensitive(backups.len() > 1);
+        reload_mods();
+    });
+
+    let buttons_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(12)
+        .halign(gtk::Align::End)
+        .build();
+    buttons_box.append(&rollback_button);
+    buttons_box.append(&updates_button);
+    buttons_box.append(&refresh_button);
+
+    let main_container = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(12)
+        .margin_start(24)
+        .margin_end(24)
+        .margin_top(24)
+        .margin_bottom(24)
+        .build();
+
+    main_container.append(&scrolled_window);
+    main_container.append(&buttons_box);
+
+    let mods_window = gtk::Window::builder()
+        .transient_for(&window)
+        .modal(true)
+        .title(format!("{} mods - Copper Launcher", instance_name))
+        .default_width(560)
+        .default_height(480)
+        .child(&main_container)
+        .build();
+
+    mods_window.present();
+}
+
+// Window listing available mod updates, selected ones are applied together
+fn mod_updates_window(app: &gtk::Application, instance_name: &str) {
+    let Some(window) = app.active_window() else { return };
+
+    let metadata = match instance::instance_metadata(instance_name) {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            eprintln!("Failed to read instance metadata: {}", e);
+            return;
+        }
+    };
+
+    let available_updates = match updates::check_updates(&metadata, &ModrinthClient::new()) {
+        Ok(available_updates) => available_updates,
+        Err(e) => {
+            eprintln!("Failed to check for updates: {}", e);
+            return;
+        }
+    };
+
+    // Checkbox for every update, all selected by default
+    let updates_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(6)
+        .build();
+
+    if available_updates.is_empty() {
+        updates_box.append(&gtk::Label::new(Some("All mods are up to date")));
+    }
+
+    let update_checks: Vec<gtk::CheckButton> = available_updates
+        .iter()
+        .map(|update| {
+            let check = gtk::CheckButton::with_label(&format!(
+                "{}: {} → {}",
+                update.file_name, update.current_version, update.new_version.version_number
+            ));
+            check.set_active(true);
+            updates_box.append(&check);
+            check
+        })
+        .collect();
+
+    let scrolled_window = gtk::ScrolledWindow::builder()
+        .child(&updates_box)
+        .vexpand(true)
+        .min_content_height(320)
+        .build();
+
+    let apply_button = gtk::Button::builder()
+        .label("Update selected")
+        .halign(gtk::Align::End)
+        .sensitive(!available_updates.is_empty())
+        .build();
+
+    let main_container = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(12)
+        .margin_start(24)
+        .margin_end(24)
+        .margin_top(24)
+        .margin_bottom(24)
+        .build();
+
+    main_container.append(&scrolled_window);
+    main_container.append(&apply_button);
+
+    let updates_window = gtk::Window::builder()
+        .transient_for(&window)
+        .modal(true)
+        .title(format!("{} mod updates - Copper Launcher", instance_name))
+        .default_width(560)
+        .child(&main_container)
+        .build();
+
+    let instance_name = instance_name.to_string();
+    apply_button.connect_clicked(clone!(
+        #[weak]
+        updates_window,
+        move |_| {
+            let selected: Vec<updates::ModUpdate> = available_updates
+                .iter()
+                .zip(&update_checks)
+                .filter(|(_, check)| check.is_active())
+                .map(|(update, _)| update.clone())
+                .collect();
+
+            if selected.is_empty() {
+                println!("No updates selected");
+                return;
+            }
+
+            if let Err(e) = updates::apply_updates(&instance_name, &selected) {
+                eprintln!("Failed to update mods: {}", e);
+                return;
+            }
+
+            updates_window.close();
+        }
+    ));
+
+    updates_window.present();
+}
+
+// Row of Modrinth search results - title, author, description and install button
+fn search_result_row(title: &str, author: &str, description: &str, downloads: u64, install: impl Fn(&gtk::Button) + 'static) -> gtk::ListBoxRow {
+    let title_label = gtk::Label::builder()
+        .label(format!("{} by {}", title, author))
+        .halign(gtk::Align::Start)
+        .build();
+
+    let description_label = gtk::Label::builder()
+        .label(description)
+        .halign(gtk::Align::Start)
+        .wrap(true)
+        .xalign(0.0)
+        .css_classes(["dim-label"])
+        .build();
+
+    let downloads_label = gtk::Label::builder()
+        .label(format!("{} downloads", downloads))
+        .halign(gtk::Align::Start)
+        .css_classes(["dim-label"])
+        .build();
+
+    let text_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .hexpand(true)
+        .build();
+    text_box.append(&title_label);
+    text_box.append(&description_label);
+    text_box.append(&downloads_label);
+
+    let install_button = gtk::Button::builder()
+        .label("Install")
+        .valign(gtk::Align::Center)
+        .build();
+    install_button.connect_clicked(install);
+
+    let row_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(12)
+        .margin_start(6)
+        .margin_end(6)
+        .margin_top(6)
+        .margin_bottom(6)
+        .build();
+    row_box.append(&text_box);
+    row_box.append(&install_button);
+
+    gtk::ListBoxRow::builder()
+        .child(&row_box)
+        .activatable(false)
+        .build()
+}
+
+// Window for searching Modrinth and installing projects into instance
+// Results are filtered by instance's minecraft version and mod loader
+fn modrinth_browser_window(app: &gtk::Application, instance_name: &str) {
+    let Some(window) = app.active_window() else { return };
+
+    let metadata = match instance::instance_metadata(instance_name) {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            eprintln!("Failed to read instance metadata: {}", e);
+            return;
+        }
+    };
+
+    // What to search for
+    let type_dropdown = gtk::DropDown::from_strings(&ProjectType::ALL.map(|t| t.label()));
+        type_dropdown.set_selected(0);
+
+    let search_entry = gtk::SearchEntry::builder()
+        .placeholder_text("Search Modrinth")
+        .hexpand(true)
+        .build();
+
+    // Data packs are installed into world
+    let worlds = install::world_list(instance_name);
+    let world_dropdown = gtk::DropDown::from_strings(&worlds.iter().map(|w| w.as_str()).collect::<Vec<&str>>());
+        world_dropdown.set_visible(false);
+
+    let search_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(12)
+        .build();
+    search_box.append(&type_dropdown);
+    search_box.append(&search_entry);
+    search_box.append(&world_dropdown);
+
+    let results_list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .build();
+
+    let scrolled_window = gtk::ScrolledWindow::builder()
+        .child(&results_list)
+        .vexpand(true)
+        .min_content_height(420)
+        .build();
+
+    let more_button = gtk::Button::builder()
+        .label("More results")
+        .halign(gtk::Align::Center)
+        .sensitive(false)
+        .build();
+
+    // Loads page of results, offset 0 starts new search
+    let offset = std::rc::Rc::new(std::cell::Cell::new(0u32));
+    let load_results = std::rc::Rc::new(clone!(
+        #[weak]
+        type_dropdown,
+        #[weak]
+        search_entry,
+        #[weak]
+        world_dropdown,
+        #[weak]
+        results_list,
+        #[weak]
+        more_button,
+        #[strong]
+        offset,
+        move |new_search: bool| {
+            let project_type = ProjectType::ALL[type_dropdown.selected() as usize];
+            world_dropdown.set_visible(project_type == ProjectType::Datapack);
+
+            if new_search {
+                offset.set(0);
+                results_list.remove_all();
+            }
+
+            let filter = install::instance_filter(&metadata, project_type);
+            let results = match ModrinthClient::new().search(&search_entry.text(), project_type.api_name(), &filter, offset.get()) {
+                Ok(results) => results,
+                Err(e) => {
+                    eprintln!("Failed to search Modrinth: {}", e);
+                    return;
+                }
+            };
+
+            offset.set(offset.get() + results.hits.len() as u32);
+            more_button.set_sensitive(offset.get() < results.total_hits);
+
+            for hit in &results.hits {
+                let metadata = metadata.clone();
+                let project_id = hit.project_id.clone();
+                let install = clone!(
+                    #[weak]
+                    world_dropdown,
+                    move |button: &gtk::Button| {
+                        let world = selected_string(&world_dropdown);
+                        match install::install_project(&metadata, &project_id, project_type, world.as_deref(), &ModrinthClient::new()) {
+                            Ok(files) => {
+                                println!("Installed {}", files.join(", "));
+                                button.set_label("Installed");
+                                button.set_sensitive(false);
+                            }
+                            Err(e) => eprintln!("Failed to install: {}", e),
+                        }
+                    }
+                );
+
+                results_list.append(&search_result_row(&hit.title, &hit.author, &hit.description, hit.downloads, install));
+            }
+        }
+    ));
+
+    let load_on_search = load_results.clone();
+    search_entry.connect_activate(move |_| load_on_search(true));
+
+    let load_on_type = load_results.clone();
+    type_dropdown.connect_selected_notify(move |_| load_on_type(true));
+
+    let load_more = load_results.clone();
+    more_button.connect_clicked(move |_| load_more(false));
+
+    load_results(true);
+
+    let main_container = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(12)
+        .margin_start(24)
+        .margin_end(24)
+        .margin_top(24)
+        .margin_bottom(24)
+        .build();
+
+    main_container.append(&search_box);
+    main_container.append(&scrolled_window);
+    main_container.append(&more_button);
+
+    let browser_window = gtk::Window::builder()
+        .transient_for(&window)
+        .modal(true)
+        .title(format!("Browse Modrinth for {} - Copper Launcher", instance_name))
+        .default_width(720)
+        .default_height(600)
+        .child(&main_container)
+        .build();
+
+    browser_window.present();
+}
+
+// Window for searching CurseForge and installing projects into instance
+// Needs CurseForge API key from preferences
+fn curseforge_browser_window(app: &gtk::Application, instance_name: &str) {
+    let Some(window) = app.active_window() else { return };
+
+    let metadata = match instance::instance_metadata(instance_name) {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            eprintln!("Failed to read instance metadata: {}", e);
+            return;
+        }
+    };
+
+    let curseforge_config = config::load_config().curseforge;
+    if let Err(e) = CurseForgeClient::from_config(&curseforge_config) {
+        eprintln!("{}", e);
+        preferences_window(app);
+        return;
+    }
+
+    // What to search for
+    let type_dropdown = gtk::DropDown::from_strings(&ProjectType::ALL.map(|t| t.label()));
+        type_dropdown.set_selected(0);
+
+    let search_entry = gtk::SearchEntry::builder()
+        .placeholder_text("Search CurseForge")
+        .hexpand(true)
+        .build();
+
+    // Data packs are installed into world
+    let worlds = install::world_list(instance_name);
+    let world_dropdown = gtk::DropDown::from_strings(&worlds.iter().map(|w| w.as_str()).collect::<Vec<&str>>());
+        world_dropdown.set_visible(false);
+
+    let search_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(12)
+        .build();
+    search_box.append(&type_dropdown);
+    search_box.append(&search_entry);
+    search_box.append(&world_dropdown);
+
+    let results_list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .build();
+
+    let scrolled_window = gtk::ScrolledWindow::builder()
+        .child(&results_list)
+        .vexpand(true)
+        .min_content_height(420)
+        .build();
+
+    let more_button = gtk::Button::builder()
+        .label("More results")
+        .halign(gtk::Align::Center)
+        .sensitive(false)
+        .build();
+
+    // Loads page of results, index 0 starts new search
+    let index = std::rc::Rc::new(std::cell::Cell::new(0u32));
+    let load_results = std::rc::Rc::new(clone!(
+        #[weak]
+        type_dropdown,
+        #[weak]
+        search_entry,
+        #[weak]
+        world_dropdown,
+        #[weak]
+        results_list,
+        #[weak]
+        more_button,
+        #[strong]
+        index,
+        move |new_search: bool| {
+            let project_type = ProjectType::ALL[type_dropdown.selected() as usize];
+            world_dropdown.set_visible(project_type == ProjectType::Datapack);
+
+            if new_search {
+                index.set(0);
+                results_list.remove_all();
+            }
+
+            // Only mods are filtered by loader
+            let loader = match project_type {
+                ProjectType::Mod => metadata.loader.as_ref().map(|l| l.kind),
+                _ => None,
+            };
+
+            let search_result = CurseForgeClient::from_config(&curseforge_config).and_then(|client| {
+                client.search(&search_entry.text(), curseforge_install::class_id(project_type), &metadata.minecraft_version, loader, index.get())
+            });
+            let results = match search_result {
+                Ok(results) => results,
+                Err(e) => {
+                    eprintln!("Failed to search CurseForge: {}", e);
+                    return;
+                }
+            };
+
+            index.set(results.pagination.index + results.pagination.result_count);
+            more_button.set_sensitive(index.get() < results.pagination.total_count);
+
+            for project in &results.data {
+                let metadata = metadata.clone();
+                let curseforge_config = curseforge_config.clone();
+                let mod_id = project.id;
+                let install = clone!(
+                    #[weak]
+                    world_dropdown,
+                    move |button: &gtk::Button| {
+                        let world = selected_string(&world_dropdown);
+                        let install_result = CurseForgeClient::from_config(&curseforge_config).and_then(|client| {
+                            curseforge_install::install_project(&metadata, mod_id, project_type, world.as_deref(), &client)
+                        });
+
+                        match install_result {
+                            Ok(files) => {
+                                println!("Installed {}", files.join(", "));
+                                button.set_label("Installed");
+                                button.set_sensitive(false);
+                            }
+                            Err(e) => eprintln!("Failed to install: {}", e),
+                        }
+                    }
+                );
+
+                let author = project.authors.first().map(|a| a.name.as_str()).unwrap_or_default();
+                results_list.append(&search_result_row(&project.name, author, &project.summary, project.download_count, install));
+            }
+        }
+    ));
+
+    let load_on_search = load_results.clone();
+    search_entry.connect_activate(move |_| load_on_search(true));
+
+    let load_on_type = load_results.clone();
+    type_dropdown.connect_selected_notify(move |_| load_on_type(true));
+
+    let load_more = load_results.clone();
+    more_button.connect_clicked(move |_| load_more(false));
+
+    load_results(true);
+
+    let main_container = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(12)
+        .margin_start(24)
+        .margin_end(24)
+        .margin_top(24)
+        .margin_bottom(24)
+        .build();
+
+    main_container.append(&search_box);
+    main_container.append(&scrolled_window);
+    main_container.append(&more_button);
+
+    let browser_window = gtk::Window::builder()
+        .transient_for(&window)
+        .modal(true)
+        .title(format!("Browse CurseForge for {} - Copper Launcher", instance_name))
+        .default_width(720)
+        .default_height(600)
+        .child(&main_container)
+        .build();
+
+    browser_window.present();
+}
+
+// Rebuilds rows of accounts window, main window selector is refreshed too
+fn fill_accounts_list(app: &gtk::Application, accounts_list: &gtk::ListBox) {
+    accounts_list.remove_all();
+    app.activate_action("refresh_accounts", None);
+
+    let list = match accounts::list() {
+        Ok(list) => list,
+        Err(e) => {
+            eprintln!("Failed to load accounts: {}", e);
+            return;
+        }
+    };
+    let active_id = list.active_account().map(|a| a.id.clone());
+
+    for account in &list.accounts {
+        let is_active = Some(&account.id) == active_id.as_ref();
+
+        let name_label = gtk::Label::builder()
+            .label(account.label())
+            .halign(gtk::Align::Start)
+            .hexpand(true)
+            .build();
+
+        let use_button = gtk::Button::builder()
+            .label(if is_active { "Active" } else { "Use" })
+            .sensitive(!is_active)
+            .build();
+
+        let remove_button = gtk::Button::builder()
+            .label("Remove")
+            .css_classes(["destructive-action"])
+            .build();
+
+        let id = account.id.clone();
+        use_button.connect_clicked(clone!(
+            #[weak]
+            app,
+            #[weak]
+            accounts_list,
+            move |_| {
+                if let Err(e) = accounts::update(|accounts| accounts.set_active(&id)) {
+                    eprintln!("Failed to change account: {}", e);
+                }
+                fill_accounts_list(&app, &accounts_list);
+            }
+        ));
+
+        let id = account.id.clone();
+        remove_button.connect_clicked(clone!(
+            #[weak]
+            app,
+            #[weak]
+            accounts_list,
+            move |_| {
+                if let Err(e) = accounts::update(|accounts| accounts.remove(&id)) {
+                    eprintln!("Failed to remove account: {}", e);
+                }
+                fill_accounts_list(&app, &accounts_list);
+            }
+        ));
+
+        let row_box = gtk::Box::builder()
+            .orientation(gtk::Orientation::Horizontal)
+            .spacing(12)
+            .margin_start(6)
+            .margin_end(6)
+            .margin_top(6)
+            .margin_bottom(6)
+            .build();
+        row_box.append(&name_label);
+        row_box.append(&use_button);
+        row_box.append(&remove_button);
+
+        accounts_list.append(&gtk::ListBoxRow::builder().child(&row_box).activatable(false).build());
+    }
+}
+
+// Window for adding, removing and choosing accounts
+fn accounts_window(app: &gtk::Application) {
+    let Some(window) = app.active_window() else { return };
+
+    let accounts_list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .build();
+
+    let accounts_scroll = gtk::ScrolledWindow::builder()
+        .child(&accounts_list)
+        .min_content_height(240)
+        .vexpand(true)
+        .build();
+
+    // Microsoft account
+    let microsoft_button = gtk::Button::builder()
+        .label("Add Microsoft account")
+        .build();
+
+    // Account on custom auth server
+    let authlib_injector_button = gtk::Button::builder()
+        .label("Add authlib-injector account")
+        .build();
+
+    // Offline account
+    let offline_field = gtk::Entry::builder()
+        .placeholder_text("Player")
+        .hexpand(true)
+        .build();
+
+    let offline_button = gtk::Button::builder()
+        .label("Add offline account")
+        .sensitive(false)
+        .build();
+
+    // Names follow Minecraft rules, reason is shown as tooltip
+    offline_field.connect_changed(clone!(
+        #[weak]
+        offline_button,
+        move |entry| {
+            let valid = offline::validate_name(entry.text().trim());
+            offline_button.set_sensitive(valid.is_ok());
+            entry.set_tooltip_text(valid.err().as_deref());
+        }
+    ));
+
+    let add_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(12)
+        .build();
+    add_box.append(&offline_field);
+    add_box.append(&offline_button);
+    add_box.append(&microsoft_button);
+    add_box.append(&authlib_injector_button);
+
+    let main_container = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(12)
+        .margin_start(24)
+        .margin_end(24)
+        .margin_top(24)
+        .margin_bottom(24)
+        .build();
+
+    main_container.append(&accounts_scroll);
+    main_container.append(&add_box);
+
+    let accounts_window = gtk::Window::builder()
+        .transient_for(&window)
+        .modal(true)
+        .title("Accounts - Copper Launcher")
+        .default_width(560)
+        .child(&main_container)
+        .build();
+
+    microsoft_button.connect_clicked(clone!(
+        #[weak]
+        app,
+        #[weak]
+        accounts_window,
+        #[weak]
+        accounts_list,
+        move |_| {
+            microsoft_login_window(accounts_window.upcast_ref(), clone!(
+                #[weak]
+                app,
+                #[weak]
+                accounts_list,
+                move |microsoft_account: MicrosoftAccount| {
+                    if let Err(e) = accounts::update(|accounts| accounts.add_microsoft(microsoft_account)) {
+                        eprintln!("Failed to save account: {}", e);
+                    }
+                    fill_accounts_list(&app, &accounts_list);
+                }
+            ));
+        }
+    ));
+
+    authlib_injector_button.connect_clicked(clone!(
+        #[weak]
+        app,
+        #[weak]
+        accounts_window,
+        #[weak]
+        accounts_list,
+        move |_| {
+            authlib_injector_login_window(accounts_window.upcast_ref(), clone!(
+                #[weak]
+                app,
+                #[weak]
+                accounts_list,
+                move |session: YggdrasilSession| {
+                    if let Err(e) = accounts::update(|accounts| accounts.add_authlib_injector(session)) {
+                        eprintln!("Failed to save account: {}", e);
+                    }
+                    fill_accounts_list(&app, &accounts_list);
+                }
+            ));
+        }
+    ));
+
+    offline_button.connect_clicked(clone!(
+        #[weak]
+        app,
+        #[weak]
+        accounts_list,
+        #[weak]
+        offline_field,
+        move |_| {
+            let username = offline_field.text().trim().to_string();
+
+            match accounts::update(|accounts| accounts.add_offline(&username)) {
+                Ok(Ok(_)) => offline_field.set_text(""),
+                Ok(Err(e)) => eprintln!("Invalid name: {}", e),
+                Err(e) => eprintln!("Failed to save account: {}", e),
+            }
+            fill_accounts_list(&app, &accounts_list);
+        }
+    ));
+
+    fill_accounts_list(app, &accounts_list);
+
+    accounts_window.present();
+}
+
+// Microsoft login window, shows device code and waits until user logs in browser
+fn microsoft_login_window(window: &gtk::Window, on_login: impl Fn(MicrosoftAccount) + 'static) {
+    let microsoft_config = config::load_config().microsoft;
+
+    let device_code = match MicrosoftAuth::new(&microsoft_config).and_then(|auth| auth.request_device_code()) {
+        Ok(device_code) => device_code,
+        Err(e) => {
+            eprintln!("Failed to start Microsoft login: {}", e);
+            return;
+        }
+    };
+
+    let info_label = gtk::Label::builder()
+        .label(format!("Open {} and enter this code:", device_code.verification_uri))
+        .wrap(true)
+        .build();
+
+    let code_label = gtk::Label::builder()
+        .label(&device_code.user_code)
+        .selectable(true)
+        .css_classes(["title-1"])
+        .build();
+
+    let open_button = gtk::Button::builder()
+        .label("Open login page")
+        .halign(gtk::Align::Center)
+        .build();
+
+    let status_label = gtk::Label::builder()
+        .label("Waiting for login...")
+        .css_classes(["dim-label"])
+        .wrap(true)
+        .build();
+
+    let main_container = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(12)
+        .margin_start(24)
+        .margin_end(24)
+        .margin_top(24)
+        .margin_bottom(24)
+        .build();
+
+    main_container.append(&info_label);
+    main_container.append(&code_label);
+    main_container.append(&open_button);
+    main_container.append(&status_label);
+
+    let login_window = gtk::Window::builder()
+        .transient_for(window)
+        .modal(true)
+        .title("Log in with Microsoft - Copper Launcher")
+        .default_width(420)
+        .resizable(false)
+        .child(&main_container)
+        .build();
+
+    let verification_uri = device_code.verification_uri.clone();
+    open_button.connect_clicked(clone!(
+        #[weak]
+        login_window,
+        move |_| {
+            gtk::UriLauncher::new(&verification_uri).launch(Some(&login_window), None::<&gio::Cancellable>, |result| {
+                if let Err(e) = result {
+                    eprintln!("Failed to open URL: {}", e);
+                }
+            });
+        }
+    ));
+
+    // Waiting takes minutes, so it runs outside of UI thread
+    glib::MainContext::default().spawn_local(clone!(
+        #[weak]
+        login_window,
+        #[weak]
+        status_label,
+        async move {
+            let login = gio::spawn_blocking(move || {
+                MicrosoftAuth::new(&microsoft_config)
+                    .and_then(|auth| {
+                        let microsoft_token = auth.wait_for_token(&device_code)?;
+                        auth.login(&microsoft_token)
+                    })
+                    .map_err(|e| e.to_string())
+            }).await;
+
+            match login {
+                Ok(Ok(account)) => {
+                    on_login(account);
+                    login_window.close();
+                }
+                Ok(Err(e)) => {
+                    eprintln!("Microsoft login failed: {}", e);
+                    status_label.set_label(&e);
+                }
+                Err(_) => eprintln!("Microsoft login thread panicked"),
+            }
+        }
+    ));
+
+    login_window.present();
+}
+
+// Login window for Yggdrasil auth servers used with authlib-injector
+fn authlib_injector_login_window(window: &gtk::Window, on_login: impl Fn(YggdrasilSession) + 'static) {
+    let server_title = gtk::Label::default();
+        server_title.set_markup("Auth server");
+        server_title.set_halign(gtk::Align::Start);
+
+    let server_field = gtk::Entry::builder()
+        .placeholder_text("https://example.com/api/yggdrasil")
+        .build();
+
+    let username_title = gtk::Label::default();
+        username_title.set_markup("Username or email");
+        username_title.set_halign(gtk::Align::Start);
+
+    let username_field = gtk::Entry::new();
+
+    let password_title = gtk::Label::default();
+        password_title.set_markup("Password");
+        password_title.set_halign(gtk::Align::Start);
+
+    let password_field = gtk::PasswordEntry::builder()
+        .show_peek_icon(true)
+        .build();
+
+    let status_label = gtk::Label::builder()
+        .css_classes(["dim-label"])
+        .wrap(true)
+        .build();
+
+    let login_button = gtk::Button::builder()
+        .label("Log in")
+        .halign(gtk::Align::End)
+        .css_classes(["suggested-action"])
+        .build();
+
+    let main_container = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(12)
+        .margin_start(24)
+        .margin_end(24)
+        .margin_top(24)
+        .margin_bottom(24)
+        .build();
+
+    main_container.append(&server_title);
+    main_container.append(&server_field);
+    main_container.append(&username_title);
+    main_container.append(&username_field);
+    main_container.append(&password_title);
+    main_container.append(&password_field);
+    main_container.append(&status_label);
+    main_container.append(&login_button);
+
+    let login_window = gtk::Window::builder()
+        .transient_for(window)
+        .modal(true)
+        .title("Log in with authlib-injector - Copper Launcher")
+        .default_width(420)
+        .resizable(false)
+        .child(&main_container)
+        .build();
 
-use std::path::PathBuf;
-use gtk::prelude::*;
-use gtk::{Application, ApplicationWindow, glib, gdk, StringList};
-use glib::{clone};
+    let on_login = Rc::new(on_login);
+    login_button.connect_clicked(clone!(
+        #[weak]
+        login_window,
+        #[weak]
+        server_field,
+        #[weak]
+        username_field,
+        #[weak]
+        password_field,
+        #[weak]
+        status_label,
+        move |button| {
+            let server = server_field.text().trim().to_string();
+            let username = username_field.text().trim().to_string();
+            let password = password_field.text().to_string();
+            if server.is_empty() || username.is_empty() {
+                status_label.set_label("Fill in auth server and username");
+                return;
+            }
 
+            button.set_sensitive(false);
+            status_label.set_label("Logging in...");
 
-// App logo, currently just copper ingot item lol
-static LOGO_SVG: &[u8] = include_bytes!("Copper_Ingot.svg");
+            glib::MainContext::default().spawn_local(clone!(
+                #[weak]
+                login_window,
+                #[weak]
+                button,
+                #[weak]
+                status_label,
+                #[strong]
+                on_login,
+                async move {
+                    let login = gio::spawn_blocking(move || {
+                        authlib_injector::resolve_api_root(&server)
+                            .and_then(|api_root| authlib_injector::authenticate(&api_root, &username, &password))
+                            .map_err(|e| e.to_string())
+                    }).await;
+
+                    match login {
+                        Ok(Ok(session)) => {
+                            on_login(session);
+                            login_window.close();
+                        }
+                        Ok(Err(e)) => {
+                            eprintln!("authlib-injector login failed: {}", e);
+                            status_label.set_label(&e);
+                            button.set_sensitive(true);
+                        }
+                        Err(_) => eprintln!("authlib-injector login thread panicked"),
+                    }
+                }
+            ));
+        }
+    ));
 
-pub fn build_ui(app: &Application) {
-    // Play button
-    let play_button = gtk::Button::builder()
-        .label("Launch game")
-        .build();
+    login_window.present();
+}
+
+// Pixels of PNG image as premultiplied BGRA, with width and height
+fn texture_pixels(png: &[u8]) -> Option<(Vec<u8>, usize, usize)> {
+    let texture = gdk::Texture::from_bytes(&glib::Bytes::from(png)).ok()?;
+    let (width, height) = (texture.width() as usize, texture.height() as usize);
+
+    let mut pixels = vec![0; width * height * 4];
+    texture.download(&mut pixels, width * 4);
+
+    Some((pixels, width, height))
+}
+
+fn pixels_texture(pixels: Vec<u8>, width: usize, height: usize) -> gdk::MemoryTexture {
+    gdk::MemoryTexture::new(
+        width as i32,
+        height as i32,
+        gdk::MemoryFormat::B8g8r8a8Premultiplied,
+        &glib::Bytes::from_owned(pixels),
+        width * 4,
+    )
+}
+
+// Preview size, one skin pixel is this many screen pixels
+const SKIN_PREVIEW_SCALE: usize = 8;
+
+// Profile after change, with skin and cape textures
+type ProfileView = (PlayerProfile, Option<Vec<u8>>, Option<Vec<u8>>);
+
+// Widgets of skins window, shared by all of its actions
+#[derive(Clone)]
+struct SkinsPage {
+    account_id: String,
+    name_label: gtk::Label,
+    skin_picture: gtk::Picture,
+    cape_picture: gtk::Picture,
+    model_dropdown: gtk::DropDown,
+    cape_dropdown: gtk::DropDown,
+    cape_options: StringList,
+    // Index 0 is "No cape"
+    cape_ids: Rc<RefCell<Vec<String>>>,
+    buttons: gtk::Box,
+    status_label: gtk::Label,
+}
+
+impl SkinsPage {
+    // Runs profile request outside of UI thread and shows profile it returns
+    fn run(&self, status: &str, change: impl FnOnce(&dyn ProfileApi) -> Result<PlayerProfile, Box<dyn std::error::Error>> + Send + 'static) {
+        let page = self.clone();
+        let account_id = self.account_id.clone();
+        page.buttons.set_sensitive(false);
+        page.status_label.set_label(status);
+
+        glib::MainContext::default().spawn_local(async move {
+            let result = gio::spawn_blocking(move || {
+                (|| -> Result<ProfileView, Box<dyn std::error::Error>> {
+                    let access_token = accounts::launch_account(&account_id)?.access_token;
+                    let client = ProfileClient::new(&config::load_config().microsoft, &access_token);
+
+                    let profile = change(&client)?;
+                    let skin = profile.active_skin().map(|s| client.texture(&s.url)).transpose()?;
+                    let cape = profile.active_cape().map(|c| client.texture(&c.url)).transpose()?;
+                    Ok((profile, skin, cape))
+                })().map_err(|e| e.to_string())
+            }).await;
+
+            page.buttons.set_sensitive(true);
+            match result {
+                Ok(Ok(view)) => {
+                    page.status_label.set_label("");
+                    page.show(view);
+                }
+                Ok(Err(e)) => {
+                    eprintln!("Profile request failed: {}", e);
+                    page.status_label.set_label(&e);
+                }
+                Err(_) => eprintln!("Profile request thread panicked"),
+            }
+        });
+    }
+
+    fn show(&self, (profile, skin, cape): ProfileView) {
+        self.name_label.set_label(&profile.name);
+
+        let model = profile.active_skin().map(|s| s.model()).unwrap_or(SkinModel::Classic);
+        self.model_dropdown.set_selected(if model == SkinModel::Slim { 1 } else { 0 });
+
+        let skin_texture = skin.as_deref().and_then(texture_pixels).map(|(pixels, width, height)| {
+            let front = skins::render_front(&pixels, width, height, model, SKIN_PREVIEW_SCALE);
+            pixels_texture(front, skins::FRONT_WIDTH * SKIN_PREVIEW_SCALE, skins::FRONT_HEIGHT * SKIN_PREVIEW_SCALE)
+        });
+        self.skin_picture.set_paintable(skin_texture.as_ref());
 
-        play_button.connect_clicked(|_| {
-            println!("Play button: Click!")
+        let cape_texture = cape.as_deref().and_then(texture_pixels).map(|(pixels, width, _)| {
+            pixels_texture(skins::render_cape(&pixels, width, SKIN_PREVIEW_SCALE), 10 * SKIN_PREVIEW_SCALE, 16 * SKIN_PREVIEW_SCALE)
         });
+        self.cape_picture.set_paintable(cape_texture.as_ref());
+
+        // Owned capes
+        let mut cape_ids = self.cape_ids.borrow_mut();
+        cape_ids.clear();
+        cape_ids.push(String::new());
+        self.cape_options.splice(0, self.cape_options.n_items(), &["No cape"]);
+        for owned_cape in &profile.capes {
+            cape_ids.push(owned_cape.id.clone());
+            self.cape_options.append(if owned_cape.alias.is_empty() { &owned_cape.id } else { &owned_cape.alias });
+        }
 
-    // Username field   
-    let username_input_field_title = gtk::Label::default();
-    username_input_field_title.set_markup("Enter your username");
+        let active = profile.active_cape().and_then(|c| cape_ids.iter().position(|id| *id == c.id)).unwrap_or(0);
+        self.cape_dropdown.set_selected(active as u32);
+    }
+}
 
-    let username_input_field = gtk::Entry::new();
-    let username_box = gtk::Box::builder()
-        .orientation(gtk::Orientation::Vertical)
-        .spacing(6)
+// Skins and capes window of active account, only Microsoft accounts have them
+fn skins_window(app: &gtk::Application) {
+    let Some(window) = app.active_window() else { return };
+
+    let account = accounts::list().ok().and_then(|accounts| accounts.active_account().cloned());
+    let Some(account) = account.filter(|a| a.kind == AccountKind::Microsoft) else {
+        gtk::AlertDialog::builder()
+            .modal(true)
+            .message("No Microsoft account selected")
+            .detail("Skins and capes can only be changed for Microsoft accounts.")
+            .build()
+            .show(Some(&window));
+        return;
+    };
+
+    let name_label = gtk::Label::builder()
+        .label(&account.username)
+        .css_classes(["title-2"])
         .build();
-    username_box.append(&username_input_field_title);
-    username_box.append(&username_input_field);
 
-    username_input_field.set_placeholder_text(Some("Player"));
+    let skin_picture = gtk::Picture::builder()
+        .can_shrink(false)
+        .build();
 
-    // Label
-    let label = gtk::Label::builder()
-        .label("Copper Launcher")
-        .vexpand(true)
+    let cape_picture = gtk::Picture::builder()
+        .can_shrink(false)
+        .valign(gtk::Align::Start)
+        .build();
+
+    let preview_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(24)
         .halign(gtk::Align::Center)
-        .valign(gtk::Align::Center)
-        .css_classes(["large-title"])
         .build();
+    preview_box.append(&skin_picture);
+    preview_box.append(&cape_picture);
 
-    // Instance selector
-    let instance_dropdown_options = StringList::new(&["Instance 0", "Instance 1"]);
-        
-    let instance_dropdown = gtk::DropDown::new(Some(instance_dropdown_options), None::<gtk::Expression>);
-        instance_dropdown.set_selected(0);
+    // Skin
+    let model_dropdown = gtk::DropDown::from_strings(&["Classic", "Slim"]);
 
-            instance_dropdown.connect_selected_notify(|dd| {
-                println!("Instance selector. Selected: {}", dd.selected())
-            });
+    let upload_button = gtk::Button::builder()
+        .label("Upload skin")
+        .build();
+
+    let skin_row = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(6)
+        .build();
+    skin_row.append(&model_dropdown);
+    skin_row.append(&upload_button);
+
+    // Cape
+    let cape_options = StringList::new(&["No cape"]);
+    let cape_dropdown = gtk::DropDown::new(Some(cape_options.clone()), None::<gtk::Expression>);
+
+    let cape_button = gtk::Button::builder()
+        .label("Apply cape")
+        .build();
+
+    let cape_row = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(6)
+        .build();
+    cape_row.append(&cape_dropdown);
+    cape_row.append(&cape_button);
+
+    let buttons = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(12)
+        .halign(gtk::Align::Center)
+        .build();
+    buttons.append(&skin_row);
+    buttons.append(&cape_row);
+
+    let status_label = gtk::Label::builder()
+        .css_classes(["dim-label"])
+        .wrap(true)
+        .build();
 
-    // Containers - For nice layout
     let main_container = gtk::Box::builder()
         .orientation(gtk::Orientation::Vertical)
-        .halign(gtk::Align::Fill)
-        .valign(gtk::Align::Fill)
-        .hexpand(true)
-        .vexpand(true)
+        .spacing(12)
+        .margin_start(24)
+        .margin_end(24)
+        .margin_top(24)
+        .margin_bottom(24)
         .build();
 
-    let pushdownpls = gtk::Box::new(gtk::Orientation::Vertical, 0);
-    pushdownpls.set_vexpand(true);
+    main_container.append(&name_label);
+    main_container.append(&preview_box);
+    main_container.append(&buttons);
+    main_container.append(&status_label);
 
-    let bottom_container = gtk::Box::builder()
-        .orientation(gtk::Orientation::Horizontal)
-        .halign(gtk::Align::Fill)
-        .spacing(24)
+    let skins_window = gtk::Window::builder()
+        .transient_for(&window)
+        .modal(true)
+        .title("Skins and capes - Copper Launcher")
+        .default_width(360)
+        .child(&main_container)
+        .build();
+
+    let page = SkinsPage {
+        account_id: account.id.clone(),
+        name_label,
+        skin_picture,
+        cape_picture,
+        model_dropdown,
+        cape_dropdown,
+        cape_options,
+        cape_ids: Rc::new(RefCell::new(vec![String::new()])),
+        buttons,
+        status_label,
+    };
+
+    upload_button.connect_clicked(clone!(
+        #[weak]
+        skins_window,
+        #[strong]
+        page,
+        move |_| {
+            let filter = gtk::FileFilter::new();
+                filter.set_name(Some("Skin (64x64 PNG)"));
+                filter.add_suffix("png");
+
+            let filters = gio::ListStore::new::<gtk::FileFilter>();
+                filters.append(&filter);
+
+            let dialog = gtk::FileDialog::builder()
+                .title("Upload skin")
+                .modal(true)
+                .filters(&filters)
+                .build();
+
+            dialog.open(
+                Some(&skins_window),
+                None::<&gio::Cancellable>,
+                clone!(
+                    #[strong]
+                    page,
+                    move |result| {
+                        let Ok(file) = result else { return };
+                        let Some(skin_path) = file.path() else { return };
+
+                        let png = match std::fs::read(&skin_path) {
+                            Ok(png) => png,
+                            Err(e) => {
+                                page.status_label.set_label(&format!("Failed to read skin: {}", e));
+                                return;
+                            }
+                        };
+                        if let Err(e) = skins::validate_skin(&png) {
+                            page.status_label.set_label(&e);
+                            return;
+                        }
+
+                        let model = if page.model_dropdown.selected() == 1 { SkinModel::Slim } else { SkinModel::Classic };
+                        page.run("Uploading skin...", move |client| client.upload_skin(&png, model));
+                    }
+                )
+            );
+        }
+    ));
+
+    cape_button.connect_clicked(clone!(
+        #[strong]
+        page,
+        move |_| {
+            let cape_id = page.cape_ids.borrow().get(page.cape_dropdown.selected() as usize).cloned().unwrap_or_default();
+
+            if cape_id.is_empty() {
+                page.run("Hiding cape...", |client| client.hide_cape());
+            } else {
+                page.run("Changing cape...", move |client| client.show_cape(&cape_id));
+            }
+        }
+    ));
+
+    page.run("Loading profile...", |client| client.profile());
+
+    skins_window.present();
+}
+
+// Launcher preferences window
+fn preferences_window(app: &gtk::Application) {
+    let Some(window) = app.active_window() else { return };
+
+    let launcher_config = config::load_config();
+
+    // CurseForge
+    let curseforge_title = gtk::Label::builder()
+        .label("CurseForge")
+        .halign(gtk::Align::Start)
+        .css_classes(["heading"])
+        .build();
+
+    let api_key_title = gtk::Label::default();
+        api_key_title.set_markup("API key (from console.curseforge.com)");
+        api_key_title.set_halign(gtk::Align::Start);
+
+    let api_key_field = gtk::PasswordEntry::builder()
+        .show_peek_icon(true)
+        .text(launcher_config.curseforge.api_key.clone().unwrap_or_default())
+        .build();
+
+    let api_url_title = gtk::Label::default();
+        api_url_title.set_markup("API URL");
+        api_url_title.set_halign(gtk::Align::Start);
+
+    let api_url_field = gtk::Entry::builder()
+        .text(&launcher_config.curseforge.api_url)
+        .build();
+
+    // Microsoft
+    let microsoft_title = gtk::Label::builder()
+        .label("Microsoft")
+        .halign(gtk::Align::Start)
+        .css_classes(["heading"])
+        .build();
+
+    let client_id_title = gtk::Label::default();
+        client_id_title.set_markup("Azure application client ID");
+        client_id_title.set_halign(gtk::Align::Start);
+
+    let client_id_field = gtk::Entry::builder()
+        .text(&launcher_config.microsoft.client_id)
+        .build();
+
+    // Accounts, Secret Service is only offered when launcher is built with it
+    let secret_service_check = gtk::CheckButton::builder()
+        .label("Keep account tokens in Secret Service")
+        .active(launcher_config.secret_service)
+        .visible(cfg!(feature = "secret-service"))
+        .build();
+
+    // Defaults of instance settings
+    let instance_defaults_title = gtk::Label::builder()
+        .label("Instance defaults")
+        .halign(gtk::Align::Start)
+        .css_classes(["heading"])
+        .build();
+
+    let defaults_editor = SettingsEditor::new(&launcher_config.instance_defaults, &instance::InstanceSettings::default());
+
+    let save_button = gtk::Button::builder()
+        .label("Save")
+        .halign(gtk::Align::End)
+        .build();
+
+    let main_container = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(12)
         .margin_start(24)
         .margin_end(24)
+        .margin_top(24)
         .margin_bottom(24)
         .build();
 
-    bottom_container.append(&username_box);
+    main_container.append(&curseforge_title);
+    main_container.append(&api_key_title);
+    main_container.append(&api_key_field);
+    main_container.append(&api_url_title);
+    main_container.append(&api_url_field);
+    main_container.append(&microsoft_title);
+    main_container.append(&client_id_title);
+    main_container.append(&client_id_field);
+    main_container.append(&secret_service_check);
+    main_container.append(&instance_defaults_title);
+    main_container.append(&defaults_editor.grid);
+    main_container.append(&save_button);
+
+    let preferences_window = gtk::Window::builder()
+        .transient_for(&window)
+        .modal(true)
+        .title("Preferences - Copper Launcher")
+        .default_width(480)
+        .resizable(false)
+        .child(&main_container)
+        .build();
 
-    let pushbuttonrightpls = gtk::Box::new(gtk::Orientation::Horizontal, 0);
-    pushbuttonrightpls.set_hexpand(true);
-    
-    bottom_container.append(&pushbuttonrightpls);
-    bottom_container.append(&instance_dropdown);
-    bottom_container.append(&play_button);
+    save_button.connect_clicked(clone!(
+        #[weak]
+        preferences_window,
+        #[weak]
+        api_key_field,
+        #[weak]
+        api_url_field,
+        #[weak]
+        client_id_field,
+        #[weak]
+        secret_service_check,
+        move |_| {
+            let mut launcher_config = launcher_config.clone();
+
+            let api_key = api_key_field.text().trim().to_string();
+            launcher_config.curseforge.api_key = if api_key.is_empty() { None } else { Some(api_key) };
+
+            let api_url = api_url_field.text().trim().to_string();
+            if !api_url.is_empty() {
+                launcher_config.curseforge.api_url = api_url;
+            }
 
-    main_container.append(&label);
-    main_container.append(&pushdownpls);
-    main_container.append(&bottom_container);
+            launcher_config.microsoft.client_id = client_id_field.text().trim().to_string();
 
-    // Create main window
-    let window = ApplicationWindow::builder()
-        .application(app)
-        .title("Copper Launcher")
-        .default_width(1280)
-        .default_height(720)
+            launcher_config.instance_defaults = match defaults_editor.settings() {
+                Ok(settings) => settings,
+                Err(e) => {
+                    eprintln!("Invalid instance defaults: {}", e);
+                    return;
+                }
+            };
+
+            // Tokens move to new store before it is used
+            if secret_service_check.is_active() != launcher_config.secret_service {
+                if let Err(e) = accounts::move_secrets(secret_service_check.is_active()) {
+                    eprintln!("Failed to move account tokens: {}", e);
+                    return;
+                }
+                launcher_config.secret_service = secret_service_check.is_active();
+            }
+
+            match config::save_config(&launcher_config) {
+                Ok(_) => preferences_window.close(),
+                Err(e) => eprintln!("Failed to save preferences: {}", e),
+            }
+        }
+    ));
+
+    preferences_window.present();
+}
+
+// Gets currently selected string from dropdown
+fn selected_string(dropdown: &gtk::DropDown) -> Option<String> {
+    dropdown
+        .selected_item()
+        .and_then(|item| item.downcast::<gtk::StringObject>().ok())
+        .map(|item| item.string().to_string())
+}
+
+// Window for choosing what gets exported
+fn export_instance_window(app: &gtk::Application, instance_name: &str) {
+    let Some(window) = app.active_window() else { return };
+
+    let defaults = instance::ExportOptions::default();
+
+    // Checkbox for every exportable entry
+    let entries_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(6)
+        .build();
+
+    let entry_checks: Vec<(String, gtk::CheckButton)> = defaults.include
+        .iter()
+        .map(|entry| {
+            let check = gtk::CheckButton::with_label(entry);
+            check.set_active(true);
+            entries_box.append(&check);
+            (entry.clone(), check)
+        })
+        .collect();
+
+    let saves_check = gtk::CheckButton::with_label("saves");
+    entries_box.append(&saves_check);
+
+    // Excluded paths
+    let exclude_title = gtk::Label::default();
+        exclude_title.set_markup("Exclude (comma separated, e.g. config/secret.json)");
+
+    let exclude_field = gtk::Entry::new();
+
+    // Archive format
+    let format_dropdown = gtk::DropDown::from_strings(&["Copper Launcher archive (.zip)", "Modrinth modpack (.mrpack)"]);
+        format_dropdown.set_selected(0);
+
+    // Modpack version, only used by .mrpack, defaults to export date
+    let version_field = gtk::Entry::builder()
+        .placeholder_text("Modpack version")
+        .text(glib::DateTime::now_local().and_then(|now| now.format("%Y.%m.%d")).map(|d| d.to_string()).unwrap_or_default())
+        .sensitive(false)
+        .build();
+
+    format_dropdown.connect_selected_notify(clone!(
+        #[weak]
+        version_field,
+        move |format_dropdown| version_field.set_sensitive(format_dropdown.selected() == 1)
+    ));
+
+    let export_button = gtk::Button::builder()
+        .label("Export")
+        .halign(gtk::Align::End)
+        .build();
+
+    let main_container = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(12)
+        .margin_start(24)
+        .margin_end(24)
+        .margin_top(24)
+        .margin_bottom(24)
+        .build();
+
+    main_container.append(&entries_box);
+    main_container.append(&exclude_title);
+    main_container.append(&exclude_field);
+    main_container.append(&format_dropdown);
+    main_container.append(&version_field);
+    main_container.append(&export_button);
+
+    let export_window = gtk::Window::builder()
+        .transient_for(&window)
+        .modal(true)
+        .title(format!("Export {} - Copper Launcher", instance_name))
         .resizable(false)
-        .show_menubar(true)
         .child(&main_container)
         .build();
 
-    // Present window
-    window.present();
+    let instance_name = instance_name.to_string();
+    export_button.connect_clicked(clone!(
+        #[weak]
+        export_window,
+        #[weak]
+        saves_check,
+        #[weak]
+        exclude_field,
+        #[weak]
+        format_dropdown,
+        #[weak]
+        version_field,
+        move |_| {
+            let options = instance::ExportOptions {
+                include: entry_checks
+                    .iter()
+                    .filter(|(_, check)| check.is_active())
+                    .map(|(entry, _)| entry.clone())
+                    .collect(),
+                exclude: exclude_field
+                    .text()
+                    .split(',')
+                    .map(|e| e.trim().trim_matches('/').to_string())
+                    .filter(|e| !e.is_empty())
+                    .collect(),
+                include_saves: saves_check.is_active(),
+                keep_settings: false,
+            };
+
+            let as_mrpack = format_dropdown.selected() == 1;
+            let extension = if as_mrpack { "mrpack" } else { "zip" };
+
+            let version_id = version_field.text().trim().to_string();
+            if as_mrpack && version_id.is_empty() {
+                println!("Modpack version is required");
+                return;
+            }
+
+            let dialog = gtk::FileDialog::builder()
+                .title("Export instance")
+                .modal(true)
+                .initial_name(format!("{}.{}", instance_name, extension))
+                .build();
+
+            let instance_name = instance_name.clone();
+            dialog.save(
+                Some(&export_window),
+                None::<&gio::Cancellable>,
+                clone!(
+                    #[weak]
+                    export_window,
+                    move |result| {
+                        let Ok(file) = result else { return };
+                        let Some(archive_path) = file.path() else { return };
+
+                        let export_result = if as_mrpack {
+                            mrpack::mrpack_export(&instance_name, &archive_path, &version_id, &options, &ModrinthClient::new())
+                        } else {
+                            instance::instance_export(&instance_name, &archive_path, &options)
+                        };
+
+                        match export_result {
+                            Ok(_) => export_window.close(),
+                            Err(e) => eprintln!("Failed to export instance: {}", e),
+                        }
+                    }
+                )
+            );
+        }
+    ));
+
+    export_window.present();
+}
+
+// Window listing official launcher profiles that can be imported
+fn official_import_window(app: &gtk::Application) {
+    let Some(window) = app.active_window() else { return };
+
+    let profiles = match official::list_profiles() {
+        Ok(profiles) => profiles,
+        Err(e) => {
+            eprintln!("Failed to read launcher profiles: {}", e);
+            return;
+        }
+    };
+
+    let profiles_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(6)
+        .build();
+
+    // Checkbox for every profile
+    let profile_checks: Vec<(official::LauncherProfile, gtk::CheckButton)> = profiles
+        .into_iter()
+        .map(|profile| {
+            let check = gtk::CheckButton::with_label(&format!("{} ({})", profile.display_name(), profile.last_version_id));
+            profiles_box.append(&check);
+            (profile, check)
+        })
+        .collect();
+
+    let profiles_scroll = gtk::ScrolledWindow::builder()
+        .min_content_height(240)
+        .vexpand(true)
+        .child(&profiles_box)
+        .build();
+
+    let import_button = gtk::Button::builder()
+        .label("Import selected")
+        .halign(gtk::Align::End)
+        .build();
+
+    let main_container = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(12)
+        .margin_start(24)
+        .margin_end(24)
+        .margin_top(24)
+        .margin_bottom(24)
+        .build();
+
+    main_container.append(&profiles_scroll);
+    main_container.append(&import_button);
+
+    let import_window = gtk::Window::builder()
+        .transient_for(&window)
+        .modal(true)
+        .title("Import from Minecraft Launcher - Copper Launcher")
+        .default_width(480)
+        .child(&main_container)
+        .build();
+
+    import_button.connect_clicked(clone!(
+        #[weak]
+        app,
+        #[weak]
+        import_window,
+        move |_| {
+            for (profile, check) in &profile_checks {
+                if !check.is_active() {
+                    continue;
+                }
+
+                match official::official_import(profile, None) {
+                    Ok(instance_name) => println!("Imported instance: {}", instance_name),
+                    Err(e) => eprintln!("Failed to import profile {}: {}", profile.display_name(), e),
+                }
+            }
+
+            app.activate_action("refresh_instances", None);
+            import_window.close();
+        }
+    ));
+
+    import_window.present();
 }
 
 pub fn on_startup(app: &gtk::Application) {
@@ -266,6 +2949,29 @@ pub fn on_startup(app: &gtk::Application) {
             ver_dropdown.connect_selected_notify(|vdd| {
                 println!("Version selector dropdown. Selected: {}", vdd.selected())
             });
+
+    // Mod loader selector
+    // Loader versions are fetched for selected Minecraft version
+        let loader_selector = LoaderSelector::new();
+
+        let refresh_loader_versions = clone!(
+            #[weak]
+            ver_dropdown,
+            #[strong]
+            loader_selector,
+            move || {
+                if let Some(game_version) = selected_string(&ver_dropdown) {
+                    loader_selector.refresh(&game_version);
+                }
+            }
+        );
+
+        loader_selector.loader_dropdown.connect_selected_notify(clone!(
+            #[strong]
+            refresh_loader_versions,
+            move |_| refresh_loader_versions()
+        ));
+        ver_dropdown.connect_selected_notify(move |_| refresh_loader_versions());
         
 
 
@@ -297,6 +3003,8 @@ pub fn on_startup(app: &gtk::Application) {
 
         // bottom_container.append(&search_button);
         bottom_container.append(&ver_dropdown);
+        bottom_container.append(&loader_selector.loader_dropdown);
+        bottom_container.append(&loader_selector.version_dropdown);
 
         let pushbuttonrightpls = gtk::Box::new(gtk::Orientation::Horizontal, 0);
             pushbuttonrightpls.set_hexpand(true);
@@ -334,6 +3042,8 @@ pub fn on_startup(app: &gtk::Application) {
                 instance_input_field,
                 #[weak]
                 ver_dropdown,
+                #[strong]
+                loader_selector,
                 #[weak]
                 new_instance_window,
                 move |_| {
@@ -352,7 +3062,20 @@ pub fn on_startup(app: &gtk::Application) {
                             .unwrap()
                             .string();
 
-                        instance::instance_create(&instance_name, &version_string);
+                        // Mod loader
+                        let loader = match loader_selector.selected_loader() {
+                            Ok(loader) => loader,
+                            Err(e) => {
+                                println!("{}", e);
+                                return;
+                            }
+                        };
+
+                        instance::instance_create(&instance_name, &version_string, loader);
+
+                        if let Some(app) = new_instance_window.application() {
+                            app.activate_action("refresh_instances", None);
+                        }
 
                         new_instance_window.close();
                     }
@@ -418,16 +3141,46 @@ pub fn on_startup(app: &gtk::Application) {
             let new_instance = gio::MenuItem::new(Some("Create new instance"), Some("app.new_instance")); //Instance creation window
             
             let files_instance = gio::MenuItem::new(Some("Open instances directory"), Some("app.files_instance")); //Opens instances directory in file manager
+
+            let instance_settings = gio::MenuItem::new(Some("Instance settings"), Some("app.instance_settings")); //Settings of selected instance
+
+            let manage_mods = gio::MenuItem::new(Some("Mods"), Some("app.manage_mods")); //Mods of selected instance
+
+            let show_game_log = gio::MenuItem::new(Some("Game log"), Some("app.show_game_log")); //Log console of selected instance
+
+            let browse_modrinth = gio::MenuItem::new(Some("Browse Modrinth"), Some("app.browse_modrinth")); //Installs mods and packs into selected instance
+
+            let browse_curseforge = gio::MenuItem::new(Some("Browse CurseForge"), Some("app.browse_curseforge")); //Installs mods and packs from CurseForge into selected instance
+
+            let import_instance = gio::MenuItem::new(Some("Import instance"), Some("app.import_instance")); //Imports instance from archive
+
+            let import_prism_instance = gio::MenuItem::new(Some("Import Prism Launcher instance"), Some("app.import_prism_instance")); //Imports instance folder from Prism Launcher or MultiMC
+
+            let import_official_profiles = gio::MenuItem::new(Some("Import from Minecraft Launcher"), Some("app.import_official_profiles")); //Imports profiles from official launcher
+
+            let export_instance = gio::MenuItem::new(Some("Export instance"), Some("app.export_instance")); //Exports selected instance into archive
             
             // Other
             let quit_menu_item = gio::MenuItem::new(Some("Quit"), Some("app.quit")); //Quits
 
             let open_preferences = gio::MenuItem::new(Some("Preferences"), Some("app.open_preferences"));
 
+            let manage_skins = gio::MenuItem::new(Some("Skins and capes"), Some("app.manage_skins")); //Skin and cape of active account
+
             // Stuff for bar
             let file_menu = gio::Menu::new();
             file_menu.append_item(&new_instance);
             file_menu.append_item(&files_instance);
+            file_menu.append_item(&instance_settings);
+            file_menu.append_item(&manage_mods);
+            file_menu.append_item(&show_game_log);
+            file_menu.append_item(&browse_modrinth);
+            file_menu.append_item(&browse_curseforge);
+            file_menu.append_item(&import_instance);
+            file_menu.append_item(&import_prism_instance);
+            file_menu.append_item(&import_official_profiles);
+            file_menu.append_item(&export_instance);
+            file_menu.append_item(&manage_skins);
             file_menu.append_item(&open_preferences);
             file_menu.append_item(&quit_menu_item);
  
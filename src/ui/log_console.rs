@@ -0,0 +1,510 @@
+// This is where game log console is shown.
+
+// Copper Launcher is licensed under GNU General Public License v3.0.
+// Copyright (c) 2026 Suverent_Shiro
+//
+// See LICENSE file for more details.
+
+// Console reads new lines from game session few times a second
+// Filters rebuild the text, search only highlights and jumps between matches
+// Logger and thread filters only match log4j events, plain output lines don't have them
+
+
+use gtk::prelude::*;
+use gtk::{glib};
+use glib::{clone};
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::launcher::paste;
+use crate::minecraft::game_process::{self, GameSession, LogLevel, LogLine, ProcessState};
+
+
+// How often console looks for new lines
+const REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+
+// Minimum level filter, index matches dropdown position
+const LEVEL_FILTERS: &[&str] = &["All", "Debug", "Info", "Warn", "Error"];
+
+fn filter_level(index: u32) -> LogLevel {
+    match index {
+        0 => LogLevel::Trace,
+        1 => LogLevel::Debug,
+        2 => LogLevel::Info,
+        3 => LogLevel::Warn,
+        _ => LogLevel::Error,
+    }
+}
+
+// Time filter, index matches dropdown position
+const TIME_FILTERS: &[(&str, Option<u64>)] = &[
+    ("Whole session", None),
+    ("Last minute", Some(60)),
+    ("Last 5 minutes", Some(5 * 60)),
+    ("Last 15 minutes", Some(15 * 60)),
+    ("Last hour", Some(60 * 60)),
+];
+
+// Which lines are shown
+struct LogFilter {
+    min_level: LogLevel,
+    logger: String,
+    thread: String,
+    // Milliseconds since Unix epoch
+    since: Option<u64>,
+}
+
+impl LogFilter {
+    fn matches(&self, line: &LogLine) -> bool {
+        let contains = |field: &Option<String>, query: &str| {
+            query.is_empty() || field.as_ref().is_some_and(|f| f.to_lowercase().contains(&query.to_lowercase()))
+        };
+
+        line.level >= self.min_level
+            && contains(&line.logger, &self.logger)
+            && contains(&line.thread, &self.thread)
+            && self.since.is_none_or(|since| line.timestamp >= since)
+    }
+}
+
+// Tag for every level, named after it
+fn create_tags(buffer: &gtk::TextBuffer) {
+    let table = buffer.tag_table();
+
+    for (level, color) in [
+        (LogLevel::Trace, Some("#77767b")),
+        (LogLevel::Debug, Some("#77767b")),
+        (LogLevel::Info, None),
+        (LogLevel::Warn, Some("#c64600")),
+        (LogLevel::Error, Some("#e01b24")),
+        (LogLevel::Fatal, Some("#a51d2d")),
+    ] {
+        let tag = gtk::TextTag::builder()
+            .name(level.label())
+            .build();
+        if let Some(color) = color {
+            tag.set_foreground(Some(color));
+        }
+        if level == LogLevel::Fatal {
+            tag.set_weight(700);
+        }
+        table.add(&tag);
+    }
+
+    let search_tag = gtk::TextTag::builder()
+        .name("search")
+        .background("#f6d32d")
+        .foreground("#000000")
+        .build();
+    table.add(&search_tag);
+}
+
+fn append_lines(buffer: &gtk::TextBuffer, lines: &[LogLine], filter: &LogFilter) {
+    for line in lines.iter().filter(|l| filter.matches(l)) {
+        let mut end = buffer.end_iter();
+        buffer.insert_with_tags_by_name(&mut end, &format!("{}\n", line.text), &[line.level.label()]);
+    }
+}
+
+// Marks every match of query
+fn highlight(buffer: &gtk::TextBuffer, query: &str) {
+    let (start, end) = buffer.bounds();
+    buffer.remove_tag_by_name("search", &start, &end);
+
+    if query.is_empty() {
+        return;
+    }
+
+    let mut from = start;
+    while let Some((match_start, match_end)) = from.forward_search(query, gtk::TextSearchFlags::CASE_INSENSITIVE, None) {
+        buffer.apply_tag_by_name("search", &match_start, &match_end);
+        from = match_end;
+    }
+}
+
+// Selects next match after selection, goes around to the start
+fn find_next(text_view: &gtk::TextView, query: &str) -> bool {
+    let buffer = text_view.buffer();
+    if query.is_empty() {
+        return false;
+    }
+
+    let from = buffer.selection_bounds().map(|(_, end)| end).unwrap_or_else(|| buffer.iter_at_mark(&buffer.get_insert()));
+    let found = from
+        .forward_search(query, gtk::TextSearchFlags::CASE_INSENSITIVE, None)
+        .or_else(|| buffer.start_iter().forward_search(query, gtk::TextSearchFlags::CASE_INSENSITIVE, None));
+
+    let Some((mut match_start, match_end)) = found else { return false };
+    buffer.select_range(&match_start, &match_end);
+    text_view.scroll_to_iter(&mut match_start, 0.1, false, 0.0, 0.0);
+
+    true
+}
+
+
+// Log console of game session, stays usable after game exits
+pub fn log_console_window(app: &gtk::Application, session: Arc<GameSession>) {
+    let Some(window) = app.active_window() else { return };
+
+    // Log view
+    let buffer = gtk::TextBuffer::new(None);
+        create_tags(&buffer);
+
+    // Stays at the end, for following new lines
+    let end_mark = buffer.create_mark(None, &buffer.end_iter(), false);
+
+    let text_view = gtk::TextView::builder()
+        .buffer(&buffer)
+        .editable(false)
+        .cursor_visible(false)
+        .monospace(true)
+        .wrap_mode(gtk::WrapMode::WordChar)
+        .build();
+
+    let log_scroll = gtk::ScrolledWindow::builder()
+        .child(&text_view)
+        .vexpand(true)
+        .hexpand(true)
+        .build();
+
+    // Toolbar
+    let level_dropdown = gtk::DropDown::from_strings(LEVEL_FILTERS);
+        level_dropdown.set_selected(0);
+
+    let search_entry = gtk::SearchEntry::builder()
+        .placeholder_text("Search")
+        .hexpand(true)
+        .build();
+
+    let follow_check = gtk::CheckButton::builder()
+        .label("Follow")
+        .active(true)
+        .build();
+
+    let copy_button = gtk::Button::builder()
+        .label("Copy")
+        .tooltip_text("Copies selection, or whole shown log")
+        .build();
+
+    let save_button = gtk::Button::builder()
+        .label("Save")
+        .build();
+
+    let upload_button = gtk::Button::builder()
+        .label("Upload")
+        .build();
+
+    // Filters of log4j event fields
+    let logger_entry = gtk::Entry::builder()
+        .placeholder_text("Logger")
+        .hexpand(true)
+        .build();
+
+    let thread_entry = gtk::Entry::builder()
+        .placeholder_text("Thread")
+        .hexpand(true)
+        .build();
+
+    let time_dropdown = gtk::DropDown::from_strings(&TIME_FILTERS.iter().map(|(label, _)| *label).collect::<Vec<&str>>());
+        time_dropdown.set_selected(0);
+
+    let filter_bar = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(6)
+        .build();
+    filter_bar.append(&level_dropdown);
+    filter_bar.append(&logger_entry);
+    filter_bar.append(&thread_entry);
+    filter_bar.append(&time_dropdown);
+
+    let toolbar = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(6)
+        .build();
+    toolbar.append(&search_entry);
+    toolbar.append(&follow_check);
+    toolbar.append(&copy_button);
+    toolbar.append(&save_button);
+    toolbar.append(&upload_button);
+
+    // Process state
+    let state_label = gtk::Label::builder()
+        .label(session.state().label())
+        .halign(gtk::Align::Start)
+        .hexpand(true)
+        .build();
+
+    let kill_button = gtk::Button::builder()
+        .label("Kill")
+        .css_classes(["destructive-action"])
+        .sensitive(session.is_running())
+        .build();
+
+    let state_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(12)
+        .build();
+    state_box.append(&state_label);
+    state_box.append(&kill_button);
+
+    let main_container = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(12)
+        .margin_start(12)
+        .margin_end(12)
+        .margin_top(12)
+        .margin_bottom(12)
+        .build();
+
+    main_container.append(&toolbar);
+    main_container.append(&filter_bar);
+    main_container.append(&log_scroll);
+    main_container.append(&state_box);
+
+    let console_window = gtk::Window::builder()
+        .transient_for(&window)
+        .title(format!("{} log - Copper Launcher", session.instance_name))
+        .default_width(960)
+        .default_height(600)
+        .child(&main_container)
+        .build();
+
+    // Number of next line that is not in buffer yet
+    let next_line = Rc::new(Cell::new(0));
+
+    // Filter from current state of filter fields
+    let current_filter = clone!(
+        #[weak]
+        level_dropdown,
+        #[weak]
+        logger_entry,
+        #[weak]
+        thread_entry,
+        #[weak]
+        time_dropdown,
+        #[upgrade_or_panic]
+        move || LogFilter {
+            min_level: filter_level(level_dropdown.selected()),
+            logger: logger_entry.text().trim().to_string(),
+            thread: thread_entry.text().trim().to_string(),
+            since: TIME_FILTERS
+                .get(time_dropdown.selected() as usize)
+                .and_then(|(_, seconds)| *seconds)
+                .map(|seconds| game_process::now_millis().saturating_sub(seconds * 1000)),
+        }
+    );
+
+    // Adds new lines, scrolls to the end when following
+    let pull_lines = clone!(
+        #[weak]
+        buffer,
+        #[weak]
+        text_view,
+        #[weak]
+        follow_check,
+        #[weak]
+        end_mark,
+        #[strong]
+        session,
+        #[strong]
+        next_line,
+        move || {
+            let (lines, next) = session.lines_since(next_line.get());
+            next_line.set(next);
+            if lines.is_empty() {
+                return;
+            }
+
+            append_lines(&buffer, &lines, &current_filter());
+            if follow_check.is_active() {
+                text_view.scroll_to_mark(&end_mark, 0.0, false, 0.0, 0.0);
+            }
+        }
+    );
+    pull_lines();
+
+    // Filter change shows whole log again
+    let refilter = clone!(
+        #[weak]
+        buffer,
+        #[weak]
+        search_entry,
+        #[strong]
+        next_line,
+        #[strong]
+        pull_lines,
+        move || {
+            buffer.set_text("");
+            next_line.set(0);
+            pull_lines();
+            highlight(&buffer, &search_entry.text());
+        }
+    );
+
+    level_dropdown.connect_selected_notify(clone!(
+        #[strong]
+        refilter,
+        move |_| refilter()
+    ));
+    time_dropdown.connect_selected_notify(clone!(
+        #[strong]
+        refilter,
+        move |_| refilter()
+    ));
+    logger_entry.connect_changed(clone!(
+        #[strong]
+        refilter,
+        move |_| refilter()
+    ));
+    thread_entry.connect_changed(move |_| refilter());
+
+    search_entry.connect_search_changed(clone!(
+        #[weak]
+        buffer,
+        move |entry| highlight(&buffer, &entry.text())
+    ));
+
+    search_entry.connect_activate(clone!(
+        #[weak]
+        text_view,
+        #[weak]
+        follow_check,
+        move |entry| {
+            if find_next(&text_view, &entry.text()) {
+                follow_check.set_active(false);
+            }
+        }
+    ));
+
+    copy_button.connect_clicked(clone!(
+        #[weak]
+        buffer,
+        #[weak]
+        text_view,
+        move |_| {
+            let (start, end) = buffer.selection_bounds().unwrap_or_else(|| buffer.bounds());
+            text_view.clipboard().set_text(&buffer.text(&start, &end, false));
+        }
+    ));
+
+    save_button.connect_clicked(clone!(
+        #[weak]
+        console_window,
+        #[strong]
+        session,
+        move |_| {
+            let dialog = gtk::FileDialog::builder()
+                .title("Save log")
+                .modal(true)
+                .initial_name(format!("{}.log", session.instance_name))
+                .build();
+
+            dialog.save(
+                Some(&console_window),
+                None::<&gio::Cancellable>,
+                clone!(
+                    #[strong]
+                    session,
+                    move |result| {
+                        let Ok(file) = result else { return };
+                        let Some(log_path) = file.path() else { return };
+
+                        match std::fs::write(&log_path, session.log_text()) {
+                            Ok(_) => println!("Log saved to {}", log_path.display()),
+                            Err(e) => eprintln!("Failed to save log: {}", e),
+                        }
+                    }
+                )
+            );
+        }
+    ));
+
+    // Uploaded log is public, so user confirms it first
+    upload_button.connect_clicked(clone!(
+        #[weak]
+        console_window,
+        #[strong]
+        session,
+        move |upload_button| {
+            let confirm_dialog = gtk::AlertDialog::builder()
+                .modal(true)
+                .message("Upload log to mclo.gs?")
+                .detail("Anyone with the link will be able to read it.")
+                .buttons(["Cancel", "Upload"])
+                .cancel_button(0)
+                .default_button(1)
+                .build();
+
+            confirm_dialog.choose(Some(&console_window), None::<&gio::Cancellable>, clone!(
+                #[weak]
+                console_window,
+                #[weak]
+                upload_button,
+                #[strong]
+                session,
+                move |result| {
+                    if result != Ok(1) {
+                        return;
+                    }
+
+                    upload_button.set_sensitive(false);
+                    let log_text = session.log_text();
+
+                    glib::MainContext::default().spawn_local(async move {
+                        let upload = gio::spawn_blocking(move || paste::upload_log(&log_text).map_err(|e| e.to_string())).await;
+                        upload_button.set_sensitive(true);
+
+                        let (message, detail) = match upload {
+                            Ok(Ok(url)) => {
+                                console_window.clipboard().set_text(&url);
+                                ("Log uploaded".to_string(), format!("{}\n\nLink was copied to clipboard.", url))
+                            }
+                            Ok(Err(e)) => ("Failed to upload log".to_string(), e),
+                            Err(_) => ("Failed to upload log".to_string(), "Upload thread panicked".to_string()),
+                        };
+
+                        gtk::AlertDialog::builder()
+                            .modal(true)
+                            .message(message)
+                            .detail(detail)
+                            .build()
+                            .show(Some(&console_window));
+                    });
+                }
+            ));
+        }
+    ));
+
+    kill_button.connect_clicked(clone!(
+        #[strong]
+        session,
+        move |_| {
+            if let Err(e) = session.kill() {
+                eprintln!("Failed to kill game: {}", e);
+            }
+        }
+    ));
+
+    // Stops when console is closed
+    glib::timeout_add_local(REFRESH_INTERVAL, clone!(
+        #[weak]
+        state_label,
+        #[weak]
+        kill_button,
+        #[upgrade_or]
+        glib::ControlFlow::Break,
+        move || {
+            pull_lines();
+
+            let state = session.state();
+            state_label.set_label(&state.label());
+            kill_button.set_sensitive(state == ProcessState::Running);
+
+            glib::ControlFlow::Continue
+        }
+    ));
+
+    console_window.present();
+}